@@ -0,0 +1,264 @@
+use std::{fmt::Display, str::FromStr};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+// Name of the file next to the inputs of a day listing the answers submitted for it
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+#[error("invalid answer on line {line}: {reason}")]
+#[diagnostic(
+    code(aoc::invalid_answer),
+    help("answers are written as `part1: 1234 - correct` (or `too low`, `too high`, `incorrect`)")
+)]
+pub struct AnswersError {
+    pub line: usize,
+    pub reason: String,
+}
+
+// What the puzzle said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    Correct,
+    TooLow,
+    TooHigh,
+    Incorrect,
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Accept `too-low` too, it's easier to type on the command line
+        match s.trim().replace(['-', '_'], " ").as_str() {
+            "correct" => Ok(Self::Correct),
+            "too low" => Ok(Self::TooLow),
+            "too high" => Ok(Self::TooHigh),
+            "incorrect" => Ok(Self::Incorrect),
+            other => Err(format!("unknown verdict `{}`", other)),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = match self {
+            Self::Correct => "correct",
+            Self::TooLow => "too low",
+            Self::TooHigh => "too high",
+            Self::Incorrect => "incorrect",
+        };
+        write!(f, "{}", verdict)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "part{}: {} - {}", self.part, self.answer, self.verdict)
+    }
+}
+
+// Why an answer is unlikely to be right, judging by earlier submissions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    // The exact same answer was already rejected
+    Rejected(Verdict),
+    // The answer is not above one that was too low
+    NotAbove(String),
+    // The answer is not below one that was too high
+    NotBelow(String),
+    // A different answer was already accepted
+    NotAccepted(String),
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rejected(verdict) => write!(f, "this answer was already rejected ({})", verdict),
+            Self::NotAbove(bound) => write!(f, "{} was too low, the answer must be higher", bound),
+            Self::NotBelow(bound) => write!(f, "{} was too high, the answer must be lower", bound),
+            Self::NotAccepted(correct) => write!(f, "the accepted answer is {}", correct),
+        }
+    }
+}
+
+// The answers submitted for both parts of a day, in the order they were submitted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub submissions: Vec<Submission>,
+}
+
+impl Answers {
+    // Parses lines like `part2: 250665248 - correct`, skipping blank lines and `#` comments
+    pub fn parse(input: &str) -> Result<Self, AnswersError> {
+        let mut submissions = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |reason: &str| AnswersError {
+                line: idx + 1,
+                reason: reason.to_string(),
+            };
+
+            let (part, rest) = line.split_once(':').ok_or_else(|| error("missing `:`"))?;
+            let part = part
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse::<u8>().ok())
+                .ok_or_else(|| error("expected `part1` or `part2`"))?;
+            let (answer, verdict) = rest
+                .split_once(" - ")
+                .ok_or_else(|| error("missing ` - ` before the verdict"))?;
+            let verdict = verdict.parse::<Verdict>().map_err(|e| error(&e))?;
+
+            submissions.push(Submission {
+                part,
+                answer: answer.trim().to_string(),
+                verdict,
+            });
+        }
+        Ok(Self { submissions })
+    }
+
+    pub fn for_part(&self, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions.iter().filter(move |s| s.part == part)
+    }
+
+    // The accepted answer for a part, if it has been solved
+    pub fn correct(&self, part: u8) -> Option<&str> {
+        self.for_part(part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    // The range a numeric answer has to be in, learned from the "too low" and "too high"
+    // verdicts. Both bounds are exclusive.
+    pub fn bounds(&self, part: u8) -> (Option<i128>, Option<i128>) {
+        let mut lower = None;
+        let mut upper = None;
+        for submission in self.for_part(part) {
+            let Ok(value) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+            match submission.verdict {
+                Verdict::TooLow => lower = lower.max(Some(value)),
+                Verdict::TooHigh => upper = Some(upper.map_or(value, |u: i128| u.min(value))),
+                _ => {}
+            }
+        }
+        (lower, upper)
+    }
+
+    // Checks a new answer against everything already known about the part
+    pub fn check(&self, part: u8, answer: &str) -> Vec<Warning> {
+        if let Some(known) = self
+            .for_part(part)
+            .find(|s| s.answer == answer && s.verdict != Verdict::Correct)
+        {
+            return vec![Warning::Rejected(known.verdict)];
+        }
+
+        let mut warnings = Vec::new();
+        if let Ok(value) = answer.parse::<i128>() {
+            let (lower, upper) = self.bounds(part);
+            if let Some(lower) = lower.filter(|&lower| value <= lower) {
+                warnings.push(Warning::NotAbove(lower.to_string()));
+            }
+            if let Some(upper) = upper.filter(|&upper| value >= upper) {
+                warnings.push(Warning::NotBelow(upper.to_string()));
+            }
+        }
+        if let Some(correct) = self.correct(part).filter(|&correct| correct != answer) {
+            warnings.push(Warning::NotAccepted(correct.to_string()));
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "# Day 7
+             part1: 250120186 - correct
+
+             part2: 250475140 - too low
+             part2: 250665248 - correct # finally",
+        )
+        .unwrap();
+
+        assert_eq!(3, answers.submissions.len());
+        assert_eq!(Some("250120186"), answers.correct(1));
+        assert_eq!(Some("250665248"), answers.correct(2));
+        assert_eq!(2, answers.for_part(2).count());
+        assert_eq!(
+            "part2: 250475140 - too low",
+            answers.submissions[1].to_string()
+        );
+        assert_eq!(None, Answers::default().correct(1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Answers::parse("part1: 12 - correct\npart3 12 - correct").unwrap_err();
+        assert_eq!(2, err.line);
+
+        assert!(Answers::parse("day1: 12 - correct").is_err());
+        assert!(Answers::parse("part1: 12").is_err());
+        assert!(Answers::parse("part1: 12 - maybe").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(
+            "part1: 5466 - too low
+             part1: 6055 - too low
+             part1: 7000 - too high
+             part1: 6122 - incorrect
+             part2: 10 - correct",
+        )
+        .unwrap();
+
+        assert_eq!((Some(6055), Some(7000)), answers.bounds(1));
+        assert_eq!((None, None), answers.bounds(2));
+
+        assert!(answers.check(1, "6410").is_empty());
+        assert_eq!(
+            vec![Warning::Rejected(Verdict::Incorrect)],
+            answers.check(1, "6122")
+        );
+        assert_eq!(
+            vec![Warning::Rejected(Verdict::TooLow)],
+            answers.check(1, "5466")
+        );
+        assert_eq!(
+            vec![Warning::NotAbove("6055".to_string())],
+            answers.check(1, "6000")
+        );
+        assert_eq!(
+            vec![Warning::NotBelow("7000".to_string())],
+            answers.check(1, "7001")
+        );
+
+        assert!(answers.check(2, "10").is_empty());
+        assert_eq!(
+            vec![Warning::NotAccepted("10".to_string())],
+            answers.check(2, "11")
+        );
+        assert_eq!(Ok(Verdict::TooHigh), "too-high".parse());
+    }
+}
@@ -0,0 +1,50 @@
+// Registers divan benchmarks for a part in a module of the given name: `run` times parsing
+// and solving together, the way `process` does, while `parse` and `solve` time each of them
+// on their own. Parameters that should differ from the defaults are given as `key = value`.
+//
+// It expands in the bench crate, which is the one depending on divan, so the solution has
+// to be given with its full path, like `day_05::part1::Part1`.
+#[macro_export]
+macro_rules! bench_part {
+    ($name:ident, $solution:ty $(, $key:ident = $value:expr)* $(,)?) => {
+        mod $name {
+            use $crate::Solution as _;
+
+            fn input() -> String {
+                $crate::InputLoader::new(
+                    <$solution as $crate::Solution>::DAY,
+                    <$solution as $crate::Solution>::PART,
+                )
+                .dir(env!("CARGO_MANIFEST_DIR"))
+                .load()
+                .unwrap()
+                .text
+            }
+
+            fn params() -> $crate::Params {
+                let params = $crate::Params::new() $(.with(stringify!($key), $value))*;
+                <$solution>::params(&params).unwrap()
+            }
+
+            #[divan::bench]
+            fn run(bencher: divan::Bencher) {
+                let (input, params) = (input(), params());
+                bencher.bench(|| <$solution>::run(divan::black_box(&input), &params).unwrap());
+            }
+
+            #[divan::bench]
+            fn parse(bencher: divan::Bencher) {
+                let input = input();
+                bencher.bench(|| <$solution>::parse(divan::black_box(&input)).unwrap());
+            }
+
+            #[divan::bench]
+            fn solve(bencher: divan::Bencher) {
+                let (input, params) = (input(), params());
+                bencher
+                    .with_inputs(|| <$solution>::parse(&input).unwrap())
+                    .bench_values(|parsed| <$solution>::solve(parsed, &params).unwrap());
+            }
+        }
+    };
+}
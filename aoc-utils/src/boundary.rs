@@ -0,0 +1,56 @@
+use thiserror::Error;
+
+use crate::point::Point;
+
+// Defines what a grid returns when a cell outside of its bounds is accessed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Boundary {
+    // Every out of bounds cell has the grid's default value
+    #[default]
+    Default,
+    // The grid repeats infinitely in every direction (toroidal wrapping)
+    Wrap,
+    // Coordinates are clamped to the nearest edge cell
+    Clamp,
+    // Out of bounds access is an error (`try_cell` returns it, `cell` panics)
+    Strict,
+}
+
+impl Boundary {
+    // Maps a coordinate on an axis of the given size into the grid, if possible
+    pub fn resolve(&self, idx: i64, size: usize) -> Option<usize> {
+        let size = size as i64;
+        match self {
+            Boundary::Wrap if size > 0 => Some(idx.rem_euclid(size) as usize),
+            Boundary::Clamp if size > 0 => Some(idx.clamp(0, size - 1) as usize),
+            _ if idx < 0 || idx >= size => None,
+            _ => Some(idx as usize),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("point {point} is out of bounds of a {width}x{height} grid")]
+pub struct OutOfBounds {
+    pub point: Point<i64>,
+    pub width: usize,
+    pub height: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(Some(2), Boundary::Default.resolve(2, 3));
+        assert_eq!(None, Boundary::Default.resolve(3, 3));
+        assert_eq!(None, Boundary::Strict.resolve(-1, 3));
+        assert_eq!(Some(0), Boundary::Wrap.resolve(3, 3));
+        assert_eq!(Some(2), Boundary::Wrap.resolve(-1, 3));
+        assert_eq!(Some(1), Boundary::Wrap.resolve(-5, 3));
+        assert_eq!(Some(0), Boundary::Clamp.resolve(-7, 3));
+        assert_eq!(Some(2), Boundary::Clamp.resolve(100, 3));
+        assert_eq!(None, Boundary::Wrap.resolve(0, 0));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    grid::{Grid, Row},
+    parse_error::ParseError,
+    point::Point,
+};
+
+// A grid where each cell is a char, the most common shape of puzzle input
+pub type CharMap = Grid<char>;
+pub type CharRow<'a> = Row<'a, char>;
+
+impl CharMap {
+    pub fn from_iter<T>(lines: impl Iterator<Item = T>, default: char) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let rows = lines.map(|line| line.as_ref().chars().collect::<Vec<char>>());
+        Self::from_rows(rows, default)
+    }
+
+    pub fn from_str_with_trim(input: &str, default: char) -> Self {
+        let iter = input.lines().map(|line| line.trim());
+        Self::from_iter(iter, default)
+    }
+
+    pub fn from_str(input: &str, default: char) -> Self {
+        Self::from_iter(input.lines(), default)
+    }
+
+    // Like `from_str_with_trim`, but reports an empty input or a cell the map can't have
+    // instead of building a map the solution would choke on
+    pub fn parse(
+        input: &str,
+        default: char,
+        valid: impl Fn(char) -> bool,
+    ) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            return Err(ParseError::new(
+                "empty map",
+                input,
+                "expected at least one row",
+            ));
+        }
+
+        for line in &lines {
+            if let Some((idx, c)) = line.char_indices().find(|(_, c)| !valid(*c)) {
+                let at = &line[idx..idx + c.len_utf8()];
+                return Err(ParseError::new(
+                    format!("unexpected `{}` in the map", c),
+                    at,
+                    "not a valid cell",
+                ));
+            }
+        }
+        Ok(Self::from_iter(lines.into_iter(), default))
+    }
+
+    pub fn cell_digit_for_point(&self, point: &Point<i64>) -> i64 {
+        let cell = self.cell_for_point(point);
+        (*cell as i64) - ('0' as i64)
+    }
+
+    // Converts a map of digits into a grid of numbers (non-digit cells become 0)
+    pub fn to_digits(&self) -> Grid<u8> {
+        self.map(|c| c.to_digit(10).unwrap_or(0) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "#..
+                          .#.
+                          ..@
+                          ...";
+
+    #[test]
+    fn test_from_str_with_trim() {
+        let map = CharMap::from_str_with_trim(SAMPLE, ' ');
+        assert_eq!(3, map.width());
+        assert_eq!(4, map.height());
+        assert_eq!(Point::new(2, 3), map.bottom_right());
+        assert_eq!('#', *map.cell(1, 1));
+        assert_eq!(Some(Point::new(2, 2)), map.find('@'));
+        assert_eq!(None, map.find('!'));
+        assert_eq!(vec![Point::new(0, 0), Point::new(1, 1)], map.find_all('#'));
+        assert_eq!(9, map.count('.'));
+    }
+
+    #[test]
+    fn test_parse() {
+        let map = CharMap::parse(SAMPLE, ' ', |c| "#.@".contains(c)).unwrap();
+        assert_eq!(CharMap::from_str_with_trim(SAMPLE, ' '), map);
+
+        let rows = SAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(rows.join("\n"), map.to_string());
+
+        let input = "#..\n.x.\n";
+        let err = CharMap::parse(input, ' ', |c| "#.".contains(c)).unwrap_err();
+        assert_eq!("unexpected `x` in the map", err.message);
+        assert_eq!(
+            miette::SourceSpan::from((5, 1)),
+            err.with_source(input).span
+        );
+
+        assert!(CharMap::parse(" \n", ' ', |_| true).is_err());
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let map = CharMap::from_str_with_trim(SAMPLE, ' ');
+        assert_eq!(' ', *map.cell(-1, 0));
+        assert_eq!(' ', *map.cell(3, 0));
+        assert_eq!(' ', *map.cell(0, 4));
+        assert!(map.out_of_bounds(&Point::new(3, 3)));
+        assert!(!map.out_of_bounds(&Point::new(2, 3)));
+        assert_eq!(vec![' ', '#', '.', '.', ' '], map.line(0).slice(&(-1..4)));
+
+        let mut map = map;
+        map.set_cell_for_point(&Point::new(5, 5), 'X');
+        assert_eq!(0, map.count('X'));
+    }
+
+    #[test]
+    fn test_padding() {
+        let map = CharMap::from_str("ab\ncd", '.').with_padding(1, 2);
+        assert_eq!(4, map.width());
+        assert_eq!(6, map.height());
+        assert_eq!('a', *map.cell(1, 2));
+        assert_eq!('d', *map.cell(2, 3));
+        assert_eq!(20, map.count('.'));
+    }
+
+    #[test]
+    fn test_transformations() {
+        let map = CharMap::from_str("ab\ncd\nef", '.');
+        assert_eq!(CharMap::from_str("ace\nbdf", '.'), map.transpose());
+        assert_eq!(CharMap::from_str("ba\ndc\nfe", '.'), map.flip_horizontal());
+        assert_eq!(CharMap::from_str("bdf\nace", '.'), map.rotate_left());
+        assert_eq!(CharMap::from_str("eca\nfdb", '.'), map.rotate_right());
+        assert_eq!(map, map.rotate_right().rotate_left());
+    }
+
+    #[test]
+    fn test_hash64() {
+        let mut map = CharMap::from_str_with_trim(SAMPLE, ' ');
+        let original_hash = map.hash64();
+        assert_eq!(original_hash, map.clone().hash64());
+        map.set_cell(0, 0, '.');
+        assert_ne!(original_hash, map.hash64());
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut map = CharMap::from_str("...\n###\n...", '.');
+        map.flood_fill(Point::new(0, 0), 'O');
+        assert_eq!(CharMap::from_str("OOO\n###\n...", '.'), map);
+    }
+
+    #[test]
+    fn test_cell_digit_for_point() {
+        let map = CharMap::from_str("19\n05", '.');
+        assert_eq!(9, map.cell_digit_for_point(&Point::new(1, 0)));
+        assert_eq!(5, map.cell_digit_for_point(&map.bottom_right()));
+        assert_eq!(&[1, 9, 0, 5], map.to_digits().cells());
+    }
+
+    #[test]
+    fn test_rows() {
+        let map = CharMap::from_str("ab\nc", '.');
+        assert_eq!(2, map.width());
+        let lines = map
+            .lines()
+            .map(|line| line.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["ab", "c."], lines);
+        assert_eq!(vec!['.', 'a', 'b', '.'], map.line(0).slice(&(-1..3)));
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+// The history of an iterated simulation up to the point where it started repeating itself:
+// the first `tail` states are never seen again, after that the next `len` states repeat forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    pub tail: usize,
+    pub len: usize,
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    // State after the given number of iterations (0 being the initial state)
+    pub fn state_at(&self, iterations: usize) -> &S {
+        if iterations < self.states.len() {
+            return &self.states[iterations];
+        }
+        &self.states[self.tail + (iterations - self.tail) % self.len]
+    }
+
+    // All distinct states in the order they were reached, starting with the initial one
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+fn hash_of<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Keeps applying the step function to the initial state until a state repeats.
+// States are bucketed by hash, but always compared in full, so hash collisions cannot
+// produce a bogus cycle. The state space must be finite, otherwise this never returns.
+#[tracing::instrument(skip_all)]
+pub fn find_cycle<S, F>(start: S, mut step: F) -> Cycle<S>
+where
+    S: Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut states = vec![start];
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    seen.insert(hash_of(&states[0]), vec![0]);
+
+    loop {
+        let next = tracing::trace_span!("step", iteration = states.len())
+            .in_scope(|| step(states.last().unwrap()));
+        let bucket = seen.entry(hash_of(&next)).or_default();
+        if let Some(&tail) = bucket.iter().find(|&&idx| states[idx] == next) {
+            let len = states.len() - tail;
+            return Cycle { tail, len, states };
+        }
+        bucket.push(states.len());
+        states.push(next);
+    }
+}
+
+// State of the simulation after the given number of iterations. Uses cycle detection
+// so it stays fast even for a huge number of iterations.
+pub fn state_after<S, F>(start: S, step: F, iterations: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    find_cycle(start, step).state_at(iterations).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hashes every value to the same bucket to make sure we never trust the hash alone
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Colliding(u32);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            0.hash(state);
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+        let cycle = find_cycle(0u32, |s| if *s == 4 { 2 } else { s + 1 });
+        assert_eq!(2, cycle.tail);
+        assert_eq!(3, cycle.len);
+        assert_eq!(&[0, 1, 2, 3, 4], cycle.states());
+    }
+
+    #[test]
+    fn test_pure_cycle() {
+        let cycle = find_cycle(0u32, |s| (s + 1) % 7);
+        assert_eq!(0, cycle.tail);
+        assert_eq!(7, cycle.len);
+        assert_eq!(&6, cycle.state_at(1_000_000_000));
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let cycle = find_cycle(10u32, |s| s.saturating_sub(3));
+        assert_eq!(4, cycle.tail);
+        assert_eq!(1, cycle.len);
+        assert_eq!(&0, cycle.state_at(usize::MAX));
+    }
+
+    #[test]
+    fn test_state_after() {
+        let step = |s: &u32| if *s == 4 { 2 } else { s + 1 };
+        for n in 0..20 {
+            let mut expected = 0;
+            for _ in 0..n {
+                expected = step(&expected);
+            }
+            assert_eq!(expected, state_after(0, step, n));
+        }
+    }
+
+    #[test]
+    fn test_hash_collisions() {
+        let cycle = find_cycle(Colliding(0), |s| Colliding((s.0 + 1) % 5));
+        assert_eq!(0, cycle.tail);
+        assert_eq!(5, cycle.len);
+        assert_eq!(&Colliding(2), cycle.state_at(12));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::point::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Direction {
+    // The four cardinal directions
+    pub fn each() -> Vec<Self> {
+        vec![Self::North, Self::South, Self::West, Self::East]
+    }
+
+    pub fn diagonals() -> Vec<Self> {
+        vec![
+            Self::NorthEast,
+            Self::NorthWest,
+            Self::SouthEast,
+            Self::SouthWest,
+        ]
+    }
+
+    // All eight directions, clockwise starting from North
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::North,
+            Self::NorthEast,
+            Self::East,
+            Self::SouthEast,
+            Self::South,
+            Self::SouthWest,
+            Self::West,
+            Self::NorthWest,
+        ]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            Self::NorthEast | Self::NorthWest | Self::SouthEast | Self::SouthWest
+        )
+    }
+
+    pub fn delta(&self) -> Point<i64> {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::East => Point::new(1, 0),
+            Direction::NorthEast => Point::new(1, -1),
+            Direction::NorthWest => Point::new(-1, -1),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::SouthWest => Point::new(-1, 1),
+        }
+    }
+
+    // Rotates the direction 90 degrees counter-clockwise
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+            Direction::NorthEast => Direction::NorthWest,
+            Direction::NorthWest => Direction::SouthWest,
+            Direction::SouthWest => Direction::SouthEast,
+            Direction::SouthEast => Direction::NorthEast,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.opposite().turn_left()
+    }
+
+    pub fn to_char(&self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::South => 'v',
+            Direction::West => '<',
+            Direction::East => '>',
+            Direction::NorthEast | Direction::SouthWest => '/',
+            Direction::NorthWest | Direction::SouthEast => '\\',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::East, Direction::North.turn_right());
+        assert_eq!(Direction::South, Direction::North.opposite());
+        for dir in Direction::each() {
+            assert_eq!(dir, dir.turn_left().turn_right());
+            assert_eq!(dir, dir.opposite().opposite());
+        }
+    }
+
+    #[test]
+    fn test_diagonal_turns() {
+        assert_eq!(Direction::NorthWest, Direction::NorthEast.turn_left());
+        assert_eq!(Direction::SouthEast, Direction::NorthEast.turn_right());
+        assert_eq!(Direction::SouthWest, Direction::NorthEast.opposite());
+        assert!(Direction::diagonals().iter().all(|dir| dir.is_diagonal()));
+        assert!(!Direction::each().iter().any(|dir| dir.is_diagonal()));
+    }
+
+    #[test]
+    fn test_all() {
+        let all = Direction::all();
+        assert_eq!(8, all.len());
+        for (i, dir) in all.iter().enumerate() {
+            // Each quarter turn skips over one neighbouring direction
+            assert_eq!(all[(i + 2) % 8], dir.turn_right());
+        }
+    }
+
+    #[test]
+    fn test_delta() {
+        for dir in Direction::all() {
+            assert_eq!(Point::new(0, 0), dir.delta() + dir.opposite().delta());
+            assert_eq!(Point::new(0, 0).neighbour(dir), dir.delta());
+        }
+    }
+
+    #[test]
+    fn test_to_char() {
+        let chars = Direction::each()
+            .iter()
+            .map(|dir| dir.to_char())
+            .collect::<String>();
+        assert_eq!("^v<>", chars);
+    }
+}
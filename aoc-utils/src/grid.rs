@@ -0,0 +1,523 @@
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+
+use crate::{
+    boundary::{Boundary, OutOfBounds},
+    point::Point,
+};
+
+// A read-only view into a single row of a grid
+// Behaves like a slice, but allows out of bounds access (following the grid's boundary policy)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Row<'a, T> {
+    cells: &'a [T],
+    default: &'a T,
+    boundary: Boundary,
+}
+
+impl<'a, T: Copy> Row<'a, T> {
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.cells.iter()
+    }
+
+    pub fn as_slice(&self) -> &'a [T] {
+        self.cells
+    }
+
+    pub fn cell(&self, idx: i64) -> &'a T {
+        match self.boundary.resolve(idx, self.cells.len()) {
+            Some(idx) => &self.cells[idx],
+            None if self.boundary == Boundary::Strict => {
+                panic!("index {} is out of bounds of a row of {}", idx, self.len())
+            }
+            None => self.default,
+        }
+    }
+
+    pub fn slice(&self, range: &Range<i64>) -> Vec<T> {
+        let mut result = Vec::with_capacity(range.clone().count());
+        for idx in range.start..range.end {
+            result.push(*self.cell(idx));
+        }
+        result
+    }
+}
+
+// A rectangular grid of cells stored contiguously in row-major order
+// It behaves like a 2D array, but allows out of bounds access (see `Boundary` for the options)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    default: T,
+    boundary: Boundary,
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn from_dimensions(width: usize, height: usize, default: T) -> Self {
+        Self {
+            cells: vec![default; width * height],
+            width,
+            height,
+            default,
+            boundary: Boundary::Default,
+        }
+    }
+
+    // Builds a grid from a row-major list of cells
+    pub fn from_cells(cells: Vec<T>, width: usize, default: T) -> Self {
+        assert!(width > 0);
+        assert!(cells.len().is_multiple_of(width));
+
+        Self {
+            height: cells.len() / width,
+            cells,
+            width,
+            default,
+            boundary: Boundary::Default,
+        }
+    }
+
+    // Builds a grid from a list of rows, padding short rows with the default value
+    pub fn from_rows<R>(rows: impl Iterator<Item = R>, default: T) -> Self
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let rows = rows
+            .map(|row| row.into_iter().collect::<Vec<T>>())
+            .collect::<Vec<_>>();
+
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        assert!(width > 0);
+
+        let height = rows.len();
+        assert!(height > 0);
+
+        let mut cells = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, default);
+            cells.append(&mut row);
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+            default,
+            boundary: Boundary::Default,
+        }
+    }
+
+    // Converts each cell of the grid (and the default value) into a different type
+    pub fn map<U: Copy>(&self, mut f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(|cell| f(*cell)).collect(),
+            width: self.width,
+            height: self.height,
+            default: f(self.default),
+            boundary: self.boundary,
+        }
+    }
+
+    // Changes the way out of bounds cells are accessed
+    pub fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    pub fn with_padding(&self, x_padding: usize, y_padding: usize) -> Self {
+        let mut map = Self::from_dimensions(
+            self.width() + x_padding * 2,
+            self.height() + y_padding * 2,
+            self.default,
+        )
+        .with_boundary(self.boundary);
+        for (y, line) in self.lines().enumerate() {
+            for (x, cell) in line.iter().enumerate() {
+                map.set_cell(x + x_padding, y + y_padding, *cell);
+            }
+        }
+        map
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn default_cell(&self) -> T {
+        self.default
+    }
+
+    pub fn top_left(&self) -> Point<i64> {
+        Point::new(0, 0)
+    }
+
+    pub fn top_right(&self) -> Point<i64> {
+        Point::new(self.width() as i64 - 1, 0)
+    }
+
+    pub fn bottom_left(&self) -> Point<i64> {
+        Point::new(0, self.height() as i64 - 1)
+    }
+
+    pub fn bottom_right(&self) -> Point<i64> {
+        Point::new(self.width() as i64 - 1, self.height() as i64 - 1)
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Row<'_, T>> {
+        self.cells.chunks(self.width).map(|cells| self.row(cells))
+    }
+
+    pub fn lines_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_mut(self.width)
+    }
+
+    pub fn line(&self, idx: i64) -> Row<'_, T> {
+        match self.boundary.resolve(idx, self.height) {
+            Some(idx) => {
+                let start = idx * self.width;
+                self.row(&self.cells[start..start + self.width])
+            }
+            None if self.boundary == Boundary::Strict => {
+                panic!("line {} is out of bounds of a grid of {}", idx, self.height)
+            }
+            None => self.row(&[]),
+        }
+    }
+
+    fn row<'a>(&'a self, cells: &'a [T]) -> Row<'a, T> {
+        Row {
+            cells,
+            default: &self.default,
+            boundary: self.boundary,
+        }
+    }
+
+    // Iterates over all cells in row-major order along with their coordinates
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i64>, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(idx, cell)| {
+            let point = Point::new((idx % width) as i64, (idx / width) as i64);
+            (point, cell)
+        })
+    }
+
+    // Iterates over the cells surrounding a horizontal run of cells (x in start..=end on row y),
+    // including the diagonal corners, top to bottom and left to right
+    pub fn run_neighbours(
+        &self,
+        y: i64,
+        start: i64,
+        end: i64,
+    ) -> impl Iterator<Item = (Point<i64>, &T)> {
+        let above = (start - 1..=end + 1).map(move |x| Point::new(x, y - 1));
+        let sides = [Point::new(start - 1, y), Point::new(end + 1, y)];
+        let below = (start - 1..=end + 1).map(move |x| Point::new(x, y + 1));
+        above
+            .chain(sides)
+            .chain(below)
+            .map(|point| (point, self.cell_for_point(&point)))
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    // Maps coordinates into an index of the cell, following the boundary policy
+    fn index_of(&self, x: i64, y: i64) -> Option<usize> {
+        let x = self.boundary.resolve(x, self.width)?;
+        let y = self.boundary.resolve(y, self.height)?;
+        Some(y * self.width + x)
+    }
+
+    pub fn cell(&self, x: i64, y: i64) -> &T {
+        match self.try_cell(x, y) {
+            Ok(cell) => cell,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    // Same as `cell`, but returns an error instead of panicking on strict grids
+    pub fn try_cell(&self, x: i64, y: i64) -> Result<&T, OutOfBounds> {
+        match self.index_of(x, y) {
+            Some(idx) => Ok(&self.cells[idx]),
+            None if self.boundary == Boundary::Strict => Err(OutOfBounds {
+                point: Point::new(x, y),
+                width: self.width,
+                height: self.height,
+            }),
+            None => Ok(&self.default),
+        }
+    }
+
+    pub fn try_cell_for_point(&self, point: &Point<i64>) -> Result<&T, OutOfBounds> {
+        self.try_cell(point.x, point.y)
+    }
+
+    pub fn set_cell(&mut self, x: usize, y: usize, value: T) {
+        assert!(x < self.width);
+        self.cells[y * self.width + x] = value;
+    }
+
+    pub fn cell_for_point(&self, point: &Point<i64>) -> &T {
+        self.cell(point.x, point.y)
+    }
+
+    // Sets the cell following the boundary policy (writes outside of the grid are ignored by default)
+    pub fn set_cell_for_point(&mut self, point: &Point<i64>, value: T) {
+        match self.index_of(point.x, point.y) {
+            Some(idx) => self.cells[idx] = value,
+            None if self.boundary == Boundary::Strict => panic!(
+                "point {} is out of bounds of a {}x{} grid",
+                point, self.width, self.height
+            ),
+            None => {}
+        }
+    }
+
+    // Checks if the point is outside of the cells stored in the grid (regardless of the boundary policy)
+    pub fn out_of_bounds(&self, point: &Point<i64>) -> bool {
+        point.x < 0
+            || point.y < 0
+            || point.x as usize >= self.width
+            || point.y as usize >= self.height
+    }
+
+    pub fn copy_from_vec(&mut self, new_map: &[Vec<T>]) {
+        for (y, line) in new_map.iter().enumerate() {
+            for (x, cell) in line.iter().enumerate() {
+                self.set_cell(x, y, *cell);
+            }
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut new_map = Self::from_dimensions(self.height(), self.width(), self.default)
+            .with_boundary(self.boundary);
+
+        for (y, line) in self.lines().enumerate() {
+            for (x, cell) in line.iter().enumerate() {
+                new_map.set_cell(y, x, *cell);
+            }
+        }
+        new_map
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        let mut new_map = self.clone();
+        for row in new_map.lines_mut() {
+            row.reverse();
+        }
+        new_map
+    }
+
+    pub fn rotate_left(&self) -> Self {
+        self.flip_horizontal().transpose()
+    }
+
+    pub fn rotate_right(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    pub fn find(&self, c: T) -> Option<Point<i64>> {
+        self.iter()
+            .find(|(_, cell)| **cell == c)
+            .map(|(point, _)| point)
+    }
+
+    pub fn find_all(&self, c: T) -> Vec<Point<i64>> {
+        self.iter()
+            .filter(|(_, cell)| **cell == c)
+            .map(|(point, _)| point)
+            .collect()
+    }
+
+    pub fn count(&self, c: T) -> usize {
+        self.cells.iter().filter(|cell| **cell == c).count()
+    }
+
+    // Fills all default-valued cells reachable from the start with the given value
+    pub fn flood_fill(&mut self, start: Point<i64>, fill_with: T) {
+        let mut stack = Vec::new();
+        stack.push(start);
+
+        while let Some(point) = stack.pop() {
+            self.set_cell_for_point(&point, fill_with);
+
+            for neighbour in point.neighbours_list() {
+                if self.out_of_bounds(&neighbour) {
+                    continue;
+                }
+                let cell = self.cell_for_point(&neighbour);
+                if *cell != self.default {
+                    continue;
+                }
+                stack.push(neighbour);
+            }
+        }
+    }
+}
+
+impl<T: Hash> Grid<T> {
+    pub fn hash64(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// Prints the rows one per line, the way the puzzle input shows them
+impl<T: Copy + Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, line) in self.lines().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in line.iter() {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_cells() {
+        let grid = Grid::from_cells(vec![1u8, 2, 3, 4, 5, 6], 3, 0);
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(6, *grid.cell(2, 1));
+        assert_eq!(0, *grid.cell(3, 0));
+        assert_eq!(0, *grid.cell(0, -1));
+        assert_eq!(&[4, 5, 6], grid.line(1).as_slice());
+        assert!(grid.line(2).is_empty());
+        assert_eq!(0, *grid.line(2).cell(0));
+    }
+
+    #[test]
+    fn test_from_rows_pads_short_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4]].into_iter(), 0);
+        assert_eq!(3, grid.width());
+        assert_eq!(&[1, 2, 3, 4, 0, 0], grid.cells());
+    }
+
+    #[test]
+    fn test_iter() {
+        let grid = Grid::from_cells(vec![1, 2, 3, 4], 2, 0);
+        let cells = grid.iter().map(|(p, c)| (p, *c)).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Point::new(0, 0), 1),
+                (Point::new(1, 0), 2),
+                (Point::new(0, 1), 3),
+                (Point::new(1, 1), 4),
+            ],
+            cells
+        );
+    }
+
+    #[test]
+    fn test_run_neighbours() {
+        let grid = Grid::from_cells((0..20).collect(), 5, -1);
+        let neighbours = grid
+            .run_neighbours(1, 1, 2)
+            .map(|(_, c)| *c)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0, 1, 2, 3, 5, 8, 10, 11, 12, 13], neighbours);
+
+        let corner = grid.run_neighbours(0, 0, 0).collect::<Vec<_>>();
+        assert_eq!(8, corner.len());
+        assert_eq!((Point::new(-1, -1), &-1), corner[0]);
+        assert_eq!((Point::new(1, 1), &6), corner[7]);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = Grid::from_cells(vec![1u8, 2, 3, 4], 2, 0);
+        let mapped = grid.map(|c| c % 2 == 0);
+        assert_eq!(&[false, true, false, true], mapped.cells());
+        assert!(*mapped.cell(-1, -1));
+    }
+
+    #[test]
+    fn test_set_cell() {
+        let mut grid = Grid::from_dimensions(2, 2, 0);
+        grid.set_cell(1, 0, 7);
+        grid.set_cell_for_point(&Point::new(0, 1), 8);
+        grid.set_cell_for_point(&Point::new(2, 1), 9);
+        assert_eq!(&[0, 7, 8, 0], grid.cells());
+        assert_eq!(Some(Point::new(1, 0)), grid.find(7));
+        assert_eq!(2, grid.count(0));
+    }
+
+    #[test]
+    fn test_boundary_wrap() {
+        let mut grid = Grid::from_cells(vec![1, 2, 3, 4, 5, 6], 3, 0).with_boundary(Boundary::Wrap);
+        assert_eq!(1, *grid.cell(3, 0));
+        assert_eq!(6, *grid.cell(-1, -1));
+        assert_eq!(5, *grid.cell(-8, 7));
+        assert_eq!(4, *grid.line(-1).cell(3));
+        assert!(grid.out_of_bounds(&Point::new(3, 0)));
+
+        grid.set_cell_for_point(&Point::new(4, 2), 9);
+        assert_eq!(9, *grid.cell(1, 0));
+        assert_eq!(Boundary::Wrap, grid.transpose().boundary());
+    }
+
+    #[test]
+    fn test_boundary_clamp() {
+        let grid = Grid::from_cells(vec![1, 2, 3, 4, 5, 6], 3, 0).with_boundary(Boundary::Clamp);
+        assert_eq!(1, *grid.cell(-5, -5));
+        assert_eq!(3, *grid.cell(10, 0));
+        assert_eq!(6, *grid.cell(10, 10));
+        assert_eq!(vec![4, 4, 5, 6, 6], grid.line(7).slice(&(-1..4)));
+    }
+
+    #[test]
+    fn test_boundary_strict() {
+        let grid = Grid::from_cells(vec![1, 2, 3, 4, 5, 6], 3, 0).with_boundary(Boundary::Strict);
+        assert_eq!(Ok(&5), grid.try_cell(1, 1));
+        assert_eq!(
+            Err(OutOfBounds {
+                point: Point::new(3, 1),
+                width: 3,
+                height: 2
+            }),
+            grid.try_cell_for_point(&Point::new(3, 1))
+        );
+        assert_eq!(Ok(&0), grid.with_boundary(Boundary::Default).try_cell(3, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "point (0,2) is out of bounds of a 3x2 grid")]
+    fn test_boundary_strict_panics() {
+        let grid = Grid::from_cells(vec![1, 2, 3, 4, 5, 6], 3, 0).with_boundary(Boundary::Strict);
+        grid.cell(0, 2);
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+// Directory with inputs laid out as `<dir>/day-07/input2.txt`, checked before the day crates
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum InputError {
+    #[error("could not read input from {path}")]
+    #[diagnostic(code(aoc::input_read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("could not read input from stdin")]
+    #[diagnostic(code(aoc::input_stdin))]
+    Stdin(#[source] std::io::Error),
+
+    #[error("no input found for day {day} part {part}")]
+    #[diagnostic(
+        code(aoc::input_missing),
+        help("looked in: {searched}; pass a path (or `-` for stdin) or set AOC_INPUT_DIR")
+    )]
+    Missing { day: u8, part: u8, searched: String },
+}
+
+// Where the puzzle input came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Embedded,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub source: InputSource,
+    pub text: String,
+}
+
+// Finds the puzzle input for a part at runtime. The sources are tried in order:
+// an explicit path (`-` reads stdin), `$AOC_INPUT_DIR/day-XX/inputN.txt`,
+// `inputN.txt` in each of the given directories and finally the embedded copy.
+#[derive(Debug, Clone, Default)]
+pub struct InputLoader {
+    day: u8,
+    part: u8,
+    path: Option<String>,
+    dirs: Vec<PathBuf>,
+    embedded: Option<&'static str>,
+}
+
+impl InputLoader {
+    pub fn new(day: u8, part: u8) -> Self {
+        Self {
+            day,
+            part,
+            ..Self::default()
+        }
+    }
+
+    pub fn path(mut self, path: Option<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    pub fn dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.dirs.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn embedded(mut self, input: Option<&'static str>) -> Self {
+        self.embedded = input;
+        self
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("input{}.txt", self.part)
+    }
+
+    // Candidate files in the order they are checked (not including an explicit path)
+    fn candidates(&self) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        if let Some(dir) = std::env::var_os(INPUT_DIR_VAR) {
+            candidates.push(
+                PathBuf::from(dir)
+                    .join(format!("day-{:02}", self.day))
+                    .join(self.file_name()),
+            );
+        }
+        for dir in &self.dirs {
+            candidates.push(dir.join(self.file_name()));
+        }
+        candidates
+    }
+
+    pub fn load(&self) -> Result<Input, InputError> {
+        if let Some(path) = &self.path {
+            if path == "-" {
+                let mut text = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(InputError::Stdin)?;
+                return Ok(Input {
+                    source: InputSource::Stdin,
+                    text: normalize(&text),
+                });
+            }
+            return read_file(PathBuf::from(path));
+        }
+
+        let candidates = self.candidates();
+        if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+            return read_file(path.clone());
+        }
+
+        if let Some(text) = self.embedded {
+            return Ok(Input {
+                source: InputSource::Embedded,
+                text: normalize(text),
+            });
+        }
+
+        let searched = candidates
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        Err(InputError::Missing {
+            day: self.day,
+            part: self.part,
+            searched: if searched.is_empty() {
+                "nowhere".to_string()
+            } else {
+                searched.join(", ")
+            },
+        })
+    }
+}
+
+fn read_file(path: PathBuf) -> Result<Input, InputError> {
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Ok(Input {
+            source: InputSource::File(path),
+            text: normalize(&text),
+        }),
+        Err(source) => Err(InputError::Read { path, source }),
+    }
+}
+
+// Converts line endings to `\n` and drops trailing whitespace from every line and from the
+// end of the input, so the solutions see the same text no matter how the file was saved
+pub fn normalize(input: &str) -> String {
+    let mut text = input
+        .lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let len = text.trim_end().len();
+    text.truncate(len);
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+// Splits the input into the blocks of lines separated by blank lines. The blocks are
+// slices of the input, so parse errors inside them still point into the whole input.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end().len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!("a\nb\n", normalize("a\r\nb\r\n"));
+        assert_eq!("a\n\nb\n", normalize("a  \n\t\nb"));
+        assert_eq!("a\n", normalize("a\n\n\n"));
+        assert_eq!("", normalize(" \n"));
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(vec!["a\nb", "c"], blocks("a\nb\n\n\nc\n"));
+        assert_eq!(vec!["  a", "b"], blocks("\n  a\n  \nb"));
+        assert!(blocks("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("input1.txt"), "1 2\r\n3 4\r\n").unwrap();
+
+        let input = InputLoader::new(7, 1).dir(&dir).load().unwrap();
+        assert_eq!(InputSource::File(dir.join("input1.txt")), input.source);
+        assert_eq!("1 2\n3 4\n", input.text);
+
+        // Files win over the embedded copy, which is only used when nothing else is found
+        let input = InputLoader::new(7, 1)
+            .dir(&dir)
+            .embedded(Some("embedded"))
+            .load()
+            .unwrap();
+        assert_eq!("1 2\n3 4\n", input.text);
+
+        let input = InputLoader::new(7, 2)
+            .dir(&dir)
+            .embedded(Some("embedded"))
+            .load()
+            .unwrap();
+        assert_eq!(InputSource::Embedded, input.source);
+        assert_eq!("embedded\n", input.text);
+
+        let err = InputLoader::new(7, 2).dir(&dir).load().unwrap_err();
+        assert!(matches!(
+            err,
+            InputError::Missing {
+                day: 7,
+                part: 2,
+                ..
+            }
+        ));
+
+        let path = dir.join("missing.txt").display().to_string();
+        let err = InputLoader::new(7, 1).path(Some(path)).load().unwrap_err();
+        assert!(matches!(err, InputError::Read { .. }));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,360 @@
+use std::ops::Range;
+
+use num_traits::PrimInt;
+
+// A set of values made of half-open ranges. The ranges are kept sorted, non-empty and
+// non-overlapping (touching ranges are merged), so two sets with the same values are equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    fn from_sorted(ranges: Vec<Range<T>>) -> Self {
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges.into_iter().filter(|r| r.start < r.end) {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Total number of values in the set
+    pub fn size(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |size, r| size + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::one())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // Find the last range starting at or before the value
+        let idx = self.ranges.partition_point(|r| r.start <= value);
+        idx > 0 && value < self.ranges[idx - 1].end
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&Self::from(range));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        ranges.extend(self.ranges.iter().cloned());
+        ranges.extend(other.ranges.iter().cloned());
+        ranges.sort_by_key(|r| r.start);
+        Self::from_sorted(ranges)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // Move past whichever range ends first, the other one may overlap more ranges
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    // All values from this set that are not in the other one
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            // Skip the ranges of the other set that end before this one starts
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            // Cut out every range of the other set overlapping this one
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if other.ranges[k].start > start {
+                    ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+
+    // Splits the set into values below the given one and values at or above it
+    pub fn split_at(&self, value: T) -> (Self, Self) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for range in &self.ranges {
+            if range.end <= value {
+                below.push(range.clone());
+            } else if range.start >= value {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..value);
+                above.push(value..range.end);
+            }
+        }
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    // Moves every value of the set by the same amount, so that `from` would end up at `to`.
+    // Works in both directions without needing a signed offset.
+    pub fn shift(&self, from: T, to: T) -> Self {
+        let moved = |value: T| {
+            if to >= from {
+                value + (to - from)
+            } else {
+                value - (from - to)
+            }
+        };
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| moved(r.start)..moved(r.end))
+            .collect();
+        Self { ranges }
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::from_sorted(vec![range])
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges = iter.into_iter().collect::<Vec<_>>();
+        ranges.sort_by_key(|r| r.start);
+        Self::from_sorted(ranges)
+    }
+}
+
+// An N-dimensional box: a half-open range of values along each dimension
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalBox<T, const N: usize> {
+    dims: [Range<T>; N],
+}
+
+impl<T: PrimInt, const N: usize> IntervalBox<T, N> {
+    pub fn new(dims: [Range<T>; N]) -> Self {
+        Self { dims }
+    }
+
+    pub fn dim(&self, dim: usize) -> &Range<T> {
+        &self.dims[dim]
+    }
+
+    pub fn dims(&self) -> &[Range<T>; N] {
+        &self.dims
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dims.iter().any(|r| r.start >= r.end)
+    }
+
+    // Number of points in the box
+    pub fn size(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+        self.dims
+            .iter()
+            .fold(T::one(), |size, r| size * (r.end - r.start))
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.dims.iter().zip(point).all(|(r, v)| r.contains(v))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut dims = self.dims.clone();
+        for (range, other) in dims.iter_mut().zip(&other.dims) {
+            *range = range.start.max(other.start)..range.end.min(other.end);
+        }
+        let result = Self { dims };
+        (!result.is_empty()).then_some(result)
+    }
+
+    // Splits the box along one dimension into the part below the given value
+    // and the part at or above it. Empty parts are returned as None.
+    pub fn split_at(&self, dim: usize, value: T) -> (Option<Self>, Option<Self>) {
+        let range = &self.dims[dim];
+
+        let mut below = self.clone();
+        below.dims[dim] = range.start..value.min(range.end);
+        let mut above = self.clone();
+        above.dims[dim] = value.max(range.start)..range.end;
+
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    // All points of this box that are not in the other one, as a list of disjoint boxes
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if self.intersection(other).is_none() {
+            return vec![self.clone()];
+        }
+
+        // Peel off the slabs outside of the other box one dimension at a time
+        let mut result = Vec::new();
+        let mut rest = self.clone();
+        for dim in 0..N {
+            let (below, inside) = rest.split_at(dim, other.dims[dim].start);
+            result.extend(below);
+
+            let (inside, above) = inside.unwrap().split_at(dim, other.dims[dim].end);
+            result.extend(above);
+
+            rest = inside.unwrap();
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<u64>]) -> IntervalSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_normalization() {
+        let s = set(&[10..20, 1..5, 5..7, 15..25, 30..30]);
+        assert_eq!(&[1..7, 10..25], s.ranges());
+        assert_eq!(21, s.size());
+        assert_eq!(Some(1), s.min());
+        assert_eq!(Some(24), s.max());
+        assert!(s.contains(6) && s.contains(10) && s.contains(24));
+        assert!(!s.contains(0) && !s.contains(7) && !s.contains(25));
+
+        let mut s = s;
+        s.insert(7..10);
+        assert_eq!(IntervalSet::from(1..25), s);
+        assert!(IntervalSet::<u64>::new().is_empty());
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = set(&[0..10, 20..30]);
+        let b = set(&[5..25, 40..50]);
+
+        assert_eq!(set(&[0..30, 40..50]), a.union(&b));
+        assert_eq!(set(&[5..10, 20..25]), a.intersection(&b));
+        assert_eq!(set(&[0..5, 25..30]), a.difference(&b));
+        assert_eq!(set(&[10..20, 40..50]), b.difference(&a));
+
+        // One range of the other set covering several of ours and the other way around
+        let wide = IntervalSet::from(0..100);
+        assert_eq!(a, a.intersection(&wide));
+        assert!(a.difference(&wide).is_empty());
+        assert_eq!(set(&[10..20, 30..100]), wide.difference(&a));
+    }
+
+    #[test]
+    fn test_split_at() {
+        let s = set(&[0..10, 20..30]);
+        assert_eq!(
+            (IntervalSet::from(0..5), set(&[5..10, 20..30])),
+            s.split_at(5)
+        );
+        assert_eq!(
+            (IntervalSet::from(0..10), IntervalSet::from(20..30)),
+            s.split_at(15)
+        );
+        assert_eq!((set(&[]), s.clone()), s.split_at(0));
+        assert_eq!((s.clone(), set(&[])), s.split_at(30));
+    }
+
+    #[test]
+    fn test_shift() {
+        let s = set(&[10..20, 30..40]);
+        assert_eq!(set(&[110..120, 130..140]), s.shift(0, 100));
+        assert_eq!(set(&[0..10, 20..30]), s.shift(10, 0));
+    }
+
+    #[test]
+    fn test_box() {
+        let b = IntervalBox::new([0..10u64, 0..4]);
+        assert_eq!(40, b.size());
+        assert!(b.contains(&[9, 3]));
+        assert!(!b.contains(&[10, 3]));
+
+        let (left, right) = b.split_at(0, 3);
+        assert_eq!(Some(IntervalBox::new([0..3, 0..4])), left);
+        assert_eq!(Some(IntervalBox::new([3..10, 0..4])), right);
+        assert_eq!((None, Some(b.clone())), b.split_at(1, 0));
+
+        let other = IntervalBox::new([5..20, 2..3]);
+        assert_eq!(
+            Some(IntervalBox::new([5..10, 2..3])),
+            b.intersection(&other)
+        );
+        assert_eq!(None, b.intersection(&IntervalBox::new([10..20, 0..4])));
+    }
+
+    #[test]
+    fn test_box_difference() {
+        let b = IntervalBox::new([0..10u64, 0..10, 0..10]);
+        let hole = IntervalBox::new([2..4, 3..7, 5..20]);
+
+        let rest = b.difference(&hole);
+        let size = rest.iter().map(|r| r.size()).sum::<u64>();
+        assert_eq!(1000 - 2 * 4 * 5, size);
+
+        // The pieces don't overlap each other or the hole
+        for (i, a) in rest.iter().enumerate() {
+            assert_eq!(None, a.intersection(&hole));
+            for b in rest.iter().skip(i + 1) {
+                assert_eq!(None, a.intersection(b));
+            }
+        }
+
+        let outside = IntervalBox::new([20..30, 0..10, 0..10]);
+        assert_eq!(vec![b.clone()], b.difference(&outside));
+    }
+}
@@ -0,0 +1,39 @@
+pub mod answers;
+pub mod bench;
+pub mod boundary;
+pub mod char_map;
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod line;
+pub mod logging;
+pub mod parse_error;
+pub mod parsers;
+pub mod point;
+pub mod polygon;
+pub mod search;
+pub mod solution;
+
+pub use answers::{Answers, AnswersError, Submission, Verdict, Warning, ANSWERS_FILE};
+pub use boundary::{Boundary, OutOfBounds};
+pub use char_map::{CharMap, CharRow};
+pub use cycle::{find_cycle, state_after, Cycle};
+pub use direction::Direction;
+pub use grid::{Grid, Row};
+pub use input::{blocks, normalize, Input, InputError, InputLoader, InputSource, INPUT_DIR_VAR};
+pub use interval::{IntervalBox, IntervalSet};
+pub use line::Line;
+pub use logging::init_tracing;
+pub use parse_error::ParseError;
+pub use parsers::{
+    list1, name, number, numbers, parse_all, parse_lines, symbol, token, unsigned, ws, IResult,
+    NomError,
+};
+pub use point::{point3d, Point, Point3D};
+pub use polygon::{
+    boundary_points, interior_points, lattice_points, on_boundary, point_in_polygon, polygon_area,
+};
+pub use search::{astar, dijkstra, dijkstra_indexed, ShortestPath};
+pub use solution::{Param, ParamError, Params, Solution, DEFAULT_IMPLEMENTATION};
@@ -0,0 +1,60 @@
+use crate::point::Point;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub p1: Point<f64>,
+    pub p2: Point<f64>,
+}
+
+impl Line {
+    pub fn new(p1: Point<f64>, p2: Point<f64>) -> Self {
+        Self { p1, p2 }
+    }
+
+    // Returns Some(point) if the lines intersect, None otherwise
+    pub fn intersects(&self, other: &Line) -> Option<Point<f64>> {
+        let a1 = self.p2.y - self.p1.y;
+        let b1 = self.p1.x - self.p2.x;
+        let c1 = a1 * self.p1.x + b1 * self.p1.y;
+
+        let a2 = other.p2.y - other.p1.y;
+        let b2 = other.p1.x - other.p2.x;
+        let c2 = a2 * other.p1.x + b2 * other.p1.y;
+
+        let determinant = a1 * b2 - a2 * b1;
+
+        if determinant == 0.0 {
+            // Lines are parallel, no intersection
+            None
+        } else {
+            let x = (b2 * c1 - b1 * c2) / determinant;
+            let y = (a1 * c2 - a2 * c1) / determinant;
+            Some(Point { x, y })
+        }
+    }
+
+    pub fn vector(&self) -> Point<f64> {
+        self.p1.vector_to(&self.p2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersects() {
+        let line1 = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 2.0));
+        let line2 = Line::new(Point::new(0.0, 2.0), Point::new(2.0, 0.0));
+        assert_eq!(Some(Point::new(1.0, 1.0)), line1.intersects(&line2));
+
+        let parallel = Line::new(Point::new(0.0, 1.0), Point::new(2.0, 3.0));
+        assert_eq!(None, line1.intersects(&parallel));
+    }
+
+    #[test]
+    fn test_vector() {
+        let line = Line::new(Point::new(19.0, 13.0), Point::new(17.0, 14.0));
+        assert_eq!(Point::new(-2.0, 1.0), line.vector());
+    }
+}
@@ -0,0 +1,16 @@
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+// Sets up tracing for a binary. Events are printed to stderr, so they don't get mixed up
+// with the answers, once `RUST_LOG` enables them (e.g. `RUST_LOG=day_15=trace`). With the
+// `tracy` feature every span is also sent to Tracy as a zone, whatever `RUST_LOG` says.
+pub fn init_tracing() {
+    let fmt = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(EnvFilter::from_default_env());
+    let subscriber = tracing_subscriber::registry().with(fmt);
+
+    #[cfg(feature = "tracy")]
+    let subscriber = subscriber.with(tracing_tracy::TracyLayer::default());
+
+    subscriber.init();
+}
@@ -0,0 +1,97 @@
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+// A problem with the puzzle input, pointing at the characters that caused it. Parsers
+// create it from the fragment they were looking at and `with_source` later finds that
+// fragment in the whole input, so the diagnostic shows it in context.
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+#[diagnostic(code(aoc::parse_error))]
+pub struct ParseError {
+    pub message: String,
+    #[source_code]
+    pub input: String,
+    #[label("{label}")]
+    pub span: SourceSpan,
+    pub label: String,
+    // Address of the fragment, only used to locate it in the input
+    addr: usize,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>, at: &str, label: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            input: at.to_string(),
+            span: (0, at.len()).into(),
+            label: label.into(),
+            addr: at.as_ptr() as usize,
+        }
+    }
+
+    // Points the error into the whole input, if the fragment is a part of it
+    pub fn with_source(mut self, input: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let len = self.span.len();
+        if self.addr >= start && self.addr + len <= start + input.len() {
+            self.span = (self.addr - start, len).into();
+            self.input = input.to_string();
+        }
+        self
+    }
+}
+
+// Takes the parse error out of a day's result in tests, failing for anything else:
+// `let err = aoc_utils::expect_parse_error!(process("..."));`
+// Every day has its own error type, so this matches on the `AocError::ParseError` variant of
+// the crate it expands in.
+#[macro_export]
+macro_rules! expect_parse_error {
+    ($result:expr) => {
+        match $result {
+            Err(AocError::ParseError(err)) => err,
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_source() {
+        let input = "1 2\n3 x\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = ParseError::new("invalid number `x`", &line[2..], "not a number");
+        assert_eq!(SourceSpan::from((0, 1)), err.span);
+
+        let err = err.with_source(input);
+        assert_eq!(SourceSpan::from((6, 1)), err.span);
+        assert_eq!(input, err.input);
+
+        // A fragment from somewhere else keeps pointing at itself
+        let other = String::from("x");
+        let err = ParseError::new("bad", &other, "here").with_source(input);
+        assert_eq!("x", err.input);
+    }
+
+    #[derive(Debug)]
+    enum AocError {
+        ParseError(ParseError),
+        NoAnswer,
+    }
+
+    #[test]
+    fn test_expect_parse_error() {
+        let res: Result<(), _> = Err(AocError::ParseError(ParseError::new("bad", "x", "here")));
+        assert_eq!("bad", expect_parse_error!(res).message);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a parse error, got Err(NoAnswer)")]
+    fn test_expect_parse_error_other() {
+        let res: Result<(), _> = Err(AocError::NoAnswer);
+        expect_parse_error!(res);
+    }
+}
@@ -0,0 +1,321 @@
+use std::{borrow::Cow, str::FromStr};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, digit1, space0, space1},
+    combinator::{all_consuming, opt, recognize},
+    error::{ContextError, ErrorKind, FromExternalError},
+    multi::separated_list1,
+    sequence::{delimited, pair},
+    Finish, Parser,
+};
+
+use crate::parse_error::ParseError;
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, NomError<'a>>;
+
+// Where a nom parser gave up and what it expected to see there. The input is the part
+// that was left at that point, so it can be turned into a `ParseError` pointing at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NomError<'a> {
+    pub input: &'a str,
+    pub expected: Cow<'static, str>,
+    // How much of the input is wrong, when it's more than the next word
+    len: Option<usize>,
+    // Set once a `context` described the failure, so outer ones don't override it
+    described: bool,
+}
+
+impl<'a> NomError<'a> {
+    pub fn new(input: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            input,
+            expected: expected.into(),
+            len: None,
+            described: true,
+        }
+    }
+
+    // An error about the whole fragment, like a value that was parsed but makes no sense
+    pub fn at(fragment: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            len: Some(fragment.len()),
+            ..Self::new(fragment, expected)
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> Cow<'static, str> {
+    match kind {
+        ErrorKind::Digit => "a number".into(),
+        ErrorKind::Alpha | ErrorKind::AlphaNumeric => "a name".into(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "a space".into(),
+        ErrorKind::CrLf => "a new line".into(),
+        ErrorKind::Eof => "the end of the line".into(),
+        ErrorKind::SeparatedList | ErrorKind::Many1 => "at least one item".into(),
+        kind => kind.description().to_lowercase().into(),
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for NomError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: describe(kind),
+            len: None,
+            described: false,
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    // Alternatives failing at the same place are all listed, otherwise the one that got
+    // further is the more useful one
+    fn or(self, other: Self) -> Self {
+        match self.input.len().cmp(&other.input.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => Self::new(
+                self.input,
+                format!("{} or {}", self.expected, other.expected),
+            ),
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for NomError<'a> {
+    fn add_context(input: &'a str, ctx: &'static str, other: Self) -> Self {
+        if other.described {
+            other
+        } else {
+            Self::new(input, ctx)
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for NomError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _e: E) -> Self {
+        nom::error::ParseError::from_error_kind(input, kind)
+    }
+}
+
+// The word or the single character the parser stopped at
+fn next_token(input: &str) -> &str {
+    let input = input.trim_start_matches([' ', '\t']);
+    let end = match input
+        .char_indices()
+        .find(|(_, c)| !c.is_alphanumeric() && *c != '-')
+    {
+        Some((0, c)) if c != '\n' => c.len_utf8(),
+        Some((idx, _)) => idx,
+        None => input.len(),
+    };
+    &input[..end]
+}
+
+impl From<NomError<'_>> for ParseError {
+    fn from(err: NomError<'_>) -> Self {
+        let found = match err.len {
+            Some(len) => &err.input[..len],
+            None => next_token(err.input),
+        };
+        let message = if found.is_empty() {
+            format!("expected {}, found the end of the line", err.expected)
+        } else {
+            format!("expected {}, found `{}`", err.expected, found)
+        };
+        ParseError::new(message, found, format!("expected {}", err.expected))
+    }
+}
+
+// Runs a parser that has to consume all of the input
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, NomError<'a>>,
+) -> Result<O, ParseError> {
+    all_consuming(parser)(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|err| ParseError::from(err).with_source(input))
+}
+
+// Runs a parser on every line of the input, ignoring the indentation around them
+pub fn parse_lines<'a, O>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, O, NomError<'a>>,
+) -> Result<Vec<O>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            parse_all(line.trim(), |i| parser.parse(i)).map_err(|err| err.with_source(input))
+        })
+        .collect()
+}
+
+// A tag that says what it was looking for when it's missing
+pub fn token<'a>(expected: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input| {
+        tag::<_, _, NomError>(expected)(input)
+            .map_err(|err| err.map(|_| NomError::new(input, format!("`{}`", expected))))
+    }
+}
+
+// A single character out of the given ones, like a cell of a map or a step
+pub fn symbol<'a>(
+    chars: &'static str,
+    expected: &'static str,
+) -> impl FnMut(&'a str) -> IResult<'a, char> {
+    move |input| match input.chars().next() {
+        Some(c) if chars.contains(c) => Ok((&input[c.len_utf8()..], c)),
+        c => {
+            let at = &input[..c.map_or(0, char::len_utf8)];
+            Err(nom::Err::Error(NomError::at(at, expected)))
+        }
+    }
+}
+
+// An optionally negative integer of any type that fits it
+pub fn number<'a, T: FromStr>(input: &'a str) -> IResult<'a, T> {
+    let (rest, digits) = recognize(pair(opt(char('-')), digit1))(input)
+        .map_err(|err: nom::Err<NomError>| err.map(|_| NomError::new(input, "a number")))?;
+    match digits.parse() {
+        Ok(number) => Ok((rest, number)),
+        Err(_) => Err(nom::Err::Failure(NomError::at(digits, "a valid number"))),
+    }
+}
+
+// A number without a sign, for when a `-` next to it means something else
+pub fn unsigned<'a, T: FromStr>(input: &'a str) -> IResult<'a, T> {
+    let (rest, digits) = digit1(input)?;
+    match digits.parse() {
+        Ok(number) => Ok((rest, number)),
+        Err(_) => Err(nom::Err::Failure(NomError::at(digits, "a valid number"))),
+    }
+}
+
+// A space separated list of numbers
+pub fn numbers<'a, T: FromStr>(input: &'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(space1, number)(input)
+}
+
+// Like `separated_list1`, but once a separator is found the item after it has to be there,
+// so a broken item is reported instead of being left over after the list
+pub fn list1<'a, O, S>(
+    mut separator: impl Parser<&'a str, S, NomError<'a>>,
+    mut item: impl Parser<&'a str, O, NomError<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        loop {
+            match separator.parse(input) {
+                Ok((rest, _)) => {
+                    let (rest, next) = item.parse(rest).map_err(|err| match err {
+                        nom::Err::Error(err) => nom::Err::Failure(err),
+                        err => err,
+                    })?;
+                    items.push(next);
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) => return Ok((input, items)),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+// A name made of letters and digits
+pub fn name(input: &str) -> IResult<'_, &str> {
+    alphanumeric1(input)
+}
+
+// Allows spaces or tabs around a parser
+pub fn ws<'a, O>(
+    parser: impl Parser<&'a str, O, NomError<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, O> {
+    delimited(space0, parser, space0)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{branch::alt, sequence::separated_pair};
+
+    use super::*;
+
+    fn pair_of_numbers(input: &str) -> IResult<'_, (u32, i64)> {
+        separated_pair(number, ws(token(",")), number)(input)
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(Ok((1, -2)), parse_all("1 , -2", pair_of_numbers));
+
+        let err = parse_all("1 , -2 3", pair_of_numbers).unwrap_err();
+        assert_eq!("expected the end of the line, found `3`", err.message);
+        assert_eq!(miette::SourceSpan::from((7, 1)), err.span);
+
+        let err = parse_all("1 ; 2", pair_of_numbers).unwrap_err();
+        assert_eq!("expected `,`, found `;`", err.message);
+
+        let err = parse_all("1 ,", pair_of_numbers).unwrap_err();
+        assert_eq!("expected a number, found the end of the line", err.message);
+
+        let err = parse_all("-1, 2", pair_of_numbers).unwrap_err();
+        assert_eq!("expected a valid number, found `-1`", err.message);
+        assert_eq!(miette::SourceSpan::from((0, 2)), err.span);
+
+        assert_eq!(Ok(("-", 12)), unsigned::<u8>("12-"));
+        assert!(unsigned::<u8>("-12").is_err());
+    }
+
+    #[test]
+    fn test_list1() {
+        let mut list = list1(ws(token(",")), number::<u8>);
+        assert_eq!(Ok(vec![1, 2, 3]), parse_all("1, 2 ,3", &mut list));
+
+        let err = parse_all("1, 2, x", &mut list).unwrap_err();
+        assert_eq!("expected a number, found `x`", err.message);
+        assert_eq!(miette::SourceSpan::from((6, 1)), err.span);
+    }
+
+    #[test]
+    fn test_symbol() {
+        let mut step = symbol("LR", "`L` or `R`");
+        assert_eq!(Ok(("R", 'L')), step("LR"));
+
+        let err = parse_all("LRX", nom::multi::many1(&mut step)).unwrap_err();
+        assert_eq!("expected the end of the line, found `X`", err.message);
+        let err = parse_all("X", &mut step).unwrap_err();
+        assert_eq!("expected `L` or `R`, found `X`", err.message);
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let color = alt((token("red"), token("green"), token("blue")));
+        let err = parse_all("pink", color).unwrap_err();
+        assert_eq!(
+            "expected `red` or `green` or `blue`, found `pink`",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "1, 2\n  3 ,4\n5 6\n";
+        let err = parse_lines(input, pair_of_numbers).unwrap_err();
+        assert_eq!("expected `,`, found `6`", err.message);
+        assert_eq!(miette::SourceSpan::from((14, 1)), err.span);
+
+        assert_eq!(
+            Ok(vec![(1, 2), (3, 4)]),
+            parse_lines("1, 2\n  3 ,4", pair_of_numbers)
+        );
+        assert_eq!(
+            Ok(vec![vec![1, 2, 3]]),
+            parse_lines(" 1 2  3", numbers::<u8>)
+        );
+        assert_eq!(Ok(vec!["ab1"]), parse_lines("ab1", name));
+    }
+}
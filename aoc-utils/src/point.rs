@@ -0,0 +1,260 @@
+use std::{collections::HashMap, fmt::Formatter, ops::Sub};
+
+use nom::{
+    combinator::map,
+    sequence::{preceded, tuple},
+};
+
+use crate::{
+    direction::Direction,
+    parsers::{number, token, ws, IResult},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T>
+where
+    T: std::ops::Add<Output = T>
+        + Sub<Output = T>
+        + Copy
+        + PartialEq
+        + PartialOrd
+        + std::fmt::Debug
+        + num_traits::sign::Signed
+        + From<i32>,
+{
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn with_offset(&self, x: T, y: T) -> Self {
+        Self {
+            x: self.x + x,
+            y: self.y + y,
+        }
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn neighbour(&self, dir: Direction) -> Point<T> {
+        let one = T::from(1);
+        match dir {
+            Direction::North => Point::<T>::new(self.x, self.y - one),
+            Direction::South => Self::new(self.x, self.y + one),
+            Direction::West => Self::new(self.x - one, self.y),
+            Direction::East => Self::new(self.x + one, self.y),
+            Direction::NorthEast => Self::new(self.x + one, self.y - one),
+            Direction::NorthWest => Self::new(self.x - one, self.y - one),
+            Direction::SouthEast => Self::new(self.x + one, self.y + one),
+            Direction::SouthWest => Self::new(self.x - one, self.y + one),
+        }
+    }
+
+    pub fn neighbours(&self) -> HashMap<Direction, Self> {
+        let mut result = HashMap::new();
+        for dir in Direction::each() {
+            result.insert(dir, self.neighbour(dir));
+        }
+        result
+    }
+
+    pub fn neighbours_list(&self) -> Vec<Self> {
+        let mut result = Vec::with_capacity(4);
+        for dir in Direction::each() {
+            result.push(self.neighbour(dir));
+        }
+        result
+    }
+
+    // All eight surrounding points (including diagonals), clockwise starting from North
+    pub fn all_neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        Direction::all().into_iter().map(|dir| self.neighbour(dir))
+    }
+
+    pub fn direction_to(&self, other: &Self) -> Direction {
+        if self.x == other.x {
+            if self.y < other.y {
+                Direction::South
+            } else {
+                Direction::North
+            }
+        } else if self.x < other.x {
+            Direction::East
+        } else {
+            Direction::West
+        }
+    }
+
+    pub fn vector_to(&self, other: &Self) -> Point<T> {
+        Point::new(other.x - self.x, other.y - self.y)
+    }
+}
+
+impl<T> std::ops::Add for Point<T>
+where
+    T: std::ops::Add<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<T> std::fmt::Display for Point<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl<T> std::fmt::Debug for Point<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Point3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+// The way the inputs list coordinates, `19, 13, 30`. A width is applied to each of them.
+impl std::fmt::Display for Point3D {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = f.width().unwrap_or(0);
+        write!(
+            f,
+            "{:>w$}, {:>w$}, {:>w$}",
+            self.x,
+            self.y,
+            self.z,
+            w = width
+        )
+    }
+}
+
+// `19, 13, 30`, with any spaces around the commas
+pub fn point3d(input: &str) -> IResult<'_, Point3D> {
+    let coordinate = || preceded(ws(token(",")), number);
+    map(tuple((number, coordinate(), coordinate())), |(x, y, z)| {
+        Point3D::new(x, y, z)
+    })(input)
+}
+
+impl Point3D {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn on_top_of(&self, p2: &Point3D) -> bool {
+        self.x == p2.x && self.y == p2.y && self.z == p2.z + 1.0
+    }
+
+    pub fn xy(&self) -> Point<f64> {
+        Point::new(self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parsers::parse_all;
+
+    use super::*;
+
+    #[test]
+    fn test_neighbours() {
+        let point = Point::new(3i64, 5);
+        assert_eq!(Point::new(3, 4), point.neighbour(Direction::North));
+        assert_eq!(Point::new(3, 6), point.neighbour(Direction::South));
+        assert_eq!(Point::new(2, 5), point.neighbour(Direction::West));
+        assert_eq!(Point::new(4, 5), point.neighbour(Direction::East));
+
+        let neighbours = point.neighbours();
+        for (i, neighbour) in point.neighbours_list().iter().enumerate() {
+            assert_eq!(neighbours[&Direction::each()[i]], *neighbour);
+            assert_eq!(1, point.manhattan_distance(neighbour));
+            assert_eq!(Direction::each()[i], point.direction_to(neighbour));
+        }
+    }
+
+    #[test]
+    fn test_all_neighbours() {
+        let point = Point::new(0i64, 0);
+        let neighbours = point.all_neighbours().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Point::new(0, -1),
+                Point::new(1, -1),
+                Point::new(1, 0),
+                Point::new(1, 1),
+                Point::new(0, 1),
+                Point::new(-1, 1),
+                Point::new(-1, 0),
+                Point::new(-1, -1),
+            ],
+            neighbours
+        );
+        assert_eq!(Point::new(1, -1), point.neighbour(Direction::NorthEast));
+    }
+
+    #[test]
+    fn test_vectors() {
+        let a = Point::new(1.5, -2.0);
+        let b = Point::new(-0.5, 1.0);
+        assert_eq!(Point::new(-2.0, 3.0), a.vector_to(&b));
+        assert_eq!(b, a + a.vector_to(&b));
+        assert_eq!(Point::new(2.5, -1.0), a.with_offset(1.0, 1.0));
+    }
+
+    #[test]
+    fn test_format() {
+        let point = Point::new(-1, 2);
+        assert_eq!("(-1,2)", point.to_string());
+        assert_eq!("(-1,2)", format!("{:?}", point));
+    }
+
+    #[test]
+    fn test_point3d() {
+        let p1 = parse_all("19, 13,30", point3d).unwrap();
+        assert_eq!(Point3D::new(19.0, 13.0, 30.0), p1);
+        assert_eq!(Point::new(19.0, 13.0), p1.xy());
+        assert!(Point3D::new(19.0, 13.0, 31.0).on_top_of(&p1));
+        assert!(!p1.on_top_of(&p1));
+
+        assert_eq!("19, 13, 30", p1.to_string());
+        assert_eq!(
+            "19, 13, -2",
+            format!("{:2}", Point3D::new(19.0, 13.0, -2.0))
+        );
+        assert_eq!(
+            " 1, -5",
+            &format!("{:2}", Point3D::new(1.0, -5.0, 0.0))[..6]
+        );
+
+        assert_eq!(
+            "expected `,`, found the end of the line",
+            parse_all("19, 13", point3d).unwrap_err().message
+        );
+        assert_eq!(
+            "expected a number, found `x`",
+            parse_all("19, x, 30", point3d).unwrap_err().message
+        );
+    }
+}
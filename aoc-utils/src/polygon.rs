@@ -0,0 +1,185 @@
+use crate::Point;
+
+// Polygons are given as a list of vertices in walking order (either clockwise or
+// counter-clockwise), with the last vertex implicitly connected back to the first one.
+// Collinear vertices are fine, so a loop of grid cells can be used as is.
+
+fn edges(vertices: &[Point<i64>]) -> impl Iterator<Item = (&Point<i64>, &Point<i64>)> {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Twice the area of the polygon, which is always an integer for lattice polygons
+fn double_area(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| a.x * b.y - a.y * b.x)
+        .sum::<i64>()
+        .abs()
+}
+
+// Shoelace formula (aka Gauss's area formula): https://en.wikipedia.org/wiki/Shoelace_formula
+// Lattice polygons can have a half-integer area, which is rounded down.
+pub fn polygon_area(vertices: &[Point<i64>]) -> i64 {
+    double_area(vertices) / 2
+}
+
+// Number of lattice points lying on the edges of the polygon
+pub fn boundary_points(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd(b.x - a.x, b.y - a.y))
+        .sum()
+}
+
+// Number of lattice points strictly inside the polygon.
+// Pick's theorem (https://en.wikipedia.org/wiki/Pick%27s_theorem): A = i + b/2 - 1
+pub fn interior_points(vertices: &[Point<i64>]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+// Number of lattice points inside the polygon or on its edges
+pub fn lattice_points(vertices: &[Point<i64>]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+// Checks if the point lies on one of the edges of the polygon
+pub fn on_boundary(vertices: &[Point<i64>], point: &Point<i64>) -> bool {
+    edges(vertices).any(|(a, b)| {
+        let cross = (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x);
+        cross == 0
+            && point.x >= a.x.min(b.x)
+            && point.x <= a.x.max(b.x)
+            && point.y >= a.y.min(b.y)
+            && point.y <= a.y.max(b.y)
+    })
+}
+
+// Checks if the point lies strictly inside the polygon (points on the edges are not inside)
+pub fn point_in_polygon(vertices: &[Point<i64>], point: &Point<i64>) -> bool {
+    if on_boundary(vertices, point) {
+        return false;
+    }
+
+    // Cast a ray from the point to the east and count how many edges it crosses.
+    // Each edge includes its lower end and excludes its upper one, so a ray going
+    // through a vertex is only counted once.
+    let mut inside = false;
+    for (a, b) in edges(vertices) {
+        if (a.y > point.y) == (b.y > point.y) {
+            continue;
+        }
+
+        // Compare point.x with the x of the crossing without dividing: the crossing is
+        // to the east if (point.x - a.x) < (point.y - a.y) * (b.x - a.x) / (b.y - a.y)
+        let lhs = (point.x - a.x) * (b.y - a.y);
+        let rhs = (point.y - a.y) * (b.x - a.x);
+        if (b.y > a.y && lhs < rhs) || (b.y < a.y && lhs > rhs) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: i64) -> Vec<Point<i64>> {
+        vec![
+            Point::new(0, 0),
+            Point::new(size, 0),
+            Point::new(size, size),
+            Point::new(0, size),
+        ]
+    }
+
+    #[test]
+    fn test_polygon_area() {
+        assert_eq!(4, polygon_area(&square(2)));
+
+        // Same square walked the other way around
+        let mut reversed = square(2);
+        reversed.reverse();
+        assert_eq!(4, polygon_area(&reversed));
+
+        // A triangle with an area of 4.5
+        let triangle = vec![Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)];
+        assert_eq!(4, polygon_area(&triangle));
+    }
+
+    #[test]
+    fn test_lattice_points() {
+        let square = square(2);
+        assert_eq!(8, boundary_points(&square));
+        assert_eq!(1, interior_points(&square));
+        assert_eq!(9, lattice_points(&square));
+
+        // Diagonal edges only go through some of the lattice points
+        let triangle = vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 2)];
+        assert_eq!(8, boundary_points(&triangle));
+        assert_eq!(1, interior_points(&triangle));
+    }
+
+    #[test]
+    fn test_collinear_vertices() {
+        // A 1x1 loop of cells going around a single tile, with every cell as a vertex
+        let ring = vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(2, 0),
+            Point::new(2, 1),
+            Point::new(2, 2),
+            Point::new(1, 2),
+            Point::new(0, 2),
+            Point::new(0, 1),
+        ];
+        assert_eq!(4, polygon_area(&ring));
+        assert_eq!(1, interior_points(&ring));
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        //  ###.###
+        //  #.#.#.#
+        //  #.###.#
+        //  #.....#
+        //  #######
+        let u_shape = vec![
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 2),
+            Point::new(4, 2),
+            Point::new(4, 0),
+            Point::new(6, 0),
+            Point::new(6, 4),
+            Point::new(0, 4),
+        ];
+
+        assert!(point_in_polygon(&u_shape, &Point::new(1, 1)));
+        assert!(point_in_polygon(&u_shape, &Point::new(3, 3)));
+        assert!(point_in_polygon(&u_shape, &Point::new(5, 1)));
+        assert!(!point_in_polygon(&u_shape, &Point::new(3, 1)));
+        assert!(!point_in_polygon(&u_shape, &Point::new(7, 2)));
+        assert!(!point_in_polygon(&u_shape, &Point::new(-1, 0)));
+
+        // Edges and vertices are not inside
+        assert!(on_boundary(&u_shape, &Point::new(2, 1)));
+        assert!(on_boundary(&u_shape, &Point::new(6, 4)));
+        assert!(!point_in_polygon(&u_shape, &Point::new(2, 1)));
+        assert!(!point_in_polygon(&u_shape, &Point::new(6, 4)));
+        assert!(!on_boundary(&u_shape, &Point::new(3, 1)));
+
+        // Every interior lattice point is found by the ray casting
+        let inside = (0..=6)
+            .flat_map(|x| (0..=4).map(move |y| Point::new(x, y)))
+            .filter(|p| point_in_polygon(&u_shape, p))
+            .count() as i64;
+        assert_eq!(interior_points(&u_shape), inside);
+    }
+}
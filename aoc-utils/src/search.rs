@@ -0,0 +1,280 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+use fxhash::FxHashMap;
+use num_traits::Zero;
+
+// The cheapest way to reach a goal state: its total cost and all the states
+// visited along the way (starting with the start state and ending with the goal)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPath<S, C> {
+    pub cost: C,
+    pub path: Vec<S>,
+}
+
+impl<S, C> ShortestPath<S, C> {
+    pub fn goal(&self) -> &S {
+        self.path.last().unwrap()
+    }
+}
+
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    parent: Option<usize>,
+}
+
+// Dijkstra's algorithm over an implicit graph of search states.
+// The successors function returns all states reachable from a given state along with the cost
+// of the move. Returns None if no goal state can be reached.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+// Same as `dijkstra`, for states that can be numbered from 0 up to `size` (e.g. positions on
+// a grid). Looking the states up in a table by their index is a lot faster than hashing them.
+#[tracing::instrument(skip_all)]
+pub fn dijkstra_indexed<S, C, I>(
+    start: S,
+    size: usize,
+    index: impl Fn(&S) -> usize,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPath<S, C>>
+where
+    S: Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let known = IndexedNodes {
+        index,
+        nodes: vec![None; size],
+    };
+    search(start, known, successors, |_| C::zero(), is_goal)
+}
+
+// A* search over an implicit graph of search states.
+// The heuristic must never overestimate the remaining cost to a goal (e.g. manhattan distance
+// to the target on a grid where each move costs at least 1), otherwise the result may not be optimal.
+#[tracing::instrument(skip_all)]
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // Search states are small and hashed a lot, so skip the DoS resistant default hasher
+    let known = FxHashMap::default();
+    search(start, known, successors, heuristic, is_goal)
+}
+
+// Where the search remembers which node each state it has seen is stored in
+trait KnownNodes<S> {
+    fn get(&self, state: &S) -> Option<usize>;
+    fn insert(&mut self, state: &S, idx: usize);
+}
+
+impl<S: Clone + Eq + Hash> KnownNodes<S> for FxHashMap<S, usize> {
+    fn get(&self, state: &S) -> Option<usize> {
+        HashMap::get(self, state).copied()
+    }
+
+    fn insert(&mut self, state: &S, idx: usize) {
+        HashMap::insert(self, state.clone(), idx);
+    }
+}
+
+struct IndexedNodes<F> {
+    index: F,
+    nodes: Vec<Option<usize>>,
+}
+
+impl<S, F: Fn(&S) -> usize> KnownNodes<S> for IndexedNodes<F> {
+    fn get(&self, state: &S) -> Option<usize> {
+        self.nodes[(self.index)(state)]
+    }
+
+    fn insert(&mut self, state: &S, idx: usize) {
+        self.nodes[(self.index)(state)] = Some(idx);
+    }
+}
+
+fn search<S, C, I>(
+    start: S,
+    mut known: impl KnownNodes<S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPath<S, C>>
+where
+    S: Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    known.insert(&start, 0);
+    let mut nodes = vec![Node {
+        state: start.clone(),
+        cost: C::zero(),
+        parent: None,
+    }];
+
+    // Entries are (estimated total cost, cost so far, node index), cheapest estimate first
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((heuristic(&start), C::zero(), 0)));
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        // Skip stale entries for nodes we have since reached in a cheaper way
+        if cost > nodes[idx].cost {
+            continue;
+        }
+
+        if is_goal(&nodes[idx].state) {
+            return Some(ShortestPath {
+                cost,
+                path: reconstruct_path(&nodes, idx),
+            });
+        }
+
+        for (next, step_cost) in successors(&nodes[idx].state) {
+            let next_cost = cost + step_cost;
+            let next_idx = match known.get(&next) {
+                Some(next_idx) => {
+                    if next_cost >= nodes[next_idx].cost {
+                        continue;
+                    }
+                    nodes[next_idx].cost = next_cost;
+                    nodes[next_idx].parent = Some(idx);
+                    next_idx
+                }
+                None => {
+                    known.insert(&next, nodes.len());
+                    nodes.push(Node {
+                        state: next,
+                        cost: next_cost,
+                        parent: Some(idx),
+                    });
+                    nodes.len() - 1
+                }
+            };
+
+            let estimate = next_cost + heuristic(&nodes[next_idx].state);
+            queue.push(Reverse((estimate, next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+fn reconstruct_path<S: Clone, C>(nodes: &[Node<S, C>], goal: usize) -> Vec<S> {
+    let mut path = Vec::new();
+    let mut current = Some(goal);
+    while let Some(idx) = current {
+        path.push(nodes[idx].state.clone());
+        current = nodes[idx].parent;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharMap, Point};
+
+    #[test]
+    fn test_dijkstra_graph() {
+        // a -1-> b -1-> c -1-> d, with a direct a -5-> d shortcut that is more expensive
+        let edges = HashMap::from([
+            ('a', vec![('b', 1), ('d', 5)]),
+            ('b', vec![('c', 1)]),
+            ('c', vec![('d', 1)]),
+            ('d', vec![]),
+        ]);
+
+        let result = dijkstra('a', |s| edges[s].clone(), |s| *s == 'd').unwrap();
+        assert_eq!(3, result.cost);
+        assert_eq!(vec!['a', 'b', 'c', 'd'], result.path);
+        assert_eq!('d', *result.goal());
+    }
+
+    #[test]
+    fn test_start_is_goal() {
+        let result = dijkstra(1u32, |_| vec![(2, 1u32)], |s| *s == 1).unwrap();
+        assert_eq!(0, result.cost);
+        assert_eq!(vec![1], result.path);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let result = dijkstra(0u32, |s| (*s < 10).then_some((s + 1, 1u32)), |s| *s == 11);
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        let map = CharMap::from_str_with_trim(
+            "S...#
+             .##.#
+             ...#.
+             #.#..
+             ....E",
+            '#',
+        );
+        let start = map.find('S').unwrap();
+        let end = map.find('E').unwrap();
+
+        let successors = |p: &Point<i64>| {
+            p.neighbours_list()
+                .into_iter()
+                .filter(|n| *map.cell_for_point(n) != '#')
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let slow = dijkstra(start, successors, |p| *p == end).unwrap();
+        let fast = astar(
+            start,
+            successors,
+            |p| p.manhattan_distance(&end),
+            |p| *p == end,
+        )
+        .unwrap();
+
+        let width = map.width() as i64;
+        let size = map.width() * map.height();
+        let indexed = dijkstra_indexed(
+            start,
+            size,
+            |p| (p.y * width + p.x) as usize,
+            successors,
+            |p| *p == end,
+        )
+        .unwrap();
+
+        assert_eq!(8, slow.cost);
+        assert_eq!(slow, indexed);
+        assert_eq!(slow.cost, fast.cost);
+        assert_eq!(9, fast.path.len());
+        for pair in fast.path.windows(2) {
+            assert_eq!(1, pair[0].manhattan_distance(&pair[1]));
+        }
+    }
+}
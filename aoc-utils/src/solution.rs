@@ -0,0 +1,234 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+// A parameter a solution accepts on top of the puzzle input (e.g. the number of steps in day 21)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: &'static str,
+}
+
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    #[error("invalid parameter `{0}`")]
+    #[diagnostic(code(aoc::invalid_param), help("parameters are passed as `key=value`"))]
+    Invalid(String),
+
+    #[error("unknown parameter `{key}`")]
+    #[diagnostic(code(aoc::unknown_param), help("supported parameters: {known}"))]
+    Unknown { key: String, known: String },
+
+    #[error("missing value for parameter `{0}`")]
+    #[diagnostic(code(aoc::missing_param))]
+    Missing(String),
+
+    #[error("invalid value `{value}` for parameter `{key}`: {reason}")]
+    #[diagnostic(code(aoc::invalid_param_value))]
+    InvalidValue {
+        key: String,
+        value: String,
+        reason: String,
+    },
+}
+
+// Parameter values given to a solution, as `key=value` strings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Parses a list of `key=value` pairs
+    pub fn parse<S: AsRef<str>>(pairs: &[S]) -> Result<Self, ParamError> {
+        let mut params = Self::new();
+        for pair in pairs {
+            let pair = pair.as_ref();
+            match pair.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    params.set(key.trim(), value.trim());
+                }
+                _ => return Err(ParamError::Invalid(pair.to_string())),
+            }
+        }
+        Ok(params)
+    }
+
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn with(mut self, key: &str, value: impl ToString) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    // Checks the values against the schema and fills in the defaults for missing ones
+    pub fn resolve(&self, schema: &[Param]) -> Result<Self, ParamError> {
+        if let Some(key) = self
+            .values
+            .keys()
+            .find(|key| !schema.iter().any(|p| p.name == *key))
+        {
+            let known = schema.iter().map(|p| p.name).collect::<Vec<_>>();
+            let known = if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            };
+            return Err(ParamError::Unknown {
+                key: key.clone(),
+                known,
+            });
+        }
+
+        let mut resolved = self.clone();
+        for param in schema {
+            if !resolved.values.contains_key(param.name) {
+                resolved.set(param.name, param.default);
+            }
+        }
+        Ok(resolved)
+    }
+
+    pub fn get<T>(&self, key: &str) -> Result<T, ParamError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .values
+            .get(key)
+            .ok_or_else(|| ParamError::Missing(key.to_string()))?;
+
+        value
+            .parse()
+            .map_err(|err: T::Err| ParamError::InvalidValue {
+                key: key.to_string(),
+                value: value.clone(),
+                reason: err.to_string(),
+            })
+    }
+}
+
+// Name of the implementation of a part when there's just one
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+// A solution for one part of a day, split into parsing the puzzle input into a typed
+// representation and solving the puzzle for it, so tooling can drive (and time) both steps.
+pub trait Solution {
+    const DAY: u8;
+    const PART: u8;
+
+    // Tells the implementations of a part apart, when it's solved in more than one way
+    const NAME: &'static str = DEFAULT_IMPLEMENTATION;
+
+    // Parameters accepted by `solve`, along with their default values
+    const PARAMS: &'static [Param] = &[];
+
+    type Input;
+    type Error: Diagnostic + From<ParamError> + Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error>;
+
+    // Receives the parameters checked against `PARAMS`, with defaults filled in
+    fn solve(input: Self::Input, params: &Params) -> Result<String, Self::Error>;
+
+    fn params(params: &Params) -> Result<Params, Self::Error> {
+        Ok(params.resolve(Self::PARAMS)?)
+    }
+
+    // Parses and solves the puzzle input in one go, each step in a span of its own
+    fn run(input: &str, params: &Params) -> Result<String, Self::Error> {
+        let params = Self::params(params)?;
+        let input = tracing::info_span!("parse", day = Self::DAY, part = Self::PART)
+            .in_scope(|| Self::parse(input))?;
+        tracing::info_span!("solve", day = Self::DAY, part = Self::PART)
+            .in_scope(|| Self::solve(input, &params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &[Param] = &[Param {
+        name: "max_steps",
+        description: "number of steps",
+        default: "64",
+    }];
+
+    #[test]
+    fn test_parse() {
+        let params = Params::parse(&["max_steps=10", "min = 1.5"]).unwrap();
+        assert_eq!(10, params.get::<u64>("max_steps").unwrap());
+        assert_eq!(1.5, params.get::<f64>("min").unwrap());
+        assert_eq!(
+            ParamError::Missing("max".to_string()),
+            params.get::<f64>("max").unwrap_err()
+        );
+
+        assert!(Params::parse(&["max_steps"]).is_err());
+        assert!(Params::parse(&["=64"]).is_err());
+
+        let params = Params::parse(&["max_steps=many"]).unwrap();
+        let err = params.get::<u64>("max_steps").unwrap_err();
+        assert!(matches!(err, ParamError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let params = Params::new().resolve(SCHEMA).unwrap();
+        assert_eq!(64, params.get::<u64>("max_steps").unwrap());
+
+        let params = Params::new().with("max_steps", 6).resolve(SCHEMA).unwrap();
+        assert_eq!(6, params.get::<u64>("max_steps").unwrap());
+
+        let err = Params::new().with("steps", 6).resolve(SCHEMA).unwrap_err();
+        assert_eq!(
+            ParamError::Unknown {
+                key: "steps".to_string(),
+                known: "max_steps".to_string()
+            },
+            err
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        struct Steps;
+
+        impl Solution for Steps {
+            const DAY: u8 = 21;
+            const PART: u8 = 1;
+            const PARAMS: &'static [Param] = SCHEMA;
+
+            type Input = u64;
+            type Error = ParamError;
+
+            fn parse(input: &str) -> Result<u64, ParamError> {
+                input
+                    .trim()
+                    .parse()
+                    .map_err(|_| ParamError::Invalid(input.to_string()))
+            }
+
+            fn solve(input: u64, params: &Params) -> Result<String, ParamError> {
+                Ok((input * params.get::<u64>("max_steps")?).to_string())
+            }
+        }
+
+        assert_eq!("128", Steps::run("2", &Params::new()).unwrap());
+        assert_eq!(
+            "20",
+            Steps::run("2", &Params::new().with("max_steps", 10)).unwrap()
+        );
+        assert!(Steps::run("2", &Params::new().with("steps", 10)).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use aoc_utils::{Answers, InputSource, Submission, ANSWERS_FILE};
+
+use crate::custom_error::AocError;
+
+// The answers for an input are kept next to it, so inputs from `AOC_INPUT_DIR`
+// are checked against the answers in that directory. Input piped through stdin
+// has nowhere to keep them.
+pub fn answers_path(source: &InputSource, dir: &str) -> Option<PathBuf> {
+    match source {
+        InputSource::File(path) => Some(path.with_file_name(ANSWERS_FILE)),
+        InputSource::Stdin => None,
+        InputSource::Embedded => Some(Path::new(dir).join(ANSWERS_FILE)),
+    }
+}
+
+pub fn load_answers(path: &Path) -> Result<Answers, AocError> {
+    if !path.is_file() {
+        return Ok(Answers::default());
+    }
+    let text = std::fs::read_to_string(path)?;
+    Ok(Answers::parse(&text)?)
+}
+
+// Appends a submission to the answers file, keeping whatever is already in it
+pub fn record_answer(path: &Path, submission: &Submission) -> Result<(), AocError> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", submission)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::Verdict;
+
+    use super::*;
+
+    #[test]
+    fn test_answers_path() {
+        let path = PathBuf::from("/inputs/day-07/input2.txt");
+        assert_eq!(
+            Some(PathBuf::from("/inputs/day-07/answers.txt")),
+            answers_path(&InputSource::File(path), "/repo/day-07")
+        );
+        assert_eq!(
+            Some(PathBuf::from("/repo/day-07/answers.txt")),
+            answers_path(&InputSource::Embedded, "/repo/day-07")
+        );
+        assert_eq!(None, answers_path(&InputSource::Stdin, "/repo/day-07"));
+    }
+
+    #[test]
+    fn test_record_answer() {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(ANSWERS_FILE);
+        std::fs::write(&path, "# Day 7\npart1: 12 - too low\n").unwrap();
+
+        let submission = Submission {
+            part: 1,
+            answer: "15".to_string(),
+            verdict: Verdict::Correct,
+        };
+        record_answer(&path, &submission).unwrap();
+
+        let answers = load_answers(&path).unwrap();
+        assert_eq!(2, answers.submissions.len());
+        assert_eq!(Some("15"), answers.correct(1));
+        assert!(load_answers(&dir.join("missing.txt"))
+            .unwrap()
+            .submissions
+            .is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
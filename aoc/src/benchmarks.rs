@@ -0,0 +1,388 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    time::Duration,
+};
+
+use aoc_utils::{Params, DEFAULT_IMPLEMENTATION};
+
+use crate::{
+    custom_error::AocError,
+    registry::{Part, Phases},
+    verify::solved_input,
+};
+
+// Benchmark results of every machine, kept at the root of the workspace so they can be
+// committed and compared between machines
+pub const BENCHMARKS_FILE: &str = "benchmarks.csv";
+
+const HEADER: &str = "machine,commit,day,part,implementation,parse_ns,solve_ns,total_ns";
+
+pub fn benchmarks_path() -> PathBuf {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    workspace.join(BENCHMARKS_FILE)
+}
+
+// Median timings of one implementation of a part, measured on a machine at a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub machine: String,
+    pub commit: String,
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    pub parse: Duration,
+    pub solve: Duration,
+    pub total: Duration,
+}
+
+impl Record {
+    pub fn name(&self) -> String {
+        Part::format_name(self.day, self.part, &self.implementation)
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{}",
+            self.machine,
+            self.commit,
+            self.day,
+            self.part,
+            self.implementation,
+            self.parse.as_nanos(),
+            self.solve.as_nanos(),
+            self.total.as_nanos()
+        )
+    }
+}
+
+impl FromStr for Record {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s.split(',').map(str::trim).collect::<Vec<_>>();
+        let [machine, commit, day, part, implementation, parse, solve, total] = fields[..] else {
+            return Err(format!("expected 8 fields, found {}", fields.len()));
+        };
+
+        let number = |field: &str, name: &str| {
+            field
+                .parse::<u64>()
+                .map_err(|_| format!("invalid {} `{}`", name, field))
+        };
+        Ok(Self {
+            machine: machine.to_string(),
+            commit: commit.to_string(),
+            day: number(day, "day")? as u8,
+            part: number(part, "part")? as u8,
+            implementation: implementation.to_string(),
+            parse: Duration::from_nanos(number(parse, "parse_ns")?),
+            solve: Duration::from_nanos(number(solve, "solve_ns")?),
+            total: Duration::from_nanos(number(total, "total_ns")?),
+        })
+    }
+}
+
+pub fn load_records(path: &Path) -> Result<Vec<Record>, AocError> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let text = std::fs::read_to_string(path)?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && line.trim() != HEADER)
+        .map(|(idx, line)| {
+            line.parse().map_err(|reason| AocError::InvalidBenchmark {
+                line: idx + 1,
+                reason,
+            })
+        })
+        .collect()
+}
+
+// Appends records to the file, writing the header first when it's a new one
+pub fn record_benchmarks(path: &Path, records: &[Record]) -> Result<(), AocError> {
+    let is_new = !path.is_file();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if is_new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    for record in records {
+        writeln!(file, "{}", record)?;
+    }
+    Ok(())
+}
+
+// Runs a part with its default parameters the given number of times and returns the
+// median of each phase, or nothing when the part isn't solved yet
+pub fn measure(part: &Part, samples: usize) -> miette::Result<Option<(Phases, Duration)>> {
+    let Some((input, _)) = solved_input(part)? else {
+        return Ok(None);
+    };
+
+    let mut runs = Vec::with_capacity(samples);
+    for _ in 0..samples.max(1) {
+        let (_, phases) = (part.run)(&input.text, &Params::new())?;
+        runs.push(phases);
+    }
+    let phases = Phases {
+        parse: median(runs.iter().map(|phases| phases.parse)),
+        solve: median(runs.iter().map(|phases| phases.solve)),
+    };
+    Ok(Some((phases, median(runs.iter().map(Phases::total)))))
+}
+
+fn median(durations: impl Iterator<Item = Duration>) -> Duration {
+    let mut durations = durations.collect::<Vec<_>>();
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+// Records of one run of the benchmarks: a machine and either a commit or, when it's
+// left out, the last commit benchmarked on that machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub machine: String,
+    pub commit: Option<String>,
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (machine, commit) = match s.split_once('@') {
+            Some((machine, commit)) => (machine, Some(commit.to_string())),
+            None => (s, None),
+        };
+        check_name(machine)?;
+        Ok(Self {
+            machine: machine.to_string(),
+            commit,
+        })
+    }
+}
+
+impl Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.commit {
+            Some(commit) => write!(f, "{}@{}", self.machine, commit),
+            None => write!(f, "{}", self.machine),
+        }
+    }
+}
+
+// Machine names end up in the CSV file and in selectors
+pub fn check_name(machine: &str) -> Result<String, String> {
+    if machine.is_empty() || machine.contains([',', '@']) {
+        return Err(format!("invalid machine name `{}`", machine));
+    }
+    Ok(machine.to_string())
+}
+
+// Picks the records of the selected run, keeping the last record of each implementation
+// of a part when it was benchmarked more than once
+pub fn select<'a>(records: &'a [Record], selector: &Selector) -> Result<Vec<&'a Record>, AocError> {
+    let on_machine = records
+        .iter()
+        .filter(|record| record.machine == selector.machine);
+    let commit = match &selector.commit {
+        Some(commit) => Some(commit.as_str()),
+        None => on_machine
+            .clone()
+            .next_back()
+            .map(|record| record.commit.as_str()),
+    };
+
+    let mut selected: Vec<&Record> = Vec::new();
+    for record in on_machine.filter(|record| Some(record.commit.as_str()) == commit) {
+        let key = (record.day, record.part, &record.implementation);
+        selected.retain(|other| (other.day, other.part, &other.implementation) != key);
+        selected.push(record);
+    }
+    if selected.is_empty() {
+        return Err(AocError::NoBenchmarks {
+            selector: selector.to_string(),
+        });
+    }
+    selected.sort_by_key(|record| {
+        let is_default = record.implementation == DEFAULT_IMPLEMENTATION;
+        (record.day, record.part, !is_default)
+    });
+    Ok(selected)
+}
+
+// The median total time of an implementation of a part in two runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub name: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    // How much slower the current run is, in percent (negative when it got faster)
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        (self.current.as_secs_f64() - baseline) / baseline * 100.0
+    }
+
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+// Pairs up the parts benchmarked in both runs
+pub fn compare(baseline: &[&Record], current: &[&Record]) -> Vec<Comparison> {
+    let baseline = baseline
+        .iter()
+        .map(|record| ((record.day, record.part, &record.implementation), record))
+        .collect::<HashMap<_, _>>();
+    current
+        .iter()
+        .filter_map(|record| {
+            let key = (record.day, record.part, &record.implementation);
+            baseline.get(&key).map(|base| Comparison {
+                name: record.name(),
+                baseline: base.total,
+                current: record.total,
+            })
+        })
+        .collect()
+}
+
+// Short hash of the checked out commit, marked as dirty when there are local changes
+pub fn current_commit() -> Result<String, AocError> {
+    let hash = git(&["rev-parse", "--short", "HEAD"])?;
+    if git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        Ok(hash)
+    } else {
+        Ok(format!("{}-dirty", hash))
+    }
+}
+
+fn git(args: &[&str]) -> Result<String, AocError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()?;
+    if !output.status.success() {
+        return Err(AocError::UnknownCommit(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Name of the machine the benchmarks run on, unless one is given explicitly
+pub fn current_machine() -> String {
+    Command::new("hostname")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .and_then(|name| check_name(&name).ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(machine: &str, commit: &str, day: u8, total_us: u64) -> Record {
+        Record {
+            machine: machine.to_string(),
+            commit: commit.to_string(),
+            day,
+            part: 1,
+            implementation: "default".to_string(),
+            parse: Duration::from_micros(total_us / 4),
+            solve: Duration::from_micros(total_us - total_us / 4),
+            total: Duration::from_micros(total_us),
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let line = "mac,811eba9,1,2,aho_corasick,204980,243944,448924";
+        let record = line.parse::<Record>().unwrap();
+        assert_eq!("day-01 part2 (aho_corasick)", record.name());
+        assert_eq!(Duration::from_nanos(448924), record.total);
+        assert_eq!(line, record.to_string());
+
+        assert!("mac,811eba9,1,2,default,1,2".parse::<Record>().is_err());
+        assert!("mac,811eba9,day-01,2,default,1,2,3"
+            .parse::<Record>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_record_benchmarks() {
+        let dir = std::env::temp_dir().join(format!("aoc-benchmarks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(BENCHMARKS_FILE);
+
+        let records = vec![record("mac", "aaa", 1, 100), record("pc", "aaa", 1, 80)];
+        record_benchmarks(&path, &records[..1]).unwrap();
+        record_benchmarks(&path, &records[1..]).unwrap();
+        assert_eq!(records, load_records(&path).unwrap());
+        assert_eq!(
+            1,
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .matches(HEADER)
+                .count()
+        );
+
+        std::fs::write(&path, format!("{}\nmac,aaa,1\n", HEADER)).unwrap();
+        let err = load_records(&path).unwrap_err();
+        assert!(matches!(err, AocError::InvalidBenchmark { line: 2, .. }));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_select() {
+        let records = vec![
+            record("mac", "aaa", 1, 100),
+            record("mac", "aaa", 2, 200),
+            record("pc", "aaa", 1, 80),
+            record("mac", "bbb", 1, 150),
+            record("mac", "bbb", 1, 120),
+        ];
+
+        let latest = select(&records, &"mac".parse().unwrap()).unwrap();
+        assert_eq!(vec![&records[4]], latest);
+
+        let older = select(&records, &"mac@aaa".parse().unwrap()).unwrap();
+        assert_eq!(vec![&records[0], &records[1]], older);
+
+        let err = select(&records, &"mac@ccc".parse().unwrap()).unwrap_err();
+        assert!(matches!(err, AocError::NoBenchmarks { .. }));
+        assert!("mac,pc".parse::<Selector>().is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = [record("mac", "aaa", 1, 100), record("mac", "aaa", 2, 200)];
+        let current = [record("mac", "bbb", 1, 125), record("mac", "bbb", 3, 10)];
+        let comparisons = compare(
+            &baseline.iter().collect::<Vec<_>>(),
+            &current.iter().collect::<Vec<_>>(),
+        );
+
+        assert_eq!(1, comparisons.len());
+        assert_eq!("day-01 part1", comparisons[0].name);
+        assert!((comparisons[0].change() - 25.0).abs() < 1e-9);
+        assert!(comparisons[0].regressed(10.0));
+        assert!(!comparisons[0].regressed(30.0));
+    }
+}
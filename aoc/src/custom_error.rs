@@ -0,0 +1,71 @@
+use aoc_utils::AnswersError;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("there is no solution for day {day} part {part}")]
+    #[diagnostic(code(aoc::unknown_part), help("run `aoc list` to see all solutions"))]
+    UnknownPart { day: u8, part: u8 },
+
+    #[error("there is no `{name}` implementation of day {day} part {part}")]
+    #[diagnostic(
+        code(aoc::unknown_implementation),
+        help("run `aoc list` to see all solutions")
+    )]
+    UnknownImplementation { day: u8, part: u8, name: String },
+
+    #[error("{name} answered {answer}, but {default} answered {expected}")]
+    #[diagnostic(code(aoc::implementations_disagree))]
+    Disagreement {
+        name: String,
+        answer: String,
+        default: String,
+        expected: String,
+    },
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    AnswersError(#[from] AnswersError),
+
+    #[error("invalid benchmark record on line {line}: {reason}")]
+    #[diagnostic(
+        code(aoc::invalid_benchmark),
+        help("records are written as `machine,commit,day,part,implementation,parse_ns,solve_ns,total_ns`")
+    )]
+    InvalidBenchmark { line: usize, reason: String },
+
+    #[error("there are no benchmarks recorded for {selector}")]
+    #[diagnostic(
+        code(aoc::no_benchmarks),
+        help("record them with `aoc bench`, or pick another machine or commit")
+    )]
+    NoBenchmarks { selector: String },
+
+    #[error("can't tell which commit is checked out: {0}")]
+    #[diagnostic(code(aoc::unknown_commit), help("pass the commit with `--commit`"))]
+    UnknownCommit(String),
+
+    #[error("{regressed} of {compared} compared parts got more than {threshold}% slower")]
+    #[diagnostic(code(aoc::bench_regressed))]
+    BenchRegressed {
+        regressed: usize,
+        compared: usize,
+        threshold: f64,
+    },
+
+    #[error("heap profiling is not enabled in this build")]
+    #[diagnostic(
+        code(aoc::heap_profiling_disabled),
+        help("run it with `cargo run --profile dhat -p aoc --features dhat-heap -- heap`")
+    )]
+    HeapProfilingDisabled,
+
+    #[error("{failed} of {checked} checked parts did not produce their accepted answer")]
+    #[diagnostic(code(aoc::verify_failed))]
+    VerifyFailed { failed: usize, checked: usize },
+}
@@ -0,0 +1,118 @@
+use std::fmt::Write;
+
+// What a part allocated while parsing and solving its input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeapUsage {
+    // Number of allocations made over the whole run
+    pub total_blocks: u64,
+    pub total_bytes: u64,
+    // Bytes (and allocations) that were live at the peak of the run
+    pub peak_blocks: u64,
+    pub peak_bytes: u64,
+}
+
+// Runs a part with its default parameters under dhat, or returns nothing when the part
+// isn't solved yet. Only the allocations made by the part itself are counted, the input
+// is loaded before profiling starts.
+#[cfg(feature = "dhat-heap")]
+pub fn profile(part: &crate::registry::Part) -> miette::Result<Option<HeapUsage>> {
+    use aoc_utils::Params;
+
+    use crate::verify::solved_input;
+
+    let Some((input, _)) = solved_input(part)? else {
+        return Ok(None);
+    };
+
+    // A testing profiler keeps the stats in memory instead of writing dhat-heap.json
+    let profiler = dhat::Profiler::builder().testing().build();
+    let result = (part.run)(&input.text, &Params::new());
+    let stats = dhat::HeapStats::get();
+    drop(profiler);
+
+    result?;
+    Ok(Some(HeapUsage {
+        total_blocks: stats.total_blocks,
+        total_bytes: stats.total_bytes,
+        peak_blocks: stats.max_blocks as u64,
+        peak_bytes: stats.max_bytes as u64,
+    }))
+}
+
+// Lists the parts by the number of bytes they allocated, the heaviest first
+pub fn format_table(usages: &[(String, HeapUsage)]) -> String {
+    let mut usages = usages.iter().collect::<Vec<_>>();
+    usages.sort_by_key(|(_, usage)| std::cmp::Reverse(usage.total_bytes));
+
+    let width = usages
+        .iter()
+        .map(|(name, _)| name.len())
+        .chain(["part".len()])
+        .max()
+        .unwrap();
+    let mut table = format!(
+        "{:<width$}  {:>12}  {:>10}  {:>10}  {:>11}\n",
+        "part", "allocations", "total", "peak", "peak blocks"
+    );
+    for (name, usage) in usages {
+        writeln!(
+            table,
+            "{:<width$}  {:>12}  {:>10}  {:>10}  {:>11}",
+            name,
+            usage.total_blocks,
+            format_bytes(usage.total_bytes),
+            format_bytes(usage.peak_bytes),
+            usage.peak_blocks
+        )
+        .unwrap();
+    }
+    table
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("3.0 MiB", format_bytes(3 * 1024 * 1024));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let usage = |total_bytes| HeapUsage {
+            total_blocks: 10,
+            total_bytes,
+            peak_blocks: 2,
+            peak_bytes: 100,
+        };
+        let table = format_table(&[
+            ("day-01 part1".to_string(), usage(2048)),
+            ("day-23 part2".to_string(), usage(5 * 1024 * 1024)),
+        ]);
+
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(3, lines.len());
+        assert!(lines[0].starts_with("part  "));
+        assert!(lines[1].starts_with("day-23 part2"));
+        assert!(lines[1].contains("5.0 MiB"));
+        assert!(lines[2].ends_with("2.0 KiB       100 B            2"));
+    }
+}
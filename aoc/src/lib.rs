@@ -0,0 +1,6 @@
+pub mod answers;
+pub mod benchmarks;
+pub mod custom_error;
+pub mod heap;
+pub mod registry;
+pub mod verify;
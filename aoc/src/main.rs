@@ -0,0 +1,391 @@
+use aoc_utils::{InputLoader, Params, Submission, Verdict};
+use clap::{Parser, Subcommand};
+use miette::Context;
+
+use aoc::{
+    answers::{answers_path, load_answers, record_answer},
+    benchmarks::{
+        benchmarks_path, check_name, compare, current_commit, current_machine, load_records,
+        measure, record_benchmarks, select, Record, Selector,
+    },
+    custom_error::AocError,
+    registry,
+    verify::{cross_check, verify, Status},
+};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[derive(Parser, Debug)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve a part of a day
+    Run {
+        /// Day number (1-25)
+        day: u8,
+
+        /// Part number (1 or 2)
+        part: u8,
+
+        /// Read the puzzle input from a file (`-` for stdin) instead of the day's input file
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Use another implementation of the part, or `all` to run and compare all of them
+        #[arg(long, value_name = "NAME")]
+        implementation: Option<String>,
+
+        /// Extra parameter for the solution, e.g. `--param max_steps=64`
+        #[arg(long = "param", value_name = "KEY=VALUE")]
+        params: Vec<String>,
+
+        /// Record the answer in the day's answers.txt with the verdict it got,
+        /// e.g. `--record too-low`
+        #[arg(long, value_name = "VERDICT")]
+        record: Option<Verdict>,
+    },
+
+    /// List all available solutions
+    List,
+
+    /// Run every solved part and check it still produces the accepted answer
+    Verify {
+        /// Only check this day
+        #[arg(long)]
+        day: Option<u8>,
+    },
+
+    /// Time every solved part and append the medians to benchmarks.csv
+    Bench {
+        /// Only time this day
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Number of times each part is run
+        #[arg(long, default_value_t = 10)]
+        samples: usize,
+
+        /// Name of this machine, the hostname by default
+        #[arg(long, value_parser = check_name)]
+        machine: Option<String>,
+
+        /// Commit the timings are recorded for, the checked out one by default
+        #[arg(long)]
+        commit: Option<String>,
+    },
+
+    /// Compare recorded benchmarks with a baseline and flag the parts that got slower
+    Compare {
+        /// Benchmarks to compare against, as `machine@commit`, or just `machine` for the
+        /// last commit benchmarked on it
+        baseline: Selector,
+
+        /// Benchmarks to check, the last ones recorded on this machine by default
+        #[arg(long)]
+        current: Option<Selector>,
+
+        /// How much slower (in percent) the median of a part may get before it's flagged
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+
+    /// Count the allocations of every solved part under dhat and list them, heaviest first
+    Heap {
+        /// Only profile this day
+        #[arg(long)]
+        day: Option<u8>,
+    },
+}
+
+fn run(
+    day: u8,
+    part: u8,
+    input: Option<String>,
+    implementation: Option<&str>,
+    params: &[String],
+    record: Option<Verdict>,
+) -> miette::Result<()> {
+    let solution = registry::find(day, part).ok_or(AocError::UnknownPart { day, part })?;
+    let implementations = match implementation {
+        None => vec![solution],
+        Some("all") => registry::implementations(day, part),
+        Some(name) => {
+            let implementation =
+                registry::find_implementation(day, part, name).ok_or_else(|| {
+                    AocError::UnknownImplementation {
+                        day,
+                        part,
+                        name: name.to_string(),
+                    }
+                })?;
+            vec![implementation]
+        }
+    };
+    let solution = &implementations[0];
+    let params = Params::parse(params)?;
+
+    let input = InputLoader::new(day, part)
+        .path(input)
+        .dir(solution.dir)
+        .embedded(solution.input)
+        .load()?;
+    tracing::debug!(source = ?input.source, "loaded input");
+
+    let runs = cross_check(&implementations, &input.text, &params)?;
+    for run in &runs {
+        println!("{}: {}", run.name, run.answer);
+        println!(
+            "Elapsed: {:?} (parse: {:?}, solve: {:?})",
+            run.phases.total(),
+            run.phases.parse,
+            run.phases.solve
+        );
+    }
+    let answer = runs[0].answer.clone();
+
+    // Compare the answer with what was submitted before, so a guess that is known to be
+    // wrong doesn't get submitted again
+    let Some(answers_path) = answers_path(&input.source, solution.dir) else {
+        if record.is_some() {
+            println!("Warning: not recording the answer, there is no answers file for stdin");
+        }
+        return Ok(());
+    };
+    let answers = load_answers(&answers_path)?;
+    for warning in answers.check(part, &answer) {
+        println!("Warning: {}", warning);
+    }
+
+    if let Some(verdict) = record {
+        let submission = Submission {
+            part,
+            answer,
+            verdict,
+        };
+        record_answer(&answers_path, &submission)?;
+        println!("Recorded `{}` in {}", submission, answers_path.display());
+    }
+    Ok(())
+}
+
+fn verify_all(day: Option<u8>) -> miette::Result<()> {
+    let (mut checked, mut failed, mut unsolved) = (0, 0, 0);
+    for part in registry::parts() {
+        if day.is_some_and(|day| day != part.day) {
+            continue;
+        }
+
+        let outcome = verify(&part).with_context(|| part.name())?;
+        match &outcome.status {
+            Status::Pass => println!(
+                "{}: pass ({}) in {:?}",
+                outcome.name,
+                outcome.answer.unwrap_or_default(),
+                outcome.elapsed
+            ),
+            Status::Regression { expected } => println!(
+                "{}: REGRESSION, got {} but expected {}",
+                outcome.name,
+                outcome.answer.unwrap_or_default(),
+                expected
+            ),
+            Status::Fail(err) => println!("{}: FAIL, {}", outcome.name, err),
+            Status::Unsolved => println!("{}: unsolved", outcome.name),
+        }
+
+        match outcome.status {
+            Status::Pass => checked += 1,
+            Status::Regression { .. } | Status::Fail(_) => {
+                checked += 1;
+                failed += 1;
+            }
+            Status::Unsolved => unsolved += 1,
+        }
+    }
+
+    println!(
+        "Checked {} parts: {} passed, {} failed, {} unsolved",
+        checked,
+        checked - failed,
+        failed,
+        unsolved
+    );
+    if failed > 0 {
+        return Err(AocError::VerifyFailed { failed, checked }.into());
+    }
+    Ok(())
+}
+
+fn bench(
+    day: Option<u8>,
+    samples: usize,
+    machine: Option<String>,
+    commit: Option<String>,
+) -> miette::Result<()> {
+    let machine = machine.unwrap_or_else(current_machine);
+    let commit = match commit {
+        Some(commit) => commit,
+        None => current_commit()?,
+    };
+
+    let mut records = Vec::new();
+    for part in registry::parts() {
+        if day.is_some_and(|day| day != part.day) {
+            continue;
+        }
+
+        let Some((phases, total)) = measure(&part, samples).with_context(|| part.name())? else {
+            println!("{}: unsolved, skipped", part.name());
+            continue;
+        };
+        println!(
+            "{}: {:?} (parse: {:?}, solve: {:?})",
+            part.name(),
+            total,
+            phases.parse,
+            phases.solve
+        );
+        records.push(Record {
+            machine: machine.clone(),
+            commit: commit.clone(),
+            day: part.day,
+            part: part.part,
+            implementation: part.implementation.to_string(),
+            parse: phases.parse,
+            solve: phases.solve,
+            total,
+        });
+    }
+
+    let path = benchmarks_path();
+    record_benchmarks(&path, &records)?;
+    println!(
+        "Recorded {} parts for {}@{} in {}",
+        records.len(),
+        machine,
+        commit,
+        path.display()
+    );
+    Ok(())
+}
+
+fn compare_benchmarks(
+    baseline: &Selector,
+    current: Option<Selector>,
+    threshold: f64,
+) -> miette::Result<()> {
+    let current = current.unwrap_or_else(|| Selector {
+        machine: current_machine(),
+        commit: None,
+    });
+    let records = load_records(&benchmarks_path())?;
+    let comparisons = compare(&select(&records, baseline)?, &select(&records, &current)?);
+
+    let mut regressed = 0;
+    for comparison in &comparisons {
+        let flag = if comparison.regressed(threshold) {
+            regressed += 1;
+            " REGRESSION"
+        } else {
+            ""
+        };
+        println!(
+            "{}: {:?} -> {:?} ({:+.1}%){}",
+            comparison.name,
+            comparison.baseline,
+            comparison.current,
+            comparison.change(),
+            flag
+        );
+    }
+
+    println!(
+        "Compared {} parts of {} with {}: {} regressed",
+        comparisons.len(),
+        current,
+        baseline,
+        regressed
+    );
+    if regressed > 0 {
+        return Err(AocError::BenchRegressed {
+            regressed,
+            compared: comparisons.len(),
+            threshold,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "dhat-heap")]
+fn heap(day: Option<u8>) -> miette::Result<()> {
+    let (mut usages, mut skipped) = (Vec::new(), Vec::new());
+    for part in registry::parts() {
+        if day.is_some_and(|day| day != part.day) {
+            continue;
+        }
+
+        match aoc::heap::profile(&part).with_context(|| part.name())? {
+            Some(usage) => usages.push((part.name(), usage)),
+            None => skipped.push(part.name()),
+        }
+    }
+
+    print!("{}", aoc::heap::format_table(&usages));
+    if !skipped.is_empty() {
+        println!("\nSkipped unsolved parts: {}", skipped.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn heap(_day: Option<u8>) -> miette::Result<()> {
+    Err(AocError::HeapProfilingDisabled.into())
+}
+
+fn main() -> miette::Result<()> {
+    aoc_utils::init_tracing();
+
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            implementation,
+            params,
+            record,
+        } => run(day, part, input, implementation.as_deref(), &params, record),
+        Command::List => {
+            for solution in registry::parts() {
+                println!("{}", solution.name());
+                for param in solution.params {
+                    println!(
+                        "    {}: {} (default: {})",
+                        param.name, param.description, param.default
+                    );
+                }
+            }
+            Ok(())
+        }
+        Command::Verify { day } => verify_all(day),
+        Command::Bench {
+            day,
+            samples,
+            machine,
+            commit,
+        } => bench(day, samples, machine, commit),
+        Command::Compare {
+            baseline,
+            current,
+            threshold,
+        } => compare_benchmarks(&baseline, current, threshold),
+        Command::Heap { day } => heap(day),
+    }
+}
@@ -0,0 +1,227 @@
+use std::time::{Duration, Instant};
+
+use aoc_utils::{Param, Params, Solution, DEFAULT_IMPLEMENTATION};
+
+// Solves a part for the given puzzle input, timing how long parsing and solving took
+pub type Runner = fn(&str, &Params) -> miette::Result<(String, Phases)>;
+
+// Time spent in each phase of a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Phases {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Phases {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+// A registered solution for one part of a day
+pub struct Part {
+    pub day: u8,
+    pub part: u8,
+    // Which of the implementations of the part this is, `default` unless there are several
+    pub implementation: &'static str,
+    // Directory of the day crate, where its inputs are looked up at runtime
+    pub dir: &'static str,
+    // Copy of the input built into the binary with the `embed-inputs` feature
+    pub input: Option<&'static str>,
+    pub params: &'static [Param],
+    pub run: Runner,
+}
+
+impl Part {
+    pub fn name(&self) -> String {
+        Self::format_name(self.day, self.part, self.implementation)
+    }
+
+    pub fn format_name(day: u8, part: u8, implementation: &str) -> String {
+        if implementation == DEFAULT_IMPLEMENTATION {
+            format!("day-{:02} part{}", day, part)
+        } else {
+            format!("day-{:02} part{} ({})", day, part, implementation)
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.implementation == DEFAULT_IMPLEMENTATION
+    }
+}
+
+fn run<S: Solution>(input: &str, params: &Params) -> miette::Result<(String, Phases)> {
+    let params = S::params(params)?;
+
+    let start = Instant::now();
+    let input =
+        tracing::info_span!("parse", day = S::DAY, part = S::PART).in_scope(|| S::parse(input))?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = tracing::info_span!("solve", day = S::DAY, part = S::PART)
+        .in_scope(|| S::solve(input, &params))?;
+    let solve = start.elapsed();
+
+    Ok((answer, Phases { parse, solve }))
+}
+
+#[cfg(feature = "embed-inputs")]
+macro_rules! embedded {
+    ($path:expr) => {
+        Some(include_str!($path))
+    };
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+macro_rules! embedded {
+    ($path:expr) => {
+        None
+    };
+}
+
+macro_rules! part {
+    ($solution:ty, $dir:literal, $part:literal) => {
+        Part {
+            day: <$solution as Solution>::DAY,
+            part: <$solution as Solution>::PART,
+            implementation: <$solution as Solution>::NAME,
+            dir: concat!(env!("CARGO_MANIFEST_DIR"), "/../", $dir),
+            input: embedded!(concat!("../../", $dir, "/input", $part, ".txt")),
+            params: <$solution as Solution>::PARAMS,
+            run: run::<$solution>,
+        }
+    };
+}
+
+// Both parts of a day crate
+macro_rules! day {
+    ($krate:ident, $dir:literal) => {
+        [
+            part!($krate::part1::Part1, $dir, 1),
+            part!($krate::part2::Part2, $dir, 2),
+        ]
+    };
+}
+
+// All solutions, ordered by day and part
+pub fn parts() -> Vec<Part> {
+    let mut parts = [
+        day!(day_01, "day-01"),
+        day!(day_02, "day-02"),
+        day!(day_03, "day-03"),
+        day!(day_04, "day-04"),
+        day!(day_05, "day-05"),
+        day!(day_06, "day-06"),
+        day!(day_07, "day-07"),
+        day!(day_08, "day-08"),
+        day!(day_09, "day-09"),
+        day!(day_10, "day-10"),
+        day!(day_11, "day-11"),
+        day!(day_12, "day-12"),
+        day!(day_13, "day-13"),
+        day!(day_14, "day-14"),
+        day!(day_15, "day-15"),
+        day!(day_16, "day-16"),
+        day!(day_17, "day-17"),
+        day!(day_18, "day-18"),
+        day!(day_19, "day-19"),
+        day!(day_20, "day-20"),
+        day!(day_21, "day-21"),
+        day!(day_22, "day-22"),
+        day!(day_23, "day-23"),
+        day!(day_25, "day-25"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    #[cfg(feature = "day-24")]
+    parts.extend(day!(day_24, "day-24"));
+
+    // Other implementations of the parts above, listed after the default ones
+    parts.push(part!(
+        day_01::part2_aho_corasick::Part2AhoCorasick,
+        "day-01",
+        2
+    ));
+
+    parts.sort_by_key(|p| (p.day, p.part, !p.is_default()));
+    parts
+}
+
+// The default implementation of a part
+pub fn find(day: u8, part: u8) -> Option<Part> {
+    find_implementation(day, part, DEFAULT_IMPLEMENTATION)
+}
+
+pub fn find_implementation(day: u8, part: u8, implementation: &str) -> Option<Part> {
+    parts()
+        .into_iter()
+        .find(|p| p.day == day && p.part == part && p.implementation == implementation)
+}
+
+// All implementations of a part, the default one first
+pub fn implementations(day: u8, part: u8) -> Vec<Part> {
+    parts()
+        .into_iter()
+        .filter(|p| p.day == day && p.part == part)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let parts = parts();
+        for (i, part) in parts.iter().enumerate() {
+            assert!(part.part == 1 || part.part == 2, "{}", part.name());
+            if i > 0 {
+                // Every part has one default implementation, followed by the others
+                let prev = &parts[i - 1];
+                if part.is_default() {
+                    assert!((prev.day, prev.part) < (part.day, part.part));
+                } else {
+                    assert_eq!((prev.day, prev.part), (part.day, part.part));
+                    assert_ne!(prev.implementation, part.implementation);
+                }
+            }
+        }
+        assert_eq!("day-07 part2", find(7, 2).unwrap().name());
+
+        let names = implementations(1, 2)
+            .iter()
+            .map(Part::name)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["day-01 part2", "day-01 part2 (aho_corasick)"], names);
+        assert!(find_implementation(1, 2, "aho_corasick").is_some());
+        assert!(find_implementation(1, 1, "aho_corasick").is_none());
+        assert_eq!("max_steps", find(21, 2).unwrap().params[0].name);
+        assert!(find(26, 1).is_none());
+    }
+
+    #[test]
+    fn test_params() {
+        let part = find(21, 1).unwrap();
+        let input = "...........
+                     .....###.#.
+                     .###.##..#.
+                     ..#.#...#..
+                     ....#.#....
+                     .##..S####.
+                     .##..#...#.
+                     .......##..
+                     .##.#.####.
+                     .##..##.##.
+                     ...........";
+        let params = Params::new().with("max_steps", 6);
+        let (answer, phases) = (part.run)(input, &params).unwrap();
+        assert_eq!("16", answer);
+        assert_eq!(phases.parse + phases.solve, phases.total());
+
+        let params = Params::new().with("steps", 6);
+        assert!((part.run)(input, &params).is_err());
+    }
+}
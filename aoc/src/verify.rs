@@ -0,0 +1,160 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use aoc_utils::{Answers, Input, InputLoader, Params};
+use miette::Context;
+
+use crate::{
+    answers::{answers_path, load_answers},
+    custom_error::AocError,
+    registry::{Part, Phases},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    // The part produced the accepted answer
+    Pass,
+    // The part used to be solved but now produces a different answer
+    Regression { expected: String },
+    // The part returned an error or panicked
+    Fail(String),
+    // There is no accepted answer to check against
+    Unsolved,
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub name: String,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+// The puzzle input of a part along with its accepted answer, or nothing when the part
+// isn't solved yet
+pub fn solved_input(part: &Part) -> miette::Result<Option<(Input, String)>> {
+    let input = InputLoader::new(part.day, part.part)
+        .dir(part.dir)
+        .embedded(part.input)
+        .load()?;
+    let answers = match answers_path(&input.source, part.dir) {
+        Some(path) => load_answers(&path)?,
+        None => Answers::default(),
+    };
+    let expected = answers.correct(part.part).map(str::to_string);
+    Ok(expected.map(|expected| (input, expected)))
+}
+
+// Runs a part with its default parameters and checks the answer against the accepted one
+pub fn verify(part: &Part) -> miette::Result<Outcome> {
+    let mut outcome = Outcome {
+        name: part.name(),
+        status: Status::Unsolved,
+        answer: None,
+        elapsed: Duration::ZERO,
+    };
+
+    let Some((input, expected)) = solved_input(part)? else {
+        return Ok(outcome);
+    };
+
+    let start = Instant::now();
+    let result = catch_unwind(AssertUnwindSafe(|| (part.run)(&input.text, &Params::new())));
+    outcome.elapsed = start.elapsed();
+
+    outcome.status = match result {
+        Ok(Ok((answer, _))) => {
+            let status = if answer == expected {
+                Status::Pass
+            } else {
+                Status::Regression { expected }
+            };
+            outcome.answer = Some(answer);
+            status
+        }
+        Ok(Err(err)) => Status::Fail(err.to_string()),
+        Err(_) => Status::Fail("panicked".to_string()),
+    };
+    Ok(outcome)
+}
+
+// The answer an implementation of a part gave and how long parsing and solving took
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub name: String,
+    pub answer: String,
+    pub phases: Phases,
+}
+
+// Runs implementations of the same part on one input and makes sure they all agree
+// with the first one
+pub fn cross_check(parts: &[Part], input: &str, params: &Params) -> miette::Result<Vec<Run>> {
+    let mut runs = Vec::new();
+    for part in parts {
+        let (answer, phases) = (part.run)(input, params).with_context(|| part.name())?;
+        runs.push(Run {
+            name: part.name(),
+            answer,
+            phases,
+        });
+    }
+
+    if let Some(run) = runs.iter().find(|run| run.answer != runs[0].answer) {
+        return Err(AocError::Disagreement {
+            name: run.name.clone(),
+            answer: run.answer.clone(),
+            default: runs[0].name.clone(),
+            expected: runs[0].answer.clone(),
+        }
+        .into());
+    }
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_utils::ANSWERS_FILE;
+
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_verify() {
+        let part = registry::find(9, 2).unwrap();
+        let outcome = verify(&part).unwrap();
+        assert_eq!(Status::Pass, outcome.status);
+        assert_eq!(Some("903".to_string()), outcome.answer);
+
+        // Every day has its answers file and all of them parse
+        for part in registry::parts() {
+            let path = Path::new(part.dir).join(ANSWERS_FILE);
+            assert!(path.is_file(), "{}", path.display());
+            load_answers(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_cross_check() {
+        // Every part solved in more than one way gives the same answer for the real input
+        for part in registry::parts().iter().filter(|p| !p.is_default()) {
+            let parts = registry::implementations(part.day, part.part);
+            let input = InputLoader::new(part.day, part.part)
+                .dir(part.dir)
+                .load()
+                .unwrap();
+            let runs = cross_check(&parts, &input.text, &Params::new()).unwrap();
+            assert_eq!(parts.len(), runs.len());
+        }
+
+        let parts = [registry::find(1, 1).unwrap(), registry::find(1, 2).unwrap()];
+        let err = cross_check(&parts, "two1nine", &Params::new()).unwrap_err();
+        assert_eq!(
+            "day-01 part2 answered 29, but day-01 part1 answered 11",
+            err.to_string()
+        );
+    }
+}
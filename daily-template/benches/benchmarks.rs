@@ -1,22 +1,7 @@
-use {{crate_name}}::*;
-
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
-}
-
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+aoc_utils::bench_part!(part1, {{crate_name}}::part1::Part1);
+aoc_utils::bench_part!(part2, {{crate_name}}::part2::Part2);
\ No newline at end of file
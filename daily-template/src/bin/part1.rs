@@ -1,3 +1,4 @@
+use aoc_utils::InputLoader;
 use {{crate_name}}::part1::process;
 use miette::Context;
 
@@ -11,10 +12,13 @@ fn main() -> miette::Result<()> {
     let _profiler = dhat::Profiler::new_heap();
 
     #[cfg(not(feature = "dhat-heap"))]
-    tracing_subscriber::fmt::init();
+    aoc_utils::init_tracing();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    let input = InputLoader::new({{project-name | remove: "day-" | plus: 0}}, 1)
+        .path(std::env::args().nth(1))
+        .dir(env!("CARGO_MANIFEST_DIR"))
+        .load()?;
+    let result = process(&input.text).context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
\ No newline at end of file
@@ -1,8 +1,28 @@
+use aoc_utils::{Params, Solution};
+
 use crate::custom_error::AocError;
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = {{project-name | remove: "day-" | plus: 0}};
+    const PART: u8 = 1;
+
+    type Input = String;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<String, AocError> {
+        Ok(input.to_string())
+    }
+
+    fn solve(_input: String, _params: &Params) -> Result<String, AocError> {
+        todo!("day 01 - part 1");
+    }
+}
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
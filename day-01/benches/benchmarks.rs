@@ -1,22 +1,12 @@
-use day_01::*;
-
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
-}
+aoc_utils::bench_part!(part1, day_01::part1::Part1);
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+// Implementations of part 2 are grouped together so they are listed side by side
+mod part2 {
+    aoc_utils::bench_part!(default, day_01::part2::Part2);
+    aoc_utils::bench_part!(aho_corasick, day_01::part2_aho_corasick::Part2AhoCorasick);
+}
@@ -1,3 +1,4 @@
+use aoc_utils::InputLoader;
 use day_01::part2_aho_corasick::process;
 use miette::Context;
 
@@ -11,10 +12,13 @@ fn main() -> miette::Result<()> {
     let _profiler = dhat::Profiler::new_heap();
 
     #[cfg(not(feature = "dhat-heap"))]
-    tracing_subscriber::fmt::init();
+    aoc_utils::init_tracing();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    let input = InputLoader::new(1, 2)
+        .path(std::env::args().nth(1))
+        .dir(env!("CARGO_MANIFEST_DIR"))
+        .load()?;
+    let result = process(&input.text).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -1,10 +1,39 @@
+use aoc_utils::{Params, ParseError, Solution};
+
 use crate::custom_error::AocError;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 1;
+    const PART: u8 = 1;
+
+    type Input = Vec<String>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<String>, AocError> {
+        input
+            .lines()
+            .map(|line| {
+                if line.chars().any(|c| c.is_ascii_digit()) {
+                    Ok(line.to_string())
+                } else {
+                    let err = ParseError::new("no digits in line", line, "expected a digit here");
+                    Err(err.with_source(input).into())
+                }
+            })
+            .collect()
+    }
+
+    fn solve(lines: Vec<String>, _params: &Params) -> Result<String, AocError> {
+        Ok(calibration_sum(&lines).to_string())
+    }
+}
+
+fn calibration_sum(lines: &[String]) -> u32 {
     let mut sum = 0;
 
-    input.lines().for_each(|line| {
+    lines.iter().for_each(|line| {
         let mut digit1 = None;
         let mut digit2 = None;
 
@@ -27,7 +56,12 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
         sum += number;
     });
 
-    Ok(sum.to_string())
+    sum
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
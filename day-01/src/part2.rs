@@ -1,3 +1,5 @@
+use aoc_utils::{Params, ParseError, Solution};
+
 use crate::custom_error::AocError;
 
 struct EncodedLine {
@@ -35,11 +37,42 @@ impl Iterator for EncodedLine {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 1;
+    const PART: u8 = 2;
+
+    type Input = Vec<String>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<String>, AocError> {
+        input
+            .lines()
+            .map(|line| {
+                let mut encoded_line = EncodedLine {
+                    line: line.to_string(),
+                    index: 0,
+                };
+                if encoded_line.next().is_some() {
+                    Ok(line.to_string())
+                } else {
+                    let err = ParseError::new("no digits in line", line, "expected a digit here");
+                    Err(err.with_source(input).into())
+                }
+            })
+            .collect()
+    }
+
+    fn solve(lines: Vec<String>, _params: &Params) -> Result<String, AocError> {
+        Ok(calibration_sum(&lines).to_string())
+    }
+}
+
+fn calibration_sum(lines: &[String]) -> u32 {
     let mut sum = 0;
 
-    input.lines().for_each(|line| {
+    lines.iter().for_each(|line| {
         let mut encoded_line = EncodedLine {
             line: line.to_string(),
             index: 0,
@@ -57,10 +90,15 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
             + last_digit.unwrap().to_digit(10).unwrap();
 
         sum += number;
-        println!("{}: {} => {}", line, number, sum);
+        tracing::trace!(line, number, sum, "decoded line");
     });
 
-    Ok(sum.to_string())
+    sum
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
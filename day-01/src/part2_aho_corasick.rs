@@ -1,5 +1,7 @@
-use crate::custom_error::AocError;
 use aho_corasick::{AhoCorasick, Match};
+use aoc_utils::{Params, ParseError, Solution};
+
+use crate::custom_error::AocError;
 
 const NUMBERS: [&str; 18] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "1", "2", "3", "4",
@@ -12,26 +14,70 @@ pub fn match_to_digit(matched: Option<Match>) -> Option<u32> {
         let digit = if pattern_idx < 9 {
             pattern_idx + 1
         } else {
-            pattern_idx - 9
+            pattern_idx - 8
         };
-        return Some(digit);
+        Some(digit)
     } else {
         None
     }
 }
 
-pub fn process_line(line: &str, ac: &AhoCorasick) -> u32 {
+pub fn process_line(line: &str, ac: &AhoCorasick) -> Result<u32, ParseError> {
     let mut iter = ac.find_overlapping_iter(line);
-    let first_digit = match_to_digit(iter.next());
-    let last_digit = match_to_digit(iter.last()).or(first_digit);
-    return first_digit.unwrap() * 10 + last_digit.unwrap();
+    let Some(first_digit) = match_to_digit(iter.next()) else {
+        return Err(ParseError::new(
+            "no digits in line",
+            line,
+            "expected a digit here",
+        ));
+    };
+    let last_digit = match_to_digit(iter.last()).unwrap_or(first_digit);
+    Ok(first_digit * 10 + last_digit)
+}
+
+// Same as `Part2`, but finds the digits and the words for them in a single pass over
+// each line with an Aho-Corasick automaton
+pub struct Part2AhoCorasick;
+
+impl Solution for Part2AhoCorasick {
+    const DAY: u8 = 1;
+    const PART: u8 = 2;
+    const NAME: &'static str = "aho_corasick";
+
+    type Input = (AhoCorasick, Vec<String>);
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<(AhoCorasick, Vec<String>), AocError> {
+        let ac = AhoCorasick::new(NUMBERS).unwrap();
+        let lines = input
+            .lines()
+            .map(|line| {
+                if ac.is_match(line) {
+                    Ok(line.to_string())
+                } else {
+                    let err = ParseError::new("no digits in line", line, "expected a digit here");
+                    Err(err.with_source(input).into())
+                }
+            })
+            .collect::<Result<_, AocError>>()?;
+        Ok((ac, lines))
+    }
+
+    fn solve(
+        (ac, lines): (AhoCorasick, Vec<String>),
+        _params: &Params,
+    ) -> Result<String, AocError> {
+        let sum = lines
+            .iter()
+            .map(|line| process_line(line, &ac))
+            .sum::<Result<u32, _>>()?;
+        Ok(sum.to_string())
+    }
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let ac = AhoCorasick::new(NUMBERS).unwrap();
-    let sum: u32 = input.lines().map(|line| process_line(line, &ac)).sum();
-    Ok(sum.to_string())
+    Part2AhoCorasick::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -48,6 +94,21 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_match_to_digit_words_and_numerals() {
+        // The words come first in the patterns, so the numerals are offset by 8, not 9
+        let ac = AhoCorasick::new(NUMBERS).unwrap();
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        for (idx, word) in words.iter().enumerate() {
+            let digit = idx as u32 + 1;
+            assert_eq!(Some(digit), match_to_digit(ac.find(*word)), "{}", word);
+            assert_eq!(Some(digit), match_to_digit(ac.find(&digit.to_string())));
+        }
+    }
+
     #[test]
     fn test_iterator_words() -> miette::Result<()> {
         let words = [
@@ -83,4 +144,25 @@ mod tests {
         assert_eq!("281", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_same_as_part2() -> miette::Result<()> {
+        // Overlapping words are where the two ways of finding digits could disagree
+        let input = "two1nine
+                     eightwothree
+                     zoneight234
+                     twone
+                     sevenine
+                     5";
+        let params = Params::new();
+        let expected = crate::part2::Part2::run(input, &params)?;
+        assert_eq!(expected, Part2AhoCorasick::run(input, &params)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("1abc\nxyz\n"));
+        assert_eq!(miette::SourceSpan::from((5, 3)), err.span);
+    }
 }
@@ -1,63 +1,144 @@
 use std::collections::HashMap;
 
+use aoc_utils::{list1, number, parse_lines, token, ws, IResult, Params, Solution};
+use nom::{
+    branch::alt,
+    character::complete::space1,
+    combinator::map,
+    sequence::{delimited, pair, separated_pair},
+};
+
 use crate::custom_error::AocError;
 
-pub fn possible_draw(draw: &str, limits: &HashMap<&str, u32>) -> bool {
-    let draw_cubes = draw.split(",").collect::<Vec<&str>>();
-    for cube in draw_cubes {
-        let cube_parts = cube.trim().split(" ").collect::<Vec<&str>>();
-        let color = cube_parts[1];
-        let count = cube_parts[0].parse::<u32>().unwrap();
+// A game with all its draws, each draw being a list of cube counts by color
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    id: u32,
+    draws: Vec<Vec<(String, u32)>>,
+}
+
+impl std::fmt::Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let draws = self
+            .draws
+            .iter()
+            .map(|draw| {
+                draw.iter()
+                    .map(|(color, count)| format!("{} {}", count, color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        write!(f, "Game {}: {}", self.id, draws.join("; "))
+    }
+}
+
+fn color(input: &str) -> IResult<'_, String> {
+    map(
+        alt((token("red"), token("green"), token("blue"))),
+        str::to_string,
+    )(input)
+}
+
+fn cubes(input: &str) -> IResult<'_, (String, u32)> {
+    map(separated_pair(number, space1, color), |(count, color)| {
+        (color, count)
+    })(input)
+}
+
+// `Game 1: 3 blue, 4 red; 1 red, 2 green`
+fn game(input: &str) -> IResult<'_, Game> {
+    let draw = list1(ws(token(",")), cubes);
+    map(
+        pair(
+            delimited(token("Game "), number, ws(token(":"))),
+            list1(ws(token(";")), draw),
+        ),
+        |(id, draws)| Game { id, draws },
+    )(input)
+}
 
-        if count > *limits.get(color).unwrap() {
+pub fn possible_draw(draw: &[(String, u32)], limits: &HashMap<&str, u32>) -> bool {
+    for (color, count) in draw {
+        if *count > *limits.get(color.as_str()).unwrap() {
             return false;
         }
     }
-    return true;
+    true
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut limits: HashMap<&str, u32> = HashMap::new();
-    limits.insert("red", 12);
-    limits.insert("green", 13);
-    limits.insert("blue", 14);
-
-    let mut possible_games_sum = 0;
-    for line in input.lines() {
-        let game_parts = line.split(":").collect::<Vec<&str>>();
-        let game_number = game_parts[0].trim().split(" ").collect::<Vec<&str>>()[1];
-        let game = game_parts[1].trim();
-        let draws = game.split(";").collect::<Vec<&str>>();
-
-        let mut game_possible = true;
-        for draw in draws {
-            if !possible_draw(draw, &limits) {
-                game_possible = false;
-                break;
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 2;
+    const PART: u8 = 1;
+
+    type Input = Vec<Game>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<Game>, AocError> {
+        Ok(parse_lines(input, game)?)
+    }
+
+    fn solve(games: Vec<Game>, _params: &Params) -> Result<String, AocError> {
+        let mut limits: HashMap<&str, u32> = HashMap::new();
+        limits.insert("red", 12);
+        limits.insert("green", 13);
+        limits.insert("blue", 14);
+
+        let mut possible_games_sum = 0;
+        for game in games {
+            let game_possible = game.draws.iter().all(|draw| possible_draw(draw, &limits));
+            if game_possible {
+                possible_games_sum += game.id;
             }
         }
 
-        if game_possible {
-            possible_games_sum += game_number.parse::<u32>().unwrap();
-        }
+        Ok(possible_games_sum.to_string())
     }
+}
 
-    return Ok(possible_games_sum.to_string());
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
         Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
         Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
         Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
         Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!("8", process(input)?);
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("8", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let games = Part1::parse(EXAMPLE)?;
+        let printed = games.iter().map(Game::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue, x green\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("expected a number, found `x`", err.message);
+        assert_eq!(miette::SourceSpan::from((38, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("Game 1: 3 purple"));
+        assert_eq!(
+            "expected `red` or `green` or `blue`, found `purple`",
+            err.message
+        );
+        assert_eq!(miette::SourceSpan::from((10, 6)), err.span);
+    }
 }
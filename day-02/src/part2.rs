@@ -1,54 +1,130 @@
-use crate::custom_error::AocError;
+use aoc_utils::{list1, number, parse_lines, token, ws, IResult, Params, Solution};
+use nom::{
+    branch::alt,
+    character::complete::space1,
+    combinator::map,
+    sequence::{delimited, pair, separated_pair},
+};
 use std::collections::HashMap;
 
-pub fn update_limits<'a>(draw: &'a str, limits: &mut HashMap<&'a str, u32>) {
-    let draw_cubes = draw.split(",").collect::<Vec<&str>>();
-    for cube in draw_cubes {
-        let cube_parts = cube.trim().split(" ").collect::<Vec<&str>>();
-        let color = cube_parts[1];
-        let count = cube_parts[0].parse::<u32>().unwrap();
-        if count > *limits.get(color).unwrap() {
-            limits.insert(color, count);
+use crate::custom_error::AocError;
+
+// A game with all its draws, each draw being a list of cube counts by color
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    id: u32,
+    draws: Vec<Vec<(String, u32)>>,
+}
+
+impl std::fmt::Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let draws = self
+            .draws
+            .iter()
+            .map(|draw| {
+                draw.iter()
+                    .map(|(color, count)| format!("{} {}", count, color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        write!(f, "Game {}: {}", self.id, draws.join("; "))
+    }
+}
+
+fn color(input: &str) -> IResult<'_, String> {
+    map(
+        alt((token("red"), token("green"), token("blue"))),
+        str::to_string,
+    )(input)
+}
+
+fn cubes(input: &str) -> IResult<'_, (String, u32)> {
+    map(separated_pair(number, space1, color), |(count, color)| {
+        (color, count)
+    })(input)
+}
+
+// `Game 1: 3 blue, 4 red; 1 red, 2 green`
+fn game(input: &str) -> IResult<'_, Game> {
+    let draw = list1(ws(token(",")), cubes);
+    map(
+        pair(
+            delimited(token("Game "), number, ws(token(":"))),
+            list1(ws(token(";")), draw),
+        ),
+        |(id, draws)| Game { id, draws },
+    )(input)
+}
+
+pub fn update_limits<'a>(draw: &'a [(String, u32)], limits: &mut HashMap<&'a str, u32>) {
+    for (color, count) in draw {
+        if *count > *limits.get(color.as_str()).unwrap() {
+            limits.insert(color, *count);
         }
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut games_sum = 0;
+pub struct Part2;
 
-    for line in input.lines() {
-        let game = line.split(":").last().unwrap().trim();
-        let draws = game.split(";").collect::<Vec<&str>>();
+impl Solution for Part2 {
+    const DAY: u8 = 2;
+    const PART: u8 = 2;
 
-        let mut limits = HashMap::new();
-        limits.insert("red", 0);
-        limits.insert("green", 0);
-        limits.insert("blue", 0);
+    type Input = Vec<Game>;
+    type Error = AocError;
 
-        for draw in draws {
-            update_limits(draw, &mut limits);
+    fn parse(input: &str) -> Result<Vec<Game>, AocError> {
+        Ok(parse_lines(input, game)?)
+    }
+
+    fn solve(games: Vec<Game>, _params: &Params) -> Result<String, AocError> {
+        let mut games_sum = 0;
+
+        for game in games.iter() {
+            let mut limits = HashMap::new();
+            limits.insert("red", 0);
+            limits.insert("green", 0);
+            limits.insert("blue", 0);
+
+            for draw in &game.draws {
+                update_limits(draw, &mut limits);
+            }
+
+            let set_power: u32 = limits.values().product();
+            games_sum += set_power;
         }
 
-        let set_power: u32 = limits.values().product();
-        games_sum += set_power;
+        Ok(games_sum.to_string())
     }
+}
 
-    return Ok(games_sum.to_string());
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
         Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
         Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
         Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
         Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!("2286", process(input)?);
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("2286", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let games = Part2::parse(EXAMPLE)?;
+        let printed = games.iter().map(Game::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
         Ok(())
     }
 }
@@ -1,3 +1,11 @@
+use aoc_utils::{parse_all, unsigned, IResult, Params, ParseError, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::is_not,
+    combinator::{map, value},
+    multi::many0,
+};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug)]
@@ -9,16 +17,6 @@ pub struct PartNumber {
 }
 
 impl PartNumber {
-    fn from_str(line: &str, line_idx: usize, start: i32, end: i32) -> Self {
-        let number = parse_number(line, start, end);
-        Self {
-            number,
-            line_idx,
-            start: start as usize,
-            end: end as usize,
-        }
-    }
-
     // Checks if the part number is real (is has any non-'.' character anywhere in an adjacent cell)
     fn is_real(&self, map: &[String]) -> bool {
         let mut lines_to_check = vec![self.line_idx];
@@ -54,81 +52,156 @@ impl PartNumber {
     }
 }
 
-pub fn parse_number(line: &str, start: i32, end: i32) -> u32 {
-    line[start as usize..=end as usize].parse().unwrap()
+// Every number in a row of the schematic with the first and the last column it takes
+fn row_numbers<'a>(line: &'a str) -> IResult<'a, Vec<(usize, usize, u32)>> {
+    let number_at = |input: &'a str| -> IResult<'a, Option<(usize, usize, u32)>> {
+        let start = line.len() - input.len();
+        let (rest, number) = unsigned(input)?;
+        let end = line.len() - rest.len() - 1;
+        Ok((rest, Some((start, end, number))))
+    };
+    let not_a_number = value(None, is_not("0123456789"));
+    map(many0(alt((number_at, not_a_number))), |cells| {
+        cells.into_iter().flatten().collect()
+    })(line)
 }
 
-pub fn parse_numbers(line: &str, line_idx: usize) -> miette::Result<Vec<PartNumber>, AocError> {
-    let mut numbers = Vec::new();
-    let mut number_start = -1;
-    let mut number_end = -1;
-    for (x, c) in line.chars().enumerate() {
-        if c.is_digit(10) {
-            if number_start == -1 {
-                number_start = x as i32;
-            }
-            number_end = x as i32;
-        } else {
-            if number_start != -1 {
-                numbers.push(PartNumber::from_str(
+pub fn parse_numbers(line: &str, line_idx: usize) -> Result<Vec<PartNumber>, ParseError> {
+    let numbers = parse_all(line, row_numbers)?;
+    Ok(numbers
+        .into_iter()
+        .map(|(start, end, number)| PartNumber {
+            number,
+            line_idx,
+            start,
+            end,
+        })
+        .collect())
+}
+
+// The engine schematic with all the numbers found in it
+pub struct Schematic {
+    lines: Vec<String>,
+    numbers: Vec<PartNumber>,
+}
+
+impl std::fmt::Display for Schematic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines.join("\n"))
+    }
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 3;
+    const PART: u8 = 1;
+
+    type Input = Schematic;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Schematic, AocError> {
+        let lines = input.lines().map(|line| line.trim()).collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.len());
+
+        let mut numbers = Vec::new();
+        for (y, line) in lines.iter().enumerate() {
+            // All lines have to be the same width to check the neighbours of a number
+            if line.len() != width {
+                let err = ParseError::new(
+                    format!("line is {} wide, expected {}", line.len(), width),
                     line,
-                    line_idx,
-                    number_start,
-                    number_end,
-                ));
-                number_start = -1;
-                number_end = -1;
+                    "wrong width",
+                );
+                return Err(err.with_source(input).into());
+            }
+
+            if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+                let err = ParseError::new(
+                    format!("unexpected `{}` in the schematic", c),
+                    &line[idx..idx + c.len_utf8()],
+                    "not a valid cell",
+                );
+                return Err(err.with_source(input).into());
             }
+
+            // scan the line to find all numbers (including multi-digit numbers)
+            let mut line_numbers = parse_numbers(line, y).map_err(|err| err.with_source(input))?;
+            numbers.append(&mut line_numbers);
         }
-    }
 
-    if number_start != -1 {
-        numbers.push(PartNumber::from_str(
-            line,
-            line_idx,
-            number_start,
-            number_end,
-        ))
+        Ok(Schematic {
+            lines: lines.into_iter().map(|line| line.to_string()).collect(),
+            numbers,
+        })
     }
 
-    Ok(numbers)
-}
-
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let lines: Vec<String> = input.lines().map(|line| line.trim().to_string()).collect();
-    let mut sum = 0;
-
-    for (y, line) in lines.iter().enumerate() {
-        // scan the line to find all numbers (including multi-digit numbers)
-        let numbers = parse_numbers(line, y)?;
-        for number in numbers {
-            if number.is_real(&lines) {
+    fn solve(schematic: Schematic, _params: &Params) -> Result<String, AocError> {
+        let mut sum = 0;
+        for number in &schematic.numbers {
+            if number.is_real(&schematic.lines) {
                 sum += number.number;
             }
         }
+
+        Ok(sum.to_string())
     }
+}
 
-    Ok(sum.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "467..114..
+                           ...*......
+                           ..35..633.
+                           ......#...
+                           617*......
+                           .....+.58.
+                           ..592.....
+                           ......755.
+                           ...$.*....
+                           .664.598..";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-                     ...*......
-                     ..35..633.
-                     ......#...
-                     617*......
-                     .....+.58.
-                     ..592.....
-                     ......755.
-                     ...$.*....
-                     .664.598..";
-        assert_eq!("4361", process(input)?);
+        assert_eq!("4361", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let schematic = Part1::parse(EXAMPLE)?;
+        let rows = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(rows.join("\n"), schematic.to_string());
+
+        let numbers = schematic
+            .numbers
+            .iter()
+            .map(|n| n.number)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![467, 114, 35, 633, 617, 58, 592, 755, 664, 598],
+            numbers
+        );
+        assert_eq!(
+            (5, 7),
+            (schematic.numbers[1].start, schematic.numbers[1].end)
+        );
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("467..\n...*\n"));
+        assert_eq!(miette::SourceSpan::from((6, 4)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("..99999999999..\n"));
+        assert_eq!(miette::SourceSpan::from((2, 11)), err.span);
+    }
 }
@@ -1,152 +1,181 @@
 use std::collections::HashMap;
 
+use aoc_utils::{parse_all, unsigned, CharMap, IResult, Params, ParseError, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::is_not,
+    combinator::{map, value},
+    multi::many0,
+};
+
 use crate::custom_error::AocError;
-use crate::util::{CharMap, CharRow};
 
 #[derive(Debug)]
 pub struct Gear {
-    row: i32,
-    col: i32,
+    row: i64,
+    col: i64,
     part_number: u32,
 }
 
 #[derive(Debug)]
 pub struct PartNumber {
     number: u32,
-    line_idx: i32,
-    start: i32,
-    end: i32,
+    line_idx: i64,
+    start: i64,
+    end: i64,
 }
 
 impl PartNumber {
-    fn from_str(line: &CharRow, line_idx: i32, start: i32, end: i32) -> Self {
-        let number = parse_number(line, start, end);
-        Self {
-            number,
-            line_idx,
-            start,
-            end,
-        }
-    }
-
     // Finds all adjacent cells that have a '*' in them and returns their coordinates
     fn find_gears(&self, map: &CharMap) -> Vec<Gear> {
-        let lines_to_check = vec![self.line_idx - 1, self.line_idx, self.line_idx + 1];
+        map.run_neighbours(self.line_idx, self.start, self.end)
+            .filter(|(_, c)| **c == '*')
+            .map(|(point, _)| Gear {
+                row: point.y,
+                col: point.x,
+                part_number: self.number,
+            })
+            .collect()
+    }
+}
 
-        let mut gears = Vec::new();
-        for row in lines_to_check {
-            let line = &map.line(row as i32);
-            let start = self.start - 1;
-            let end = self.end + 1;
-
-            for col in start..=end {
-                let c = line.cell(col);
-                if *c == '*' {
-                    gears.push(Gear {
-                        row,
-                        col,
-                        part_number: self.number,
-                    });
-                }
-            }
-        }
+// Every number in a row of the schematic with the first and the last column it takes
+fn row_numbers<'a>(line: &'a str) -> IResult<'a, Vec<(usize, usize, u32)>> {
+    let number_at = |input: &'a str| -> IResult<'a, Option<(usize, usize, u32)>> {
+        let start = line.len() - input.len();
+        let (rest, number) = unsigned(input)?;
+        let end = line.len() - rest.len() - 1;
+        Ok((rest, Some((start, end, number))))
+    };
+    let not_a_number = value(None, is_not("0123456789"));
+    map(many0(alt((number_at, not_a_number))), |cells| {
+        cells.into_iter().flatten().collect()
+    })(line)
+}
 
-        return gears;
-    }
+pub fn parse_numbers(line: &str, line_idx: i64) -> Result<Vec<PartNumber>, ParseError> {
+    let numbers = parse_all(line, row_numbers)?;
+    Ok(numbers
+        .into_iter()
+        .map(|(start, end, number)| PartNumber {
+            number,
+            line_idx,
+            start: start as i64,
+            end: end as i64,
+        })
+        .collect())
 }
 
-pub fn parse_number(line: &CharRow, start: i32, end: i32) -> u32 {
-    let number_string = line
-        .iter()
-        .skip(start as usize)
-        .take((end - start + 1) as usize)
-        .collect::<String>();
-    number_string.parse().unwrap()
+// The engine schematic with all the numbers found in it
+pub struct Schematic {
+    map: CharMap,
+    numbers: Vec<PartNumber>,
 }
 
-pub fn parse_numbers(line: &CharRow, line_idx: i32) -> miette::Result<Vec<PartNumber>, AocError> {
-    let mut numbers = Vec::new();
-    let mut number_start = -1;
-    let mut number_end = -1;
-    for (x, c) in line.iter().enumerate() {
-        if c.is_digit(10) {
-            if number_start == -1 {
-                number_start = x as i32;
-            }
-            number_end = x as i32;
-        } else {
-            if number_start != -1 {
-                numbers.push(PartNumber::from_str(
-                    line,
-                    line_idx,
-                    number_start,
-                    number_end,
-                ));
-                number_start = -1;
-                number_end = -1;
-            }
-        }
+impl std::fmt::Display for Schematic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.map)
     }
+}
 
-    if number_start != -1 {
-        numbers.push(PartNumber::from_str(
-            line,
-            line_idx,
-            number_start,
-            number_end,
-        ))
-    }
+pub struct Part2;
 
-    Ok(numbers)
-}
+impl Solution for Part2 {
+    const DAY: u8 = 3;
+    const PART: u8 = 2;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str(input, '.');
-    let mut gears = Vec::new();
-    for (y, line) in map.lines().enumerate() {
-        for number in parse_numbers(line, y as i32)? {
-            gears.append(&mut number.find_gears(&map));
+    type Input = Schematic;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Schematic, AocError> {
+        let map = CharMap::parse(input, '.', |c| c.is_ascii_graphic())
+            .map_err(|err| err.with_source(input))?;
+
+        let mut numbers = Vec::new();
+        let lines = input
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+        for (y, line) in lines.enumerate() {
+            let mut line_numbers =
+                parse_numbers(line, y as i64).map_err(|err| err.with_source(input))?;
+            numbers.append(&mut line_numbers);
         }
+        Ok(Schematic { map, numbers })
     }
 
-    // Find all unique gears (by row and line)
-    let mut unique_gears = HashMap::new(); // (row, line) -> vec![part_number]
-    for gear in gears {
-        let part_numbers = unique_gears
-            .entry((gear.row, gear.col))
-            .or_insert(Vec::new());
-        part_numbers.push(gear.part_number);
-    }
+    fn solve(schematic: Schematic, _params: &Params) -> Result<String, AocError> {
+        let mut gears = Vec::new();
+        for number in &schematic.numbers {
+            gears.append(&mut number.find_gears(&schematic.map));
+        }
+
+        // Find all unique gears (by row and line)
+        let mut unique_gears = HashMap::new(); // (row, line) -> vec![part_number]
+        for gear in gears {
+            let part_numbers = unique_gears
+                .entry((gear.row, gear.col))
+                .or_insert(Vec::new());
+            part_numbers.push(gear.part_number);
+        }
 
-    // Find a sum of gear ratios (product of all part numbers adjacent to a gear) for gears with 2 or more parts
-    let mut sum = 0;
-    for part_numbers in unique_gears.values() {
-        if part_numbers.len() > 1 {
-            sum += part_numbers.iter().product::<u32>();
+        // Find a sum of gear ratios (product of all part numbers adjacent to a gear) for gears with 2 or more parts
+        let mut sum = 0;
+        for part_numbers in unique_gears.values() {
+            if part_numbers.len() > 1 {
+                sum += part_numbers.iter().product::<u32>();
+            }
         }
+
+        Ok(sum.to_string())
     }
+}
 
-    Ok(sum.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "467..114..
+                           ...*......
+                           ..35..633.
+                           ......#...
+                           617*......
+                           .....+.58.
+                           ..592.....
+                           ......755.
+                           ...$.*....
+                           .664.598..";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-                     ...*......
-                     ..35..633.
-                     ......#...
-                     617*......
-                     .....+.58.
-                     ..592.....
-                     ......755.
-                     ...$.*....
-                     .664.598..";
-        assert_eq!("467835", process(input)?);
+        assert_eq!("467835", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let schematic = Part2::parse(EXAMPLE)?;
+        let rows = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(rows.join("\n"), schematic.to_string());
+
+        let numbers = schematic
+            .numbers
+            .iter()
+            .map(|n| n.number)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![467, 114, 35, 633, 617, 58, 592, 755, 664, 598],
+            numbers
+        );
+        assert_eq!(
+            (5, 7),
+            (schematic.numbers[1].start, schematic.numbers[1].end)
+        );
         Ok(())
     }
 }
@@ -1,69 +1,126 @@
-use std::collections::HashSet;
+use aoc_utils::{number, numbers, parse_lines, token, ws, IResult, Params, Solution};
+use nom::{
+    character::complete::space1,
+    combinator::map,
+    sequence::{pair, preceded, tuple},
+};
 
 use crate::custom_error::AocError;
 
 #[derive(Debug)]
-struct Card {
-    winning_nums: HashSet<u32>,
-    owned_nums: HashSet<u32>,
+pub struct Card {
+    id: u32,
+    winning_nums: Vec<u32>,
+    owned_nums: Vec<u32>,
 }
 
-impl Card {
-    fn from_str(line: &str) -> Self {
-        let tickets = line.split(":").last().unwrap().trim();
-        let num_parts = tickets.split("|").collect::<Vec<&str>>();
-        let winning_nums = num_parts[0]
-            .trim()
-            .split_whitespace()
-            .map(|n| n.trim().parse().unwrap())
-            .collect::<HashSet<u32>>();
-
-        let owned_nums = num_parts[1]
-            .trim()
-            .split_whitespace()
-            .map(|n| n.trim().parse().unwrap())
-            .collect::<HashSet<u32>>();
-
-        Self {
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |nums: &[u32]| {
+            nums.iter()
+                .map(|num| format!("{:>2}", num))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        write!(
+            f,
+            "Card {}: {} | {}",
+            self.id,
+            list(&self.winning_nums),
+            list(&self.owned_nums)
+        )
+    }
+}
+
+// `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
+fn card(input: &str) -> IResult<'_, Card> {
+    map(
+        tuple((
+            preceded(pair(token("Card"), space1), number),
+            ws(token(":")),
+            numbers,
+            ws(token("|")),
+            numbers,
+        )),
+        |(id, _, winning_nums, _, owned_nums)| Card {
+            id,
             winning_nums,
             owned_nums,
-        }
+        },
+    )(input)
+}
+
+impl Card {
+    fn matching_nums(&self) -> usize {
+        self.owned_nums
+            .iter()
+            .filter(|num| self.winning_nums.contains(num))
+            .count()
     }
 
     fn score(&self) -> u32 {
-        let matching_nums = self.winning_nums.intersection(&self.owned_nums).count() as u32;
+        let matching_nums = self.matching_nums() as u32;
         if matching_nums == 0 {
             return 0;
         }
-        return (2 as u32).pow(matching_nums - 1);
+        2_u32.pow(matching_nums - 1)
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut sum = 0;
-    for line in input.lines() {
-        let line = line.trim();
-        let card = Card::from_str(line);
-        sum += card.score();
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 4;
+    const PART: u8 = 1;
+
+    type Input = Vec<Card>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<Card>, AocError> {
+        Ok(parse_lines(input, card)?)
+    }
+
+    fn solve(cards: Vec<Card>, _params: &Params) -> Result<String, AocError> {
+        let sum = cards.iter().map(|card| card.score()).sum::<u32>();
+        Ok(sum.to_string())
     }
+}
 
-    Ok(sum.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
         Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
         Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
         Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
         Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
         Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!("13", process(input)?);
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("13", process(EXAMPLE)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let cards = Part1::parse(EXAMPLE)?;
+        let printed = cards.iter().map(Card::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 32 20 16 61\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("expected `|`, found the end of the line", err.message);
+        assert_eq!(miette::SourceSpan::from((44, 0)), err.span);
+    }
 }
@@ -1,4 +1,14 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
+
+use aoc_utils::{
+    blocks, number, numbers, parse_all, token, IResult, NomError, Params, ParseError, Solution,
+};
+use nom::{
+    character::complete::{alpha1, line_ending, space0, space1},
+    combinator::{cut, map},
+    multi::many1,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+};
 
 use crate::custom_error::AocError;
 
@@ -16,7 +26,7 @@ impl MapRange {
             let offset = source - self.source_start;
             return Some(self.destination_start + offset);
         }
-        return None;
+        None
     }
 }
 
@@ -27,95 +37,191 @@ pub struct SomethingToSomethingMap {
     ranges_map: Vec<MapRange>,
 }
 
-impl SomethingToSomethingMap {
-    fn from_lines(lines: &mut VecDeque<&str>) -> Self {
-        let header = lines.pop_front().unwrap();
-        let src_to_dest = header.split_whitespace().nth(0).unwrap();
-        let parts = src_to_dest.split("-to-");
-        let source = parts.clone().nth(0).unwrap();
-        let destination = parts.clone().nth(1).unwrap();
-
-        let mut ranges_map = Vec::new();
-        loop {
-            match lines.pop_front() {
-                Some("") => break,
-                Some(line) => {
-                    let mut parts = line.split_whitespace();
-                    let destination_start = parts.next().unwrap().parse::<u64>().unwrap();
-                    let source_start = parts.next().unwrap().parse::<u64>().unwrap();
-                    let length = parts.next().unwrap().parse::<u64>().unwrap();
-                    ranges_map.push(MapRange {
-                        source_start,
-                        destination_start,
-                        length,
-                    });
-                }
-                _ => break,
-            }
-        }
+impl std::fmt::Display for MapRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.destination_start, self.source_start, self.length
+        )
+    }
+}
+
+// `50 98 2`: the destination start, the source start and the length of the range
+fn map_range(input: &str) -> IResult<'_, MapRange> {
+    let (rest, (destination_start, source_start, length)) =
+        tuple((number, preceded(space1, number), preceded(space1, number)))(input)?;
+    if u64::checked_add(source_start, length).is_none() {
+        let line = &input[..input.len() - rest.len()];
+        let err = NomError::at(line, "a range that ends before the largest id");
+        return Err(nom::Err::Failure(err));
+    }
+    Ok((
+        rest,
+        MapRange {
+            source_start,
+            destination_start,
+            length,
+        },
+    ))
+}
 
-        return Self {
+// `seed-to-soil map:` followed by the ranges, one per line
+fn resource_map(input: &str) -> IResult<'_, SomethingToSomethingMap> {
+    let header = terminated(
+        separated_pair(alpha1, token("-to-"), alpha1),
+        token(" map:"),
+    );
+    let ranges = many1(preceded(pair(line_ending, space0), cut(map_range)));
+    map(
+        pair(header, ranges),
+        |((source, destination), ranges_map)| SomethingToSomethingMap {
             source: source.to_string(),
             destination: destination.to_string(),
             ranges_map,
-        };
+        },
+    )(input)
+}
+
+impl std::fmt::Display for SomethingToSomethingMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-to-{} map:", self.source, self.destination)?;
+        for range in &self.ranges_map {
+            write!(f, "\n{}", range)?;
+        }
+        Ok(())
     }
+}
 
+impl SomethingToSomethingMap {
     fn map(&self, source: u64) -> u64 {
         for range in &self.ranges_map {
             if let Some(result) = range.map(source) {
                 return result;
             }
         }
-        return source;
+        source
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut lines = input.lines().collect::<VecDeque<_>>();
-    let seeds = lines
-        .pop_front()
-        .unwrap()
-        .split(": ")
-        .nth(1)
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
-    lines.pop_front();
+// The seeds to plant and the maps from each resource type to the next one
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: HashMap<String, SomethingToSomethingMap>,
+}
+
+impl std::fmt::Display for Almanac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seeds = self.seeds.iter().map(u64::to_string).collect::<Vec<_>>();
+        write!(f, "seeds: {}", seeds.join(" "))?;
+
+        // Maps are printed in the order they are used, which is how the almanac lists them
+        let mut resource = "seed";
+        while let Some(map) = self.maps.get(resource) {
+            write!(f, "\n\n{}", map)?;
+            resource = &map.destination;
+        }
+        Ok(())
+    }
+}
+
+// `seeds: 79 14 55 13`
+fn seeds(input: &str) -> IResult<'_, Vec<u64>> {
+    preceded(pair(token("seeds:"), space1), numbers)(input)
+}
+
+fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+    let blocks = blocks(input);
+    let Some((seeds_block, map_blocks)) = blocks.split_first() else {
+        return Err(ParseError::new(
+            "empty almanac",
+            input,
+            "expected a `seeds:` line",
+        ));
+    };
+    let seeds_line = seeds_block.trim();
+    let seeds = parse_all(seeds_line, seeds)?;
 
     let mut maps = HashMap::new();
-    while !lines.is_empty() {
-        let map = SomethingToSomethingMap::from_lines(&mut lines);
+    let mut headers = HashMap::new();
+    for block in map_blocks {
+        let block = block.trim_start();
+        let map = parse_all(block, resource_map)?;
+        headers.insert(map.source.clone(), block.lines().next().unwrap_or_default());
         maps.insert(map.source.clone(), map);
     }
 
-    let lowest_location = seeds
-        .iter()
-        .map(|seed| {
-            let mut resource = "seed".to_string();
-            let mut resource_id = seed.clone();
-            while resource != "location" {
-                let map = maps.get(&resource.to_string()).unwrap();
-                resource = map.destination.clone();
-                resource_id = map.map(resource_id);
-            }
-            resource_id
-        })
-        .min()
-        .unwrap();
+    // The maps have to lead from seeds to locations, or following them would never finish
+    let mut resource = "seed";
+    let mut at = seeds_line;
+    for _ in 0..=maps.len() {
+        if resource == "location" {
+            return Ok(Almanac { seeds, maps });
+        }
+        let Some(map) = maps.get(resource) else {
+            let message = format!("no map from `{}`", resource);
+            return Err(ParseError::new(
+                message,
+                at,
+                "nothing maps this any further",
+            ));
+        };
+        at = headers[resource];
+        resource = &map.destination;
+    }
+    Err(ParseError::new(
+        "the maps never reach `location`",
+        at,
+        "this map goes in circles",
+    ))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 5;
+    const PART: u8 = 1;
+
+    type Input = Almanac;
+    type Error = AocError;
 
-    Ok(lowest_location.to_string())
+    fn parse(input: &str) -> Result<Almanac, AocError> {
+        parse_almanac(input).map_err(|err| err.with_source(input).into())
+    }
+
+    fn solve(almanac: Almanac, _params: &Params) -> Result<String, AocError> {
+        let Almanac { seeds, maps } = almanac;
+
+        let lowest_location = seeds
+            .iter()
+            .map(|seed| {
+                let mut resource = "seed".to_string();
+                let mut resource_id = *seed;
+                while resource != "location" {
+                    let map = maps.get(&resource.to_string()).unwrap();
+                    resource = map.destination.clone();
+                    resource_id = map.map(resource_id);
+                }
+                resource_id
+            })
+            .min()
+            .unwrap();
+
+        Ok(lowest_location.to_string())
+    }
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
+    const EXAMPLE: &str = "seeds: 79 14 55 13
 
         seed-to-soil map:
         50 98 2
@@ -148,7 +254,31 @@ mod tests {
         humidity-to-location map:
         60 56 37
         56 93 4";
-        assert_eq!("35", process(input)?);
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("35", process(EXAMPLE)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let almanac = Part1::parse(EXAMPLE)?;
+        let lines = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(lines.join("\n"), almanac.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("expected a space, found the end of the line", err.message);
+        assert_eq!(miette::SourceSpan::from((37, 0)), err.span);
+
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("no map from `soil`", err.message);
+        assert_eq!(miette::SourceSpan::from((14, 17)), err.span);
+    }
 }
@@ -1,38 +1,24 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    ops::Range,
-    u64::MAX,
+use std::{collections::HashMap, ops::Range};
+
+use aoc_utils::{
+    blocks, number, numbers, parse_all, token, IResult, IntervalSet, NomError, Params, ParseError,
+    Solution,
+};
+use nom::{
+    character::complete::{alpha1, line_ending, space0, space1},
+    combinator::{cut, map},
+    multi::many1,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
 };
 
 use crate::custom_error::AocError;
 
 #[derive(Debug)]
 pub struct MapRange {
-    source_start: u64,
-    source_end: u64,
+    source: Range<u64>,
     destination_start: u64,
 }
 
-impl MapRange {
-    // Receives a source range and returns a destination range
-    fn map(&self, source_start: u64, source_end: u64) -> Option<(Range<u64>, u64)> {
-        if !(self.source_start..self.source_end).contains(&source_start) {
-            return None;
-        }
-
-        //........[--source---]..
-        //.....[------]..........
-        println!("  found range {:?}", self);
-        let match_start = source_start;
-        let match_end = std::cmp::min(source_end, self.source_end);
-        let match_offset = source_start - self.source_start;
-
-        let dest_start = self.destination_start + match_offset;
-        let dest_end = dest_start + (match_end - match_start);
-        return Some((dest_start..dest_end, match_end));
-    }
-}
-
 #[derive(Debug)]
 pub struct SomethingToSomethingMap {
     source: String,
@@ -40,194 +26,197 @@ pub struct SomethingToSomethingMap {
     ranges_map: Vec<MapRange>,
 }
 
-impl SomethingToSomethingMap {
-    fn from_lines(lines: &mut VecDeque<&str>) -> Self {
-        let header = lines.pop_front().unwrap();
-        let src_to_dest = header.split_whitespace().nth(0).unwrap();
-        let parts = src_to_dest.split("-to-");
-        let source = parts.clone().nth(0).unwrap();
-        let destination = parts.clone().nth(1).unwrap();
-
-        let mut ranges_map = Vec::new();
-        loop {
-            match lines.pop_front() {
-                Some("") => break,
-                Some(line) => {
-                    let mut parts = line.split_whitespace();
-                    let destination_start = parts.next().unwrap().parse::<u64>().unwrap();
-                    let source_start = parts.next().unwrap().parse::<u64>().unwrap();
-                    let length = parts.next().unwrap().parse::<u64>().unwrap();
-                    let source_end = source_start + length;
-                    ranges_map.push(MapRange {
-                        source_start,
-                        source_end,
-                        destination_start,
-                    });
-                }
-                _ => break,
-            }
-        }
+impl std::fmt::Display for MapRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.destination_start,
+            self.source.start,
+            self.source.end - self.source.start
+        )
+    }
+}
 
-        ranges_map.sort_by_key(|r| r.source_start);
+// `50 98 2`: the destination start, the source start and the length of the range
+fn map_range(input: &str) -> IResult<'_, MapRange> {
+    let (rest, (destination_start, source_start, length)) =
+        tuple((number, preceded(space1, number), preceded(space1, number)))(input)?;
+    if u64::checked_add(source_start, length).is_none() {
+        let line = &input[..input.len() - rest.len()];
+        let err = NomError::at(line, "a range that ends before the largest id");
+        return Err(nom::Err::Failure(err));
+    }
+    Ok((
+        rest,
+        MapRange {
+            source: source_start..source_start + length,
+            destination_start,
+        },
+    ))
+}
 
-        return Self {
+// `seed-to-soil map:` followed by the ranges, one per line
+fn resource_map(input: &str) -> IResult<'_, SomethingToSomethingMap> {
+    let header = terminated(
+        separated_pair(alpha1, token("-to-"), alpha1),
+        token(" map:"),
+    );
+    let ranges = many1(preceded(pair(line_ending, space0), cut(map_range)));
+    map(
+        pair(header, ranges),
+        |((source, destination), ranges_map)| SomethingToSomethingMap {
             source: source.to_string(),
             destination: destination.to_string(),
             ranges_map,
-        };
-    }
+        },
+    )(input)
+}
 
-    // Returns the destination range and the new start for the source range
-    fn map(&self, source_start: u64, source_end: u64) -> (Range<u64>, u64) {
+impl std::fmt::Display for SomethingToSomethingMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-to-{} map:", self.source, self.destination)?;
         for range in &self.ranges_map {
-            println!("  checking range {:?} for {}", range, source_start);
-            if let Some((dest_range, match_end)) = range.map(source_start, source_end) {
-                return (dest_range, match_end);
-            }
-        }
-
-        let applicable_ranges = self
-            .ranges_map
-            .iter()
-            .filter(|r| r.source_end > source_start);
-
-        if applicable_ranges.clone().count() == 0 {
-            println!(
-                "  no applicable ranges, returning {}..{}",
-                source_start, source_end
-            );
-            return (source_start..source_end, source_end);
+            write!(f, "\n{}", range)?;
         }
+        Ok(())
+    }
+}
 
-        let min_range_start = applicable_ranges.clone().map(|r| r.source_start).min();
-        let max_range_end = applicable_ranges.clone().map(|r| r.source_end).max();
-
-        println!("  source_start: {}", source_start);
-        println!("  source_end: {}", source_end);
-        println!("  min_range_start: {:?}", min_range_start);
-        println!("  max_range_end: {:?}", max_range_end);
-
-        //.............[---source---].
-        //..[--] [--].................
-        if max_range_end.is_some() && source_start >= max_range_end.unwrap() {
-            println!(
-                "  source range after all ranges, returning {}..{}",
-                source_start, source_end
-            );
-            return (source_start..source_end, source_end);
-        }
+impl SomethingToSomethingMap {
+    fn map_to_ranges(&self, sources: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut unmapped = sources.clone();
+        let mut destinations = IntervalSet::new();
 
-        if min_range_start.is_some() {
-            let min_range_start = min_range_start.unwrap();
-
-            //.[---source---].............
-            //.................[--] [--]..
-            if source_end <= min_range_start {
-                println!(
-                    "  source range before all ranges, returning {}..{}",
-                    source_start, source_end
-                );
-                return (source_start..source_end, source_end);
-            }
-
-            //.[---source---].........
-            //...........[----] [--]..
-            if source_start < min_range_start && source_end >= min_range_start {
-                println!(
-                    "  source range overlaps start of ranges, returning prefix only {}..{}",
-                    source_start, min_range_start
-                );
-                return (source_start..min_range_start, min_range_start);
-            }
+        for range in &self.ranges_map {
+            let source = IntervalSet::from(range.source.clone());
+            let matched = unmapped.intersection(&source);
+            let moved = matched.shift(range.source.start, range.destination_start);
+            destinations = destinations.union(&moved);
+            unmapped = unmapped.difference(&source);
         }
 
-        panic!("unhandled case");
+        destinations.union(&unmapped)
     }
+}
 
-    fn map_to_ranges(&self, source_start: u64, source_end: u64) -> Vec<Range<u64>> {
-        let mut cur_start = source_start;
-        let cur_end = source_end;
-        let mut dest_ranges = Vec::new();
-
-        while cur_start < cur_end {
-            println!("Range left to map: {}..{}", cur_start, cur_end);
-            let (dest_range, match_end) = self.map(cur_start, cur_end);
-            cur_start = match_end;
-            println!("  pushing range {:?}", dest_range);
-            dest_ranges.push(dest_range);
-        }
+// The seeds to plant and the maps from each resource type to the next one
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: HashMap<String, SomethingToSomethingMap>,
+}
+
+impl std::fmt::Display for Almanac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seeds = self.seeds.iter().map(u64::to_string).collect::<Vec<_>>();
+        write!(f, "seeds: {}", seeds.join(" "))?;
 
-        return dest_ranges;
+        // Maps are printed in the order they are used, which is how the almanac lists them
+        let mut resource = "seed";
+        while let Some(map) = self.maps.get(resource) {
+            write!(f, "\n\n{}", map)?;
+            resource = &map.destination;
+        }
+        Ok(())
     }
 }
 
-fn min_location_for_range(
-    resource: String,
-    source_start: u64,
-    source_end: u64,
-    maps: &HashMap<String, SomethingToSomethingMap>,
-) -> u64 {
-    println!(
-        "min_location_for_range({}, {}, {})",
-        resource, source_start, source_end
-    );
-
-    // Find the map of this resource to the next one
-    let map = maps.get(&resource).unwrap();
+// `seeds: 79 14 55 13`
+fn seeds(input: &str) -> IResult<'_, Vec<u64>> {
+    preceded(pair(token("seeds:"), space1), numbers)(input)
+}
 
-    // Map the source range to a list of destination ranges
-    let dest_ranges = map.map_to_ranges(source_start, source_end);
+fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+    let blocks = blocks(input);
+    let Some((seeds_block, map_blocks)) = blocks.split_first() else {
+        return Err(ParseError::new(
+            "empty almanac",
+            input,
+            "expected a `seeds:` line",
+        ));
+    };
+    let seeds_line = seeds_block.trim();
+    let seeds = parse_all(seeds_line, seeds)?;
+    if seeds.len() % 2 != 0 {
+        return Err(ParseError::new(
+            "odd number of seeds",
+            seeds_line,
+            "seeds come in pairs of start and length",
+        ));
+    }
 
-    // If this map maps into locations, find the minimum location
-    let dest_resource = &map.destination;
-    if dest_resource == "location" {
-        return dest_ranges.iter().map(|r| r.start).min().unwrap();
+    let mut maps = HashMap::new();
+    let mut headers = HashMap::new();
+    for block in map_blocks {
+        let block = block.trim_start();
+        let map = parse_all(block, resource_map)?;
+        headers.insert(map.source.clone(), block.lines().next().unwrap_or_default());
+        maps.insert(map.source.clone(), map);
     }
 
-    // Otherwise, recurse into the destination resource
-    dest_ranges
-        .iter()
-        .map(|r| min_location_for_range(dest_resource.clone(), r.start, r.end, maps))
-        .min()
-        .unwrap()
+    // The maps have to lead from seeds to locations, or following them would never finish
+    let mut resource = "seed";
+    let mut at = seeds_line;
+    for _ in 0..=maps.len() {
+        if resource == "location" {
+            return Ok(Almanac { seeds, maps });
+        }
+        let Some(map) = maps.get(resource) else {
+            let message = format!("no map from `{}`", resource);
+            return Err(ParseError::new(
+                message,
+                at,
+                "nothing maps this any further",
+            ));
+        };
+        at = headers[resource];
+        resource = &map.destination;
+    }
+    Err(ParseError::new(
+        "the maps never reach `location`",
+        at,
+        "this map goes in circles",
+    ))
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut lines = input.lines().collect::<VecDeque<_>>();
-    let seeds = lines
-        .pop_front()
-        .unwrap()
-        .split(": ")
-        .nth(1)
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
-    lines.pop_front();
+pub struct Part2;
 
-    let mut maps = HashMap::new();
-    while !lines.is_empty() {
-        let map = SomethingToSomethingMap::from_lines(&mut lines);
-        maps.insert(map.source.clone(), map);
+impl Solution for Part2 {
+    const DAY: u8 = 5;
+    const PART: u8 = 2;
+
+    type Input = Almanac;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Almanac, AocError> {
+        parse_almanac(input).map_err(|err| err.with_source(input).into())
     }
 
-    let mut lowest_location = MAX;
-    for seed_range in seeds.chunks(2) {
-        let range_start = seed_range[0];
-        let range_len = seed_range[1];
-        let range_end = range_start + range_len;
-        dbg!(range_start, range_end);
+    fn solve(almanac: Almanac, _params: &Params) -> Result<String, AocError> {
+        let Almanac { seeds, maps } = almanac;
 
-        let min_location =
-            min_location_for_range("seed".to_string(), range_start, range_end, &maps);
+        let mut values = seeds
+            .chunks(2)
+            .map(|seed_range| seed_range[0]..seed_range[0] + seed_range[1])
+            .collect::<IntervalSet<_>>();
 
-        if min_location < lowest_location {
-            lowest_location = min_location;
+        // Follow the seeds through the maps until we know all the locations they end up in
+        let mut resource = "seed";
+        while resource != "location" {
+            let map = maps.get(resource).unwrap();
+            values = map.map_to_ranges(&values);
+            resource = &map.destination;
         }
+
+        let lowest_location = values.min().unwrap();
+        Ok(lowest_location.to_string())
     }
+}
 
-    Ok(lowest_location.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -241,21 +230,15 @@ mod tests {
         220 20 20
         ";
 
-        let mut lines = &mut input.lines().map(|s| s.trim()).collect::<VecDeque<_>>();
-        let map = SomethingToSomethingMap::from_lines(&mut lines);
-        let ranges = map.map_to_ranges(1, 110);
-        assert_eq!(ranges.len(), 5);
-        assert_eq!(ranges[0], 1..10);
-        assert_eq!(ranges[1], 110..115);
-        assert_eq!(ranges[2], 15..20);
-        assert_eq!(ranges[3], 220..240);
-        assert_eq!(ranges[4], 40..110);
+        let map = parse_all(input.trim(), resource_map)?;
+        let ranges = map.map_to_ranges(&IntervalSet::from(1..110));
+
+        // 1..10 and 40..110 are not mapped, 10..15 and 20..40 are moved
+        assert_eq!(&[1..10, 15..20, 40..115, 220..240], ranges.ranges());
         Ok(())
     }
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
+    const EXAMPLE: &str = "seeds: 79 14 55 13
 
         seed-to-soil map:
         50 98 2
@@ -288,7 +271,18 @@ mod tests {
         humidity-to-location map:
         60 56 37
         56 93 4";
-        assert_eq!("46", process(input)?);
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("46", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let almanac = Part2::parse(EXAMPLE)?;
+        let lines = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(lines.join("\n"), almanac.to_string());
         Ok(())
     }
 }
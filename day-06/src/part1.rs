@@ -1,54 +1,121 @@
+use aoc_utils::{numbers, parse_all, token, IResult, NomError, Params, Solution};
+use nom::{
+    character::complete::{multispace0, space1},
+    combinator::consumed,
+    sequence::{pair, preceded},
+};
+
 use crate::custom_error::AocError;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let lines = input.lines().collect::<Vec<_>>();
-    let times = lines[0]
-        .split_whitespace()
-        .filter(|s| s != &"Time:")
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
-
-    let distances = lines[1]
-        .split_whitespace()
-        .filter(|s| s != &"Distance:")
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
-
-    // Merge times and distances into a vector of tuples
-    let races = times
-        .iter()
-        .zip(distances.iter())
-        .map(|(t, d)| (*t, *d))
-        .collect::<Vec<_>>();
-
-    let mut result = 1;
-    for race in races {
-        let (race_time, best_distance) = race;
-        let mut better_results = 0;
-        println!("Race: time={}, distance={}", race_time, best_distance);
-        for time in 1..race_time - 1 {
-            let speed = time;
-            let distance = (race_time - time) * speed;
-            if distance > best_distance {
-                better_results += 1;
+// The time and the record distance of every race, in the same order
+#[derive(Debug)]
+pub struct Races {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+}
+
+impl std::fmt::Display for Races {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (mut times, mut distances) = ("Time:    ".to_string(), "Distance:".to_string());
+        for (time, distance) in self.times.iter().zip(&self.distances) {
+            // Each race gets a column as wide as its longest number
+            let width = time.to_string().len().max(distance.to_string().len());
+            times += &format!("  {:>width$}", time);
+            distances += &format!("  {:>width$}", distance);
+        }
+        write!(f, "{}\n{}", times, distances)
+    }
+}
+
+// `Time:      7  15   30` followed by `Distance:  9  40  200` on the next line
+fn races(input: &str) -> IResult<'_, Races> {
+    let (input, times) = preceded(pair(token("Time:"), space1), numbers)(input)?;
+    let (rest, (line, distances)) = consumed(preceded(
+        pair(multispace0, pair(token("Distance:"), space1)),
+        numbers::<u64>,
+    ))(input)?;
+    if times.len() != distances.len() {
+        let expected = format!("{} distances, one for every race", times.len());
+        return Err(nom::Err::Failure(NomError::at(line.trim_start(), expected)));
+    }
+    Ok((rest, Races { times, distances }))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 6;
+    const PART: u8 = 1;
+
+    type Input = Races;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Races, AocError> {
+        Ok(parse_all(input.trim(), races).map_err(|err| err.with_source(input))?)
+    }
+
+    fn solve(races: Races, _params: &Params) -> Result<String, AocError> {
+        let Races { times, distances } = races;
+
+        // Merge times and distances into a vector of tuples
+        let races = times
+            .iter()
+            .zip(distances.iter())
+            .map(|(t, d)| (*t, *d))
+            .collect::<Vec<_>>();
+
+        let mut result = 1;
+        for race in races {
+            let (race_time, best_distance) = race;
+            let mut better_results = 0;
+            tracing::debug!(race_time, best_distance, "race");
+            for time in 1..race_time.saturating_sub(1) {
+                let speed = time;
+                let distance = (race_time - time) * speed;
+                if distance > best_distance {
+                    better_results += 1;
+                }
             }
+            result *= better_results;
         }
-        result *= better_results;
+
+        Ok(result.to_string())
     }
+}
 
-    Ok(result.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "Time:      7  15   30
+                           Distance:  9  40  200";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "Time:      7  15   30
-                     Distance:  9  40  200";
-        assert_eq!("288", process(input)?);
+        assert_eq!("288", process(EXAMPLE)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let races = Part1::parse(EXAMPLE)?;
+        let lines = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(lines.join("\n"), races.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("Time: 7 15\nDistance: 9\n"));
+        assert_eq!(
+            "expected 2 distances, one for every race, found `Distance: 9`",
+            err.message
+        );
+        assert_eq!(miette::SourceSpan::from((11, 11)), err.span);
+    }
 }
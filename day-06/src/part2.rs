@@ -1,65 +1,135 @@
 use std::cmp::min;
 
+use aoc_utils::{parse_all, token, IResult, NomError, Params, Solution};
+use nom::{
+    character::complete::{digit1, multispace0, space1},
+    combinator::{map, recognize},
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair},
+};
+
 use crate::custom_error::AocError;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let lines = input.lines().collect::<Vec<_>>();
-    let times = lines[0]
-        .split_whitespace()
-        .filter(|s| s != &"Time:")
-        .collect::<Vec<_>>()
-        .join("")
-        .parse::<i64>()
-        .unwrap();
-
-    let distances = lines[1]
-        .split_whitespace()
-        .filter(|s| s != &"Distance:")
-        .collect::<Vec<_>>()
-        .join("")
-        .parse::<i64>()
-        .unwrap();
-
-    // Merge times and distances into a vector of tuples
-    let race = (times, distances);
-    let (race_time, best_distance) = race;
-    println!("Race: time={}, distance={}", race_time, best_distance);
-
-    // (race_time - wait_time) * wait_time = best_distance
-    // So we should only start looking for better results after wait_time
-    let d = ((race_time * race_time - 4 * best_distance) as f64).sqrt();
-    let wait_time = min(
-        -(0 as i64 - race_time + d.round() as i64) / 2,
-        -(0 as i64 - race_time - d.round() as i64) / 2,
-    );
-    println!("Best wait time: {}", wait_time);
-
-    let mut better_results_start = 0;
-    for time in wait_time..race_time - 1 {
-        let speed = time;
-        let distance = (race_time - time) * speed;
-        if distance > best_distance {
-            better_results_start = time;
-            break;
+// The one long race the sheet really describes
+#[derive(Debug, PartialEq)]
+pub struct Race {
+    time: i64,
+    distance: i64,
+}
+
+impl std::fmt::Display for Race {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Time:      {}\nDistance:  {}", self.time, self.distance)
+    }
+}
+
+// `Time:      7  15   30` read as a single number, ignoring the spaces between its digits
+fn kerned<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<'a, i64> {
+    preceded(pair(token(label), space1), |input: &'a str| {
+        let (rest, digits) = recognize(separated_list1(space1, digit1))(input)?;
+        let number = digits.split_whitespace().collect::<String>();
+        match number.parse() {
+            Ok(number) => Ok((rest, number)),
+            Err(_) => Err(nom::Err::Failure(NomError::at(digits, "a valid number"))),
         }
+    })
+}
+
+fn race(input: &str) -> IResult<'_, Race> {
+    map(
+        separated_pair(kerned("Time:"), multispace0, kerned("Distance:")),
+        |(time, distance)| Race { time, distance },
+    )(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 6;
+    const PART: u8 = 2;
+
+    type Input = Race;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Race, AocError> {
+        Ok(parse_all(input.trim(), race).map_err(|err| err.with_source(input))?)
     }
 
-    println!("Better results start at {}", better_results_start);
-    let result = race_time - better_results_start * 2 + 1;
+    fn solve(race: Race, _params: &Params) -> Result<String, AocError> {
+        let Race {
+            time: race_time,
+            distance: best_distance,
+        } = race;
+        tracing::debug!(race_time, best_distance, "race");
 
-    Ok(result.to_string())
+        // (race_time - wait_time) * wait_time = best_distance
+        // So we should only start looking for better results after wait_time
+        let d = ((race_time * race_time - 4 * best_distance) as f64).sqrt();
+        let wait_time = min(
+            -(0_i64 - race_time + d.round() as i64) / 2,
+            -(0_i64 - race_time - d.round() as i64) / 2,
+        );
+        tracing::debug!(wait_time, "best wait time");
+
+        let mut better_results_start = 0;
+        for time in wait_time..race_time - 1 {
+            let speed = time;
+            let distance = (race_time - time) * speed;
+            if distance > best_distance {
+                better_results_start = time;
+                break;
+            }
+        }
+
+        tracing::debug!(better_results_start, "better results start");
+        let result = race_time - better_results_start * 2 + 1;
+
+        Ok(result.to_string())
+    }
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "Time:      7  15   30
+                           Distance:  9  40  200";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "Time:      7  15   30
-                     Distance:  9  40  200";
-        assert_eq!("71503", process(input)?);
+        assert_eq!("71503", process(EXAMPLE)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let race = Part2::parse(EXAMPLE)?;
+        assert_eq!(
+            Race {
+                time: 71530,
+                distance: 940200
+            },
+            race
+        );
+        assert_eq!(race, Part2::parse(&race.to_string())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("Time: 7  1x\nDistance: 9\n"));
+        assert_eq!("expected `Distance:`, found `x`", err.message);
+        assert_eq!(miette::SourceSpan::from((10, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("Time: 7\n"));
+        assert_eq!(
+            "expected `Distance:`, found the end of the line",
+            err.message
+        );
+    }
 }
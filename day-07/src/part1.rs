@@ -1,3 +1,6 @@
+use aoc_utils::{number, parse_lines, IResult, NomError, Params, Solution};
+use nom::{character::complete::space1, combinator::map, multi::count, sequence::separated_pair};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
@@ -17,6 +20,15 @@ enum Card {
     Ace,
 }
 
+// A card is a single character, checked one at a time so a bad one is pointed at
+fn card(input: &str) -> IResult<'_, Card> {
+    let symbol = input.chars().next().map_or("", |c| &input[..c.len_utf8()]);
+    match Card::from_str(symbol) {
+        Some(card) => Ok((&input[symbol.len()..], card)),
+        None => Err(nom::Err::Error(NomError::at(symbol, "a card"))),
+    }
+}
+
 impl Card {
     fn from_str(s: &str) -> Option<Self> {
         match s {
@@ -36,6 +48,24 @@ impl Card {
             _ => None,
         }
     }
+
+    fn symbol(&self) -> char {
+        match self {
+            Self::Ace => 'A',
+            Self::King => 'K',
+            Self::Queen => 'Q',
+            Self::Jack => 'J',
+            Self::Ten => 'T',
+            Self::Nine => '9',
+            Self::Eight => '8',
+            Self::Seven => '7',
+            Self::Six => '6',
+            Self::Five => '5',
+            Self::Four => '4',
+            Self::Three => '3',
+            Self::Two => '2',
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd)]
@@ -55,19 +85,6 @@ struct Hand {
 }
 
 impl Hand {
-    fn from_str(s: &str) -> Self {
-        let mut cards = Vec::new();
-        for c in s.chars() {
-            if let Some(card) = Card::from_str(&c.to_string()) {
-                cards.push(card);
-            } else {
-                panic!("Invalid card: {}", c);
-            }
-        }
-
-        Self { cards }
-    }
-
     fn hand_type(&self) -> HandType {
         let mut counts = std::collections::HashMap::new();
         for card in self.cards.iter() {
@@ -76,7 +93,7 @@ impl Hand {
         }
 
         let mut counts = counts.into_iter().collect::<Vec<_>>();
-        counts.sort_by(|a, b| b.1.cmp(&a.1));
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
 
         match counts[0].1 {
             5 => HandType::FiveOfAKind,
@@ -119,40 +136,81 @@ impl PartialOrd for Hand {
     }
 }
 
-struct Play {
+pub struct Play {
     hand: Hand,
     bid: u64,
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut plays = input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let hand = Hand::from_str(parts.next().unwrap());
-            let bid = parts.next().unwrap().parse::<u64>().unwrap();
-            Play { hand, bid }
-        })
-        .collect::<Vec<_>>();
+impl std::fmt::Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cards = self.cards.iter().map(Card::symbol).collect::<String>();
+        write!(f, "{}", cards)
+    }
+}
+
+impl std::fmt::Display for Play {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.hand, self.bid)
+    }
+}
+
+fn hand(input: &str) -> IResult<'_, Hand> {
+    map(count(card, 5), |cards| Hand { cards })(input)
+}
+
+// `32T3K 765`: the hand and its bid
+fn play(input: &str) -> IResult<'_, Play> {
+    map(separated_pair(hand, space1, number), |(hand, bid)| Play {
+        hand,
+        bid,
+    })(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 7;
+    const PART: u8 = 1;
+
+    type Input = Vec<Play>;
+    type Error = AocError;
 
-    plays.sort_by(|a, b| a.hand.partial_cmp(&b.hand).unwrap());
+    fn parse(input: &str) -> Result<Vec<Play>, AocError> {
+        Ok(parse_lines(input, play)?)
+    }
+
+    fn solve(mut plays: Vec<Play>, _params: &Params) -> Result<String, AocError> {
+        plays.sort_by(|a, b| a.hand.partial_cmp(&b.hand).unwrap());
+
+        let mut result = 0;
+        for (i, play) in plays.iter().enumerate() {
+            result += play.bid * (i + 1) as u64;
+        }
 
-    let mut result = 0;
-    for (i, play) in plays.iter().enumerate() {
-        result += play.bid * (i + 1) as u64;
+        Ok(result.to_string())
     }
+}
 
-    return Ok(result.to_string());
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_utils::{parse_all, ParseError};
+
     use super::*;
 
+    impl Hand {
+        fn from_str(s: &str) -> Result<Self, ParseError> {
+            parse_all(s, hand)
+        }
+    }
+
     #[test]
     fn test_hand_from_str() {
-        let hand = Hand::from_str("32T3K");
+        let hand = Hand::from_str("32T3K").unwrap();
         assert_eq!(
             hand,
             Hand {
@@ -161,6 +219,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hand_errors() {
+        assert_eq!(
+            "expected a card, found `X`",
+            Hand::from_str("32X3K").unwrap_err().message
+        );
+        assert!(Hand::from_str("32T3").is_err());
+
+        let err = aoc_utils::expect_parse_error!(process("32T3K 765\nT55J5\n"));
+        assert_eq!("expected a space, found the end of the line", err.message);
+        assert_eq!(miette::SourceSpan::from((15, 0)), err.span);
+    }
+
     #[test]
     fn test_card_from_str() {
         assert_eq!(Card::from_str("A"), Some(Card::Ace));
@@ -196,59 +267,68 @@ mod tests {
 
     #[test]
     fn test_hand_type() {
-        let hand = Hand::from_str("AAAAA");
+        let hand = Hand::from_str("AAAAA").unwrap();
         assert_eq!(hand.hand_type(), HandType::FiveOfAKind);
 
-        let hand = Hand::from_str("AA8AA");
+        let hand = Hand::from_str("AA8AA").unwrap();
         assert_eq!(hand.hand_type(), HandType::FourOfAKind);
 
-        let hand = Hand::from_str("23332");
+        let hand = Hand::from_str("23332").unwrap();
         assert_eq!(hand.hand_type(), HandType::FullHouse);
 
-        let hand = Hand::from_str("TTT98");
+        let hand = Hand::from_str("TTT98").unwrap();
         assert_eq!(hand.hand_type(), HandType::ThreeOfAKind);
 
-        let hand = Hand::from_str("23432");
+        let hand = Hand::from_str("23432").unwrap();
         assert_eq!(hand.hand_type(), HandType::TwoPair);
 
-        let hand = Hand::from_str("A23A4");
+        let hand = Hand::from_str("A23A4").unwrap();
         assert_eq!(hand.hand_type(), HandType::OnePair);
 
-        let hand = Hand::from_str("23456");
+        let hand = Hand::from_str("23456").unwrap();
         assert_eq!(hand.hand_type(), HandType::HighCard);
     }
 
     #[test]
     fn test_hand_type_ord() {
-        let five_of_a_kind = Hand::from_str("AAAAA");
-        let four_of_a_kind = Hand::from_str("AA8AA");
+        let five_of_a_kind = Hand::from_str("AAAAA").unwrap();
+        let four_of_a_kind = Hand::from_str("AA8AA").unwrap();
         assert!(five_of_a_kind > four_of_a_kind);
 
-        let four_of_a_kind = Hand::from_str("AA8AA");
-        let full_house = Hand::from_str("23332");
+        let four_of_a_kind = Hand::from_str("AA8AA").unwrap();
+        let full_house = Hand::from_str("23332").unwrap();
         assert!(four_of_a_kind > full_house);
 
-        let full_house = Hand::from_str("23332");
-        let three_of_a_kind = Hand::from_str("TTT98");
+        let full_house = Hand::from_str("23332").unwrap();
+        let three_of_a_kind = Hand::from_str("TTT98").unwrap();
         assert!(full_house > three_of_a_kind);
 
-        let three_of_a_kind = Hand::from_str("TTT98");
-        let two_pair = Hand::from_str("23432");
+        let three_of_a_kind = Hand::from_str("TTT98").unwrap();
+        let two_pair = Hand::from_str("23432").unwrap();
         assert!(three_of_a_kind > two_pair);
 
-        let two_pair = Hand::from_str("23432");
-        let one_pair = Hand::from_str("A23A4");
+        let two_pair = Hand::from_str("23432").unwrap();
+        let one_pair = Hand::from_str("A23A4").unwrap();
         assert!(two_pair > one_pair);
     }
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "32T3K 765
+    const EXAMPLE: &str = "32T3K 765
         T55J5 684
         KK677 28
         KTJJT 220
         QQQJA 483";
-        assert_eq!("6440", process(input)?);
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("6440", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let plays = Part1::parse(EXAMPLE)?;
+        let printed = plays.iter().map(Play::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
         Ok(())
     }
 }
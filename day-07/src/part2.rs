@@ -1,5 +1,8 @@
 use itertools::Itertools;
 
+use aoc_utils::{number, parse_lines, IResult, NomError, Params, Solution};
+use nom::{character::complete::space1, combinator::map, multi::count, sequence::separated_pair};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
@@ -19,6 +22,15 @@ enum Card {
     Ace,
 }
 
+// A card is a single character, checked one at a time so a bad one is pointed at
+fn card(input: &str) -> IResult<'_, Card> {
+    let symbol = input.chars().next().map_or("", |c| &input[..c.len_utf8()]);
+    match Card::from_str(symbol) {
+        Some(card) => Ok((&input[symbol.len()..], card)),
+        None => Err(nom::Err::Error(NomError::at(symbol, "a card"))),
+    }
+}
+
 impl Card {
     fn from_str(s: &str) -> Option<Self> {
         match s {
@@ -38,6 +50,24 @@ impl Card {
             _ => None,
         }
     }
+
+    fn symbol(&self) -> char {
+        match self {
+            Self::Joker => 'J',
+            Self::Ace => 'A',
+            Self::King => 'K',
+            Self::Queen => 'Q',
+            Self::Ten => 'T',
+            Self::Nine => '9',
+            Self::Eight => '8',
+            Self::Seven => '7',
+            Self::Six => '6',
+            Self::Five => '5',
+            Self::Four => '4',
+            Self::Three => '3',
+            Self::Two => '2',
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd)]
@@ -52,7 +82,7 @@ enum HandType {
 }
 
 impl HandType {
-    fn from_cards(cards: &Vec<Card>) -> HandType {
+    fn from_cards(cards: &[Card]) -> HandType {
         let joker_count = cards.iter().filter(|c| **c == Card::Joker).count();
         if joker_count == 5 {
             return HandType::FiveOfAKind;
@@ -93,23 +123,6 @@ struct Hand {
     hand_type: HandType,
 }
 
-impl Hand {
-    fn from_str(s: &str) -> Self {
-        println!("parsing hand: {}", s);
-        let mut cards = Vec::new();
-        for c in s.chars() {
-            if let Some(card) = Card::from_str(&c.to_string()) {
-                cards.push(card);
-            } else {
-                panic!("Invalid card: {}", c);
-            }
-        }
-
-        let hand_type = HandType::from_cards(&cards);
-        Self { cards, hand_type }
-    }
-}
-
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         let hand_type = self.hand_type;
@@ -128,40 +141,84 @@ impl PartialOrd for Hand {
     }
 }
 
-struct Play {
+pub struct Play {
     hand: Hand,
     bid: u64,
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut plays = input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let hand = Hand::from_str(parts.next().unwrap());
-            let bid = parts.next().unwrap().parse::<u64>().unwrap();
-            Play { hand, bid }
-        })
-        .collect_vec();
-
-    plays.sort_by(|a, b| a.hand.partial_cmp(&b.hand).unwrap());
-
-    let mut result = 0;
-    for (i, play) in plays.iter().enumerate() {
-        result += play.bid * (i + 1) as u64;
+impl std::fmt::Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cards = self.cards.iter().map(Card::symbol).collect::<String>();
+        write!(f, "{}", cards)
+    }
+}
+
+impl std::fmt::Display for Play {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.hand, self.bid)
+    }
+}
+
+fn hand(input: &str) -> IResult<'_, Hand> {
+    map(count(card, 5), |cards| Hand {
+        hand_type: HandType::from_cards(&cards),
+        cards,
+    })(input)
+}
+
+// `32T3K 765`: the hand and its bid
+fn play(input: &str) -> IResult<'_, Play> {
+    map(separated_pair(hand, space1, number), |(hand, bid)| Play {
+        hand,
+        bid,
+    })(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 7;
+    const PART: u8 = 2;
+
+    type Input = Vec<Play>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<Play>, AocError> {
+        Ok(parse_lines(input, play)?)
     }
 
-    return Ok(result.to_string());
+    fn solve(mut plays: Vec<Play>, _params: &Params) -> Result<String, AocError> {
+        plays.sort_by(|a, b| a.hand.partial_cmp(&b.hand).unwrap());
+
+        let mut result = 0;
+        for (i, play) in plays.iter().enumerate() {
+            result += play.bid * (i + 1) as u64;
+        }
+
+        Ok(result.to_string())
+    }
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_utils::{parse_all, ParseError};
+
     use super::*;
 
+    impl Hand {
+        fn from_str(s: &str) -> Result<Self, ParseError> {
+            parse_all(s, hand)
+        }
+    }
+
     #[test]
     fn test_hand_from_str() {
-        let hand = Hand::from_str("32T3K");
+        let hand = Hand::from_str("32T3K").unwrap();
         assert_eq!(
             hand,
             Hand {
@@ -222,50 +279,55 @@ mod tests {
         ];
 
         for (hand_str, expected_hand_type) in tests {
-            let hand = Hand::from_str(hand_str);
+            let hand = Hand::from_str(hand_str).unwrap();
             assert_eq!(hand.hand_type, expected_hand_type);
         }
     }
 
     #[test]
     fn test_hand_type_ord() {
-        let five_of_a_kind = Hand::from_str("AAAAA");
-        let four_of_a_kind = Hand::from_str("AA8AA");
+        let five_of_a_kind = Hand::from_str("AAAAA").unwrap();
+        let four_of_a_kind = Hand::from_str("AA8AA").unwrap();
         assert!(five_of_a_kind > four_of_a_kind);
 
-        let four_of_a_kind = Hand::from_str("AA8AA");
-        let full_house = Hand::from_str("23332");
+        let four_of_a_kind = Hand::from_str("AA8AA").unwrap();
+        let full_house = Hand::from_str("23332").unwrap();
         assert!(four_of_a_kind > full_house);
 
-        let full_house = Hand::from_str("23332");
-        let three_of_a_kind = Hand::from_str("TTT98");
+        let full_house = Hand::from_str("23332").unwrap();
+        let three_of_a_kind = Hand::from_str("TTT98").unwrap();
         assert!(full_house > three_of_a_kind);
 
-        let three_of_a_kind = Hand::from_str("TTT98");
-        let two_pair = Hand::from_str("23432");
+        let three_of_a_kind = Hand::from_str("TTT98").unwrap();
+        let two_pair = Hand::from_str("23432").unwrap();
         assert!(three_of_a_kind > two_pair);
 
-        let two_pair = Hand::from_str("23432");
-        let one_pair = Hand::from_str("A23A4");
+        let two_pair = Hand::from_str("23432").unwrap();
+        let one_pair = Hand::from_str("A23A4").unwrap();
         assert!(two_pair > one_pair);
 
-        let five_of_a_kind = Hand::from_str("22222");
-        let file_jacks = Hand::from_str("JJJJJ");
+        let five_of_a_kind = Hand::from_str("22222").unwrap();
+        let file_jacks = Hand::from_str("JJJJJ").unwrap();
         assert!(five_of_a_kind > file_jacks);
     }
 
+    const EXAMPLE: &str = "32T3K 765
+                           T55J5 684
+                           KK677 28
+                           KTJJT 220
+                           QQQJA 483";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "32T3K 765
-                     T55J5 684
-                     KK677 28
-                     KTJJT 220
-                     QQQJA 483";
-        assert_eq!("5905", process(input)?);
+        assert_eq!("5905", process(EXAMPLE)?);
         Ok(())
     }
-}
 
-// Submissions:
-// 250475140 - too low
-// 250665248 - correct
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let plays = Part2::parse(EXAMPLE)?;
+        let printed = plays.iter().map(Play::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
+        Ok(())
+    }
+}
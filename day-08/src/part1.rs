@@ -1,5 +1,14 @@
 use std::collections::HashMap;
 
+use aoc_utils::{list1, name, parse_all, symbol, token, ws, IResult, Params, ParseError, Solution};
+use nom::{
+    branch::alt,
+    character::complete::{line_ending, multispace1},
+    combinator::{eof, map, peek},
+    multi::many_till,
+    sequence::{delimited, separated_pair},
+};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug)]
@@ -27,66 +36,160 @@ impl Iterator for StepGenerator {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut lines = input.lines();
-    let instructions = lines.next().unwrap().trim();
-    let mut steps = StepGenerator {
-        instructions: instructions.chars().collect(),
-        current: 0,
-    };
-
-    lines.next();
-
-    let mut nodes = HashMap::new();
-    for line in lines {
-        let line = line.trim();
-        let mut parts = line.split(" = ");
-        let node_name = parts.next().unwrap().trim();
-        let adjacent = parts.next().unwrap().trim();
-        let mut adjacent = adjacent
-            .trim_start_matches('(')
-            .trim_end_matches(')')
-            .split(", ")
-            .map(|s| s.to_string());
-        let left = adjacent.next().unwrap().clone();
-        let right = adjacent.next().unwrap().clone();
-
-        nodes.insert(node_name, Node { left, right });
+// The left/right instructions and the map of nodes
+#[derive(Debug)]
+pub struct Network {
+    steps: StepGenerator,
+    nodes: HashMap<String, Node>,
+}
+
+// Prints the nodes sorted by name, which is how the puzzle examples list them
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let steps = self.steps.instructions.iter().collect::<String>();
+        writeln!(f, "{}", steps)?;
+
+        let mut names = self.nodes.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            let node = &self.nodes[name];
+            write!(f, "\n{} = ({}, {})", name, node.left, node.right)?;
+        }
+        Ok(())
     }
+}
+
+// A node and the ones to the left and to the right of it
+type Link<'a> = (&'a str, &'a str, &'a str);
+
+// `AAA = (BBB, CCC)`
+fn link(input: &str) -> IResult<'_, Link<'_>> {
+    let adjacent = delimited(
+        token("("),
+        separated_pair(name, ws(token(",")), name),
+        token(")"),
+    );
+    map(
+        separated_pair(name, ws(token("=")), adjacent),
+        |(node, (left, right))| (node, left, right),
+    )(input)
+}
+
+// The steps on the first line, then a node per line after a blank one
+fn network(input: &str) -> IResult<'_, (Vec<char>, Vec<Link<'_>>)> {
+    let steps = map(
+        many_till(symbol("LR", "`L` or `R`"), peek(alt((line_ending, eof)))),
+        |(steps, _)| steps,
+    );
+    separated_pair(steps, multispace1, list1(multispace1, link))(input)
+}
 
-    let mut current = "AAA";
-    let mut step_count = 0;
-    while current != "ZZZ" {
-        let node = nodes.get(current).unwrap();
-        let step = steps.next().unwrap();
-        match step {
-            'L' => current = &node.left,
-            'R' => current = &node.right,
-            _ => unreachable!(),
+fn parse_network(input: &str) -> Result<(Vec<char>, HashMap<String, Node>), ParseError> {
+    let (instructions, links) = parse_all(input.trim(), network)?;
+
+    // Every node the instructions can lead to has to be on the map
+    let names = links.iter().map(|(name, _, _)| *name).collect::<Vec<_>>();
+    for (_, left, right) in &links {
+        for next in [left, right] {
+            if !names.contains(next) {
+                return Err(ParseError::new(
+                    format!("unknown node `{}`", next),
+                    next,
+                    "not on the map",
+                ));
+            }
         }
-        step_count += 1;
     }
 
-    Ok(step_count.to_string())
+    let nodes = links
+        .into_iter()
+        .map(|(name, left, right)| {
+            let node = Node {
+                left: left.to_string(),
+                right: right.to_string(),
+            };
+            (name.to_string(), node)
+        })
+        .collect::<HashMap<_, _>>();
+    if !nodes.contains_key("AAA") {
+        return Err(ParseError::new(
+            "no node `AAA`",
+            input,
+            "expected a node to start from",
+        ));
+    }
+    Ok((instructions, nodes))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 8;
+    const PART: u8 = 1;
+
+    type Input = Network;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Network, AocError> {
+        let (instructions, nodes) = parse_network(input).map_err(|err| err.with_source(input))?;
+        let steps = StepGenerator {
+            instructions,
+            current: 0,
+        };
+
+        Ok(Network { steps, nodes })
+    }
+
+    fn solve(network: Network, _params: &Params) -> Result<String, AocError> {
+        let Network { mut steps, nodes } = network;
+
+        let mut current = "AAA";
+        let mut step_count = 0;
+        while current != "ZZZ" {
+            let node = nodes.get(current).unwrap();
+            let step = steps.next().unwrap();
+            match step {
+                'L' => current = &node.left,
+                'R' => current = &node.right,
+                _ => unreachable!(),
+            }
+            step_count += 1;
+        }
+
+        Ok(step_count.to_string())
+    }
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "RL
+
+                           AAA = (BBB, CCC)
+                           BBB = (DDD, EEE)
+                           CCC = (ZZZ, GGG)
+                           DDD = (DDD, DDD)
+                           EEE = (EEE, EEE)
+                           GGG = (GGG, GGG)
+                           ZZZ = (ZZZ, ZZZ)";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "RL
-
-                     AAA = (BBB, CCC)
-                     BBB = (DDD, EEE)
-                     CCC = (ZZZ, GGG)
-                     DDD = (DDD, DDD)
-                     EEE = (EEE, EEE)
-                     GGG = (GGG, GGG)
-                     ZZZ = (ZZZ, ZZZ)";
-        assert_eq!("2", process(input)?);
+        assert_eq!("2", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let network = Part1::parse(EXAMPLE)?;
+        let lines = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(lines.join("\n"), network.to_string());
         Ok(())
     }
 
@@ -100,4 +203,15 @@ mod tests {
         assert_eq!("6", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("unknown node `BBB`", err.message);
+        assert_eq!(miette::SourceSpan::from((11, 3)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("LRX\n\nAAA = (AAA, AAA)\n"));
+        assert_eq!(miette::SourceSpan::from((2, 1)), err.span);
+    }
 }
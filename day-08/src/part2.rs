@@ -1,3 +1,12 @@
+use aoc_utils::{list1, name, parse_all, symbol, token, ws, IResult, Params, ParseError, Solution};
+use nom::{
+    branch::alt,
+    character::complete::{line_ending, multispace1},
+    combinator::{eof, map, peek},
+    multi::many_till,
+    sequence::{delimited, separated_pair},
+};
+
 use crate::custom_error::AocError;
 use num_integer::lcm;
 use std::collections::HashMap;
@@ -41,7 +50,7 @@ impl Ghost {
         }
     }
 
-    fn take_step(&mut self, nodes: &HashMap<&str, Node>) {
+    fn take_step(&mut self, nodes: &HashMap<String, Node>) {
         let node = nodes.get(self.current.as_str()).unwrap();
         self.current = match self.steps.next() {
             'L' => node.left.clone(),
@@ -51,82 +60,164 @@ impl Ghost {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut lines = input.lines();
-    let instructions = lines.next().unwrap().trim();
-    let mut steps = StepGenerator {
-        instructions: instructions.chars().collect(),
-        current: 0,
-        step_count: 0,
-    };
-
-    lines.next();
-
-    let mut nodes = HashMap::new();
-    for line in lines {
-        let line = line.trim();
-        let mut parts = line.split(" = ");
-        let node_name = parts.next().unwrap().trim();
-        let adjacent = parts.next().unwrap().trim();
-        let mut adjacent = adjacent
-            .trim_start_matches('(')
-            .trim_end_matches(')')
-            .split(", ")
-            .map(|s| s.to_string());
-        let left = adjacent.next().unwrap().clone();
-        let right = adjacent.next().unwrap().clone();
-
-        nodes.insert(node_name, Node { left, right });
+// The left/right instructions and the map of nodes
+#[derive(Debug)]
+pub struct Network {
+    steps: StepGenerator,
+    nodes: HashMap<String, Node>,
+}
+
+// Prints the nodes sorted by name, which is how the puzzle examples list them
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let steps = self.steps.instructions.iter().collect::<String>();
+        writeln!(f, "{}", steps)?;
+
+        let mut names = self.nodes.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            let node = &self.nodes[name];
+            write!(f, "\n{} = ({}, {})", name, node.left, node.right)?;
+        }
+        Ok(())
     }
+}
 
-    // find all the nodes where the name ends with 'A' and spawn a ghost for each
-    let mut ghosts: Vec<Ghost> = nodes
-        .keys()
-        .filter(|k| k.ends_with('A'))
-        .map(|k| Ghost::from_node_name(k, &mut steps))
-        .collect();
-
-    // Walk each ghost until each of them reaches a node that ends with 'Z'
-    while !ghosts.iter().all(|g| g.current.ends_with('Z')) {
-        for ghost in ghosts.iter_mut() {
-            if !ghost.current.ends_with('Z') {
-                ghost.take_step(&nodes);
+// A node and the ones to the left and to the right of it
+type Link<'a> = (&'a str, &'a str, &'a str);
+
+// `AAA = (BBB, CCC)`
+fn link(input: &str) -> IResult<'_, Link<'_>> {
+    let adjacent = delimited(
+        token("("),
+        separated_pair(name, ws(token(",")), name),
+        token(")"),
+    );
+    map(
+        separated_pair(name, ws(token("=")), adjacent),
+        |(node, (left, right))| (node, left, right),
+    )(input)
+}
+
+// The steps on the first line, then a node per line after a blank one
+fn network(input: &str) -> IResult<'_, (Vec<char>, Vec<Link<'_>>)> {
+    let steps = map(
+        many_till(symbol("LR", "`L` or `R`"), peek(alt((line_ending, eof)))),
+        |(steps, _)| steps,
+    );
+    separated_pair(steps, multispace1, list1(multispace1, link))(input)
+}
+
+fn parse_network(input: &str) -> Result<(Vec<char>, HashMap<String, Node>), ParseError> {
+    let (instructions, links) = parse_all(input.trim(), network)?;
+
+    // Every node the instructions can lead to has to be on the map
+    let names = links.iter().map(|(name, _, _)| *name).collect::<Vec<_>>();
+    for (_, left, right) in &links {
+        for next in [left, right] {
+            if !names.contains(next) {
+                return Err(ParseError::new(
+                    format!("unknown node `{}`", next),
+                    next,
+                    "not on the map",
+                ));
             }
         }
     }
 
-    // Find the least common multiple of all the ghost's step counts
-    let mut lcm_steps = 1;
-    for ghost in ghosts.iter() {
-        lcm_steps = lcm(lcm_steps, ghost.steps.step_count);
+    let nodes = links
+        .into_iter()
+        .map(|(name, left, right)| {
+            let node = Node {
+                left: left.to_string(),
+                right: right.to_string(),
+            };
+            (name.to_string(), node)
+        })
+        .collect::<HashMap<_, _>>();
+    Ok((instructions, nodes))
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 8;
+    const PART: u8 = 2;
+
+    type Input = Network;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Network, AocError> {
+        let (instructions, nodes) = parse_network(input).map_err(|err| err.with_source(input))?;
+        let steps = StepGenerator {
+            instructions,
+            current: 0,
+            step_count: 0,
+        };
+
+        Ok(Network { steps, nodes })
+    }
+
+    fn solve(network: Network, _params: &Params) -> Result<String, AocError> {
+        let Network { mut steps, nodes } = network;
+
+        // find all the nodes where the name ends with 'A' and spawn a ghost for each
+        let mut ghosts: Vec<Ghost> = nodes
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .map(|k| Ghost::from_node_name(k, &mut steps))
+            .collect();
+
+        // Walk each ghost until each of them reaches a node that ends with 'Z'
+        while !ghosts.iter().all(|g| g.current.ends_with('Z')) {
+            for ghost in ghosts.iter_mut() {
+                if !ghost.current.ends_with('Z') {
+                    ghost.take_step(&nodes);
+                }
+            }
+        }
+
+        // Find the least common multiple of all the ghost's step counts
+        let mut lcm_steps = 1;
+        for ghost in ghosts.iter() {
+            lcm_steps = lcm(lcm_steps, ghost.steps.step_count);
+        }
+
+        Ok(lcm_steps.to_string())
     }
+}
 
-    Ok(lcm_steps.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "LR
+
+                           11A = (11B, XXX)
+                           11B = (XXX, 11Z)
+                           11Z = (11B, XXX)
+                           22A = (22B, XXX)
+                           22B = (22C, 22C)
+                           22C = (22Z, 22Z)
+                           22Z = (22B, 22B)
+                           XXX = (XXX, XXX)";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "LR
-
-                     11A = (11B, XXX)
-                     11B = (XXX, 11Z)
-                     11Z = (11B, XXX)
-                     22A = (22B, XXX)
-                     22B = (22C, 22C)
-                     22C = (22Z, 22Z)
-                     22Z = (22B, 22B)
-                     XXX = (XXX, XXX)";
-        assert_eq!("6", process(input)?);
+        assert_eq!("6", process(EXAMPLE)?);
         Ok(())
     }
-}
 
-// Submissions:
-// - 1000000000 - too low
-// - 10000000000 - too low
-// - 14616363770447 - correct
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let network = Part2::parse(EXAMPLE)?;
+        let lines = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(lines.join("\n"), network.to_string());
+        Ok(())
+    }
+}
@@ -1,42 +1,72 @@
-use itertools::Itertools;
+use aoc_utils::{list1, number, parse_lines, IResult, Params, Solution};
+use nom::character::complete::space1;
 
 use crate::custom_error::AocError;
 
-pub fn extrapolate(sequence: &Vec<i64>) -> i64 {
+pub fn extrapolate(sequence: &[i64]) -> i64 {
     if sequence.iter().all(|&n| n == 0) {
         return 0;
     }
-    let steps = sequence.windows(2).map(|w| w[1] - w[0]).collect();
+    let steps = sequence.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
     sequence.last().unwrap() + extrapolate(&steps)
 }
 
+// `0 3 6 9 12 15`: the history of a single value
+fn sequence(input: &str) -> IResult<'_, Vec<i64>> {
+    list1(space1, number)(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 9;
+    const PART: u8 = 2;
+
+    type Input = Vec<Vec<i64>>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i64>>, AocError> {
+        // The history is extrapolated backwards by reading it from the end
+        let mut sequences = parse_lines(input, sequence)?;
+        sequences.iter_mut().for_each(|sequence| sequence.reverse());
+        Ok(sequences)
+    }
+
+    fn solve(sequences: Vec<Vec<i64>>, _params: &Params) -> Result<String, AocError> {
+        let sum = sequences.iter().map(|s| extrapolate(s)).sum::<i64>();
+        Ok(sum.to_string())
+    }
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut sum = 0;
-    for line in input.lines() {
-        let sequence = line
-            .trim()
-            .split_whitespace()
-            .map(|n| n.parse::<i64>().unwrap())
-            .collect_vec()
-            .into_iter()
-            .rev()
-            .collect_vec();
-        sum += extrapolate(&sequence);
-    }
-    Ok(sum.to_string())
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "0 3 6 9 12 15
+                           1 3 6 10 15 21
+                           10 13 16 21 30 45";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "0 3 6 9 12 15
-                     1 3 6 10 15 21
-                     10 13 16 21 30 45";
-        assert_eq!("2", process(input)?);
+        assert_eq!("2", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let printed = Part2::parse(EXAMPLE)?
+            .iter()
+            .map(|sequence| {
+                let numbers = sequence.iter().rev().map(i64::to_string);
+                numbers.collect::<Vec<_>>().join(" ")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
         Ok(())
     }
 }
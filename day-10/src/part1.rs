@@ -1,9 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, Direction},
-};
+use aoc_utils::{CharMap, Direction, Grid, Params, ParseError, Solution};
+
+use crate::custom_error::AocError;
 
 /*
 The pipes are arranged in a two-dimensional grid of tiles:
@@ -41,17 +40,23 @@ impl Pipe {
             _ => None,
         }
     }
+
+    // Whether the pipe can be entered by moving in the given direction, i.e. it has an
+    // end facing back where we came from
+    fn connects(self, moving: Direction) -> bool {
+        self != Self::Start && neighbours_for(self).contains(&moving.opposite())
+    }
 }
 
-pub fn neighbours_for(c: Pipe) -> Vec<Direction> {
+pub fn neighbours_for(c: Pipe) -> &'static [Direction] {
     match c {
-        Pipe::Horizontal => vec![Direction::West, Direction::East],
-        Pipe::Vertical => vec![Direction::North, Direction::South],
-        Pipe::NEBend => vec![Direction::North, Direction::East],
-        Pipe::NWBend => vec![Direction::North, Direction::West],
-        Pipe::SEBend => vec![Direction::South, Direction::East],
-        Pipe::SWBend => vec![Direction::South, Direction::West],
-        Pipe::Start => vec![
+        Pipe::Horizontal => &[Direction::West, Direction::East],
+        Pipe::Vertical => &[Direction::North, Direction::South],
+        Pipe::NEBend => &[Direction::North, Direction::East],
+        Pipe::NWBend => &[Direction::North, Direction::West],
+        Pipe::SEBend => &[Direction::South, Direction::East],
+        Pipe::SWBend => &[Direction::South, Direction::West],
+        Pipe::Start => &[
             Direction::North,
             Direction::South,
             Direction::West,
@@ -60,71 +65,70 @@ pub fn neighbours_for(c: Pipe) -> Vec<Direction> {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str(input, '.');
-
-    let start = map.find('S').unwrap();
-
-    // A map of a direction (from a current point) to a possible pipe types that can
-    // be connected from that direction.
-    let mut pipe_connections = HashMap::new();
-    pipe_connections.insert(
-        Direction::North,
-        vec![Pipe::Vertical, Pipe::SEBend, Pipe::SWBend],
-    );
-    pipe_connections.insert(
-        Direction::South,
-        vec![Pipe::Vertical, Pipe::NEBend, Pipe::NWBend],
-    );
-    pipe_connections.insert(
-        Direction::West,
-        vec![Pipe::Horizontal, Pipe::NEBend, Pipe::SEBend],
-    );
-    pipe_connections.insert(
-        Direction::East,
-        vec![Pipe::Horizontal, Pipe::NWBend, Pipe::SWBend],
-    );
-
-    let mut visited = HashSet::new();
-    visited.insert(start);
-    let mut current = start;
-    loop {
-        let cell = map.cell_for_point(&current);
-        println!("Current: {:?} => {:?}", current, cell);
-
-        let cell_type = Pipe::from_char(*cell).unwrap();
-        let neighbours = neighbours_for(cell_type);
-
-        let mut possible_directions = Vec::new();
-        for direction in neighbours.iter() {
-            let point = current.neighbour(*direction);
-            if visited.contains(&point) {
-                continue;
-            }
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 10;
+    const PART: u8 = 1;
+
+    type Input = Grid<Option<Pipe>>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Grid<Option<Pipe>>, AocError> {
+        let map = CharMap::parse(input, '.', |c| "|-LJF7.S".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        if map.find('S').is_none() {
+            let err = ParseError::new("no starting position", input, "expected an `S` somewhere");
+            return Err(err.into());
+        }
+        Ok(map.map(Pipe::from_char))
+    }
+
+    fn solve(pipes: Grid<Option<Pipe>>, _params: &Params) -> Result<String, AocError> {
+        let start = pipes.find(Some(Pipe::Start)).unwrap();
+
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        let mut current = start;
+        loop {
+            let cell_type = pipes.cell_for_point(&current).unwrap();
+            tracing::trace!(?current, ?cell_type, "following the loop");
+
+            let neighbours = neighbours_for(cell_type);
 
-            let allowed_connections = pipe_connections.get(&direction).unwrap();
-            let cell = map.cell_for_point(&point);
-            let pipe_type = Pipe::from_char(*cell);
-            if let Some(pipe_type) = pipe_type {
-                if allowed_connections.contains(&pipe_type) {
+            let mut possible_directions = Vec::new();
+            for direction in neighbours.iter() {
+                let point = current.neighbour(*direction);
+                if visited.contains(&point) {
+                    continue;
+                }
+
+                if pipes
+                    .cell_for_point(&point)
+                    .is_some_and(|pipe| pipe.connects(*direction))
+                {
                     possible_directions.push(*direction);
                 }
             }
-        }
 
-        if possible_directions.is_empty() {
-            break;
+            if possible_directions.is_empty() {
+                break;
+            }
+
+            // pick first direction and follow it
+            current = current.neighbour(possible_directions[0]);
+            visited.insert(current);
         }
 
-        // pick first direction and follow it
-        current = current.neighbour(possible_directions[0]);
-        visited.insert(current);
-    }
+        tracing::debug!(visited = visited.len(), "loop closed");
 
-    println!("Visited {} cells", visited.len());
+        Ok((visited.len() / 2).to_string())
+    }
+}
 
-    return Ok((visited.len() / 2).to_string());
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -152,4 +156,13 @@ mod tests {
         assert_eq!("4", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process(".....\n.S-7.\n.|x|.\n"));
+        assert_eq!(miette::SourceSpan::from((14, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process(".F-7.\n"));
+        assert_eq!("no starting position", err.message);
+    }
 }
@@ -1,11 +1,10 @@
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, Direction, Point},
-};
-use std::collections::{HashMap, HashSet};
+use aoc_utils::{interior_points, CharMap, Direction, Grid, Params, ParseError, Solution};
+
+use crate::custom_error::AocError;
+use std::collections::HashSet;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-enum Pipe {
+pub enum Pipe {
     Horizontal, // -
     Vertical,   // |
     LBend,      // L
@@ -28,19 +27,25 @@ impl Pipe {
             _ => None,
         }
     }
+
+    // Whether the pipe can be entered by moving in the given direction, i.e. it has an
+    // end facing back where we came from
+    fn connects(self, moving: Direction) -> bool {
+        self != Self::Start && neighbours_for(self).contains(&moving.opposite())
+    }
 }
 
 // Returns the directions that can be walked from a given pipe type
 // Note: we always walk in a counter-clockwise direction
-fn neighbours_for(c: Pipe) -> Vec<Direction> {
+fn neighbours_for(c: Pipe) -> &'static [Direction] {
     match c {
-        Pipe::Horizontal => vec![Direction::West, Direction::East],
-        Pipe::Vertical => vec![Direction::North, Direction::South],
-        Pipe::LBend => vec![Direction::East, Direction::North],
-        Pipe::JBend => vec![Direction::West, Direction::North],
-        Pipe::FBend => vec![Direction::South, Direction::East],
-        Pipe::SevenBend => vec![Direction::South, Direction::West],
-        Pipe::Start => vec![
+        Pipe::Horizontal => &[Direction::West, Direction::East],
+        Pipe::Vertical => &[Direction::North, Direction::South],
+        Pipe::LBend => &[Direction::East, Direction::North],
+        Pipe::JBend => &[Direction::West, Direction::North],
+        Pipe::FBend => &[Direction::South, Direction::East],
+        Pipe::SevenBend => &[Direction::South, Direction::West],
+        Pipe::Start => &[
             Direction::South,
             Direction::East,
             Direction::North,
@@ -49,103 +54,75 @@ fn neighbours_for(c: Pipe) -> Vec<Direction> {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str(input, '.');
-    let map = map.with_padding(1, 1); // Add padding to ensure that we can walk around the edges
-    let start = map.find('S').unwrap();
-
-    // A map of a direction (from a current point) to a possible pipe types that can
-    // be connected from that direction.
-    let mut pipe_connections = HashMap::new();
-    pipe_connections.insert(
-        Direction::North,
-        vec![Pipe::Vertical, Pipe::FBend, Pipe::SevenBend],
-    );
-    pipe_connections.insert(
-        Direction::South,
-        vec![Pipe::Vertical, Pipe::LBend, Pipe::JBend],
-    );
-    pipe_connections.insert(
-        Direction::West,
-        vec![Pipe::Horizontal, Pipe::LBend, Pipe::FBend],
-    );
-    pipe_connections.insert(
-        Direction::East,
-        vec![Pipe::Horizontal, Pipe::JBend, Pipe::SevenBend],
-    );
-
-    let mut visited = HashSet::new();
-    visited.insert(start);
-
-    let mut current = start;
-    println!("Start: {:?}", start);
-    let mut path = Vec::new();
-
-    map.print();
-
-    loop {
-        // map.print_with_current(current, '*');
-        let current_cell = map.cell_for_point(&current);
-        let current_pipe = Pipe::from_char(*current_cell).unwrap();
-        path.push((current, current_pipe));
-
-        let neighbour_directions = neighbours_for(current_pipe);
-        let mut possible_directions = Vec::new();
-        for direction in neighbour_directions.iter() {
-            let next = current.neighbour(*direction);
-            if visited.contains(&next) {
-                continue;
-            }
+pub struct Part2;
 
-            let allowed_connections = pipe_connections.get(&direction).unwrap();
-            let next_cell = map.cell_for_point(&next);
-            if let Some(next_pipe) = Pipe::from_char(*next_cell) {
-                if allowed_connections.contains(&next_pipe) {
-                    possible_directions.push(*direction);
-                }
-            }
-        }
+impl Solution for Part2 {
+    const DAY: u8 = 10;
+    const PART: u8 = 2;
 
-        if possible_directions.is_empty() {
-            break;
-        }
+    type Input = Grid<Option<Pipe>>;
+    type Error = AocError;
 
-        // pick first direction and follow it
-        current = current.neighbour(possible_directions[0]);
-        visited.insert(current);
+    fn parse(input: &str) -> Result<Grid<Option<Pipe>>, AocError> {
+        let map = CharMap::parse(input, '.', |c| "|-LJF7.S".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        if map.find('S').is_none() {
+            let err = ParseError::new("no starting position", input, "expected an `S` somewhere");
+            return Err(err.into());
+        }
+        Ok(map.map(Pipe::from_char))
     }
 
-    // Create a new map with the visited points and flood fill it
-    // (the map and points are offset by 1 to allow the flood fill to work around all the edges)
-    println!("Visited map:");
-    let mut fill_map = CharMap::from_dimensions(map.width(), map.height(), '.');
-    for point in visited.iter() {
-        fill_map.set_cell_for_point(&point, *map.cell_for_point(&point));
-    }
-    fill_map.print();
-    fill_map.flood_fill(Point::new(0, 0), 'O');
-
-    for y in 0..fill_map.height() - 1 {
-        let mut outside = true;
-        for x in 0..fill_map.width() - 1 {
-            let cell = fill_map.cell(x as i32, y as i32);
-            if *cell == '.' {
-                if outside {
-                    fill_map.set_cell(x, y, 'O');
-                } else {
-                    fill_map.set_cell(x, y, 'I');
+    fn solve(pipes: Grid<Option<Pipe>>, _params: &Params) -> Result<String, AocError> {
+        let start = pipes.find(Some(Pipe::Start)).unwrap();
+
+        let mut visited = HashSet::new();
+        visited.insert(start);
+
+        let mut current = start;
+        let mut path = Vec::new();
+
+        loop {
+            // map.print_with_current(current, '*');
+            let current_pipe = pipes.cell_for_point(&current).unwrap();
+            path.push((current, current_pipe));
+
+            let neighbour_directions = neighbours_for(current_pipe);
+            let mut possible_directions = Vec::new();
+            for direction in neighbour_directions.iter() {
+                let next = current.neighbour(*direction);
+                if visited.contains(&next) {
+                    continue;
                 }
-            } else if *cell == '|' || *cell == 'L' || *cell == 'J' {
-                outside = !outside;
+
+                if pipes
+                    .cell_for_point(&next)
+                    .is_some_and(|pipe| pipe.connects(*direction))
+                {
+                    possible_directions.push(*direction);
+                }
+            }
+
+            if possible_directions.is_empty() {
+                break;
             }
+
+            // pick first direction and follow it
+            current = current.neighbour(possible_directions[0]);
+            visited.insert(current);
         }
-    }
 
-    fill_map.print();
+        // The loop is a lattice polygon with a vertex in every pipe, so the enclosed tiles
+        // are the lattice points strictly inside it
+        let vertices = path.iter().map(|(point, _)| *point).collect::<Vec<_>>();
+        let internal = interior_points(&vertices);
+        Ok(internal.to_string())
+    }
+}
 
-    let internal = fill_map.count('I');
-    return Ok(internal.to_string());
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -225,8 +202,3 @@ mod tests {
         Ok(())
     }
 }
-
-// Submissions:
-// - 393 - too high
-// - 273 - too high
-// - 265 - correct
@@ -1,16 +1,29 @@
-use day_11::*;
+use aoc_utils::InputLoader;
+use divan::counter::ItemsCount;
+
+fn input(part: u8) -> String {
+    InputLoader::new(11, part)
+        .dir(env!("CARGO_MANIFEST_DIR"))
+        .load()
+        .unwrap()
+        .text
+}
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",))).unwrap();
-}
+aoc_utils::bench_part!(part1, day_11::part1::Part1);
+aoc_utils::bench_part!(part2, day_11::part2::Part2);
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",)), 1000000).unwrap();
+// Expanding the universe only scales the distances, so the time should not depend on the
+// factor: with the galaxies counted, the galaxies per second should stay the same
+#[divan::bench(consts = [2, 10, 1000000])]
+fn part2_expansion<const EXPANSION_FACTOR: usize>(bencher: divan::Bencher) {
+    let input = input(2);
+    let galaxies = input.chars().filter(|c| *c == '#').count();
+    bencher.counter(ItemsCount::new(galaxies)).bench(|| {
+        day_11::part2::process(divan::black_box(&input), EXPANSION_FACTOR).unwrap();
+    });
 }
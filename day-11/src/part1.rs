@@ -1,4 +1,6 @@
-use crate::{custom_error::AocError, utils::CharMap};
+use aoc_utils::{CharMap, Params, Solution};
+
+use crate::custom_error::AocError;
 
 // Doubles the size of each empty row and column in the map
 fn expand_map(map: &CharMap) -> CharMap {
@@ -17,7 +19,7 @@ fn expand_map(map: &CharMap) -> CharMap {
     }
 
     let mut new_map: Vec<Vec<char>> = vec![];
-    for (y, line) in map.lines().enumerate() {
+    for line in map.lines() {
         let mut new_line = vec![];
 
         let mut empty = true;
@@ -43,28 +45,44 @@ fn expand_map(map: &CharMap) -> CharMap {
     let mut new_char_map = CharMap::from_dimensions(new_width, new_map.len(), '.');
     new_char_map.copy_from_vec(&new_map);
 
-    return new_char_map;
+    new_char_map
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str(input, '.');
-    let map = expand_map(&map);
-    map.print();
-
-    let galaxies = map.find_all('#');
-    let mut sum = 0;
-    for i in 0..galaxies.len() {
-        let src = galaxies[i];
-        for j in i + 1..galaxies.len() {
-            let dst = galaxies[j];
-            let distance = src.manhattan_distance(&dst);
-            println!("Distance from {:?} to {:?} is {}", src, dst, distance);
-            sum += distance;
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 11;
+    const PART: u8 = 1;
+
+    type Input = CharMap;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<CharMap, AocError> {
+        Ok(CharMap::parse(input, '.', |c| c == '.' || c == '#')
+            .map_err(|err| err.with_source(input))?)
+    }
+
+    fn solve(map: CharMap, _params: &Params) -> Result<String, AocError> {
+        let map = expand_map(&map);
+        tracing::trace!(%map, "expanded");
+
+        let galaxies = map.find_all('#');
+        let mut sum = 0;
+        for (i, &src) in galaxies.iter().enumerate() {
+            for &dst in &galaxies[i + 1..] {
+                let distance = src.manhattan_distance(&dst);
+                tracing::trace!(?src, ?dst, distance, "distance between galaxies");
+                sum += distance;
+            }
         }
+
+        Ok(sum.to_string())
     }
+}
 
-    Ok(sum.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -86,4 +104,10 @@ mod tests {
         assert_eq!("374", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("...#\n.*..\n"));
+        assert_eq!(miette::SourceSpan::from((6, 1)), err.span);
+    }
 }
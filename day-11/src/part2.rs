@@ -1,5 +1,5 @@
 use crate::custom_error::AocError;
-use crate::utils::{CharMap, Point};
+use aoc_utils::{CharMap, Param, Params, Point, Solution};
 use std::vec;
 
 // Finds coordinates of all empty rows and columns
@@ -25,40 +25,66 @@ fn find_expansions(map: &CharMap) -> (Vec<usize>, Vec<usize>) {
         }
     }
 
-    return (empty_columns, empty_rows);
+    (empty_columns, empty_rows)
 }
 
-#[tracing::instrument]
-pub fn process(input: &str, expansion_factor: usize) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str(input, '.');
-    let galaxies = map.find_all('#');
+pub struct Part2;
 
-    let (empty_cols, empty_rows) = find_expansions(&map);
-    let mut expanded_galaxies = vec![];
+impl Solution for Part2 {
+    const DAY: u8 = 11;
+    const PART: u8 = 2;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "expansion_factor",
+        description: "how many times larger each empty row and column becomes",
+        default: "1000000",
+    }];
 
-    for galaxy in galaxies {
-        let empty_to_left = empty_cols.iter().filter(|x| **x < galaxy.x as usize);
-        let empty_to_top = empty_rows.iter().filter(|y| **y < galaxy.y as usize);
+    type Input = CharMap;
+    type Error = AocError;
 
-        let expansion_size_x = empty_to_left.clone().count() * (expansion_factor - 1);
-        let expansion_size_y = empty_to_top.clone().count() * (expansion_factor - 1);
+    fn parse(input: &str) -> Result<CharMap, AocError> {
+        Ok(CharMap::parse(input, '.', |c| c == '.' || c == '#')
+            .map_err(|err| err.with_source(input))?)
+    }
 
-        let expanded_x = galaxy.x as usize + expansion_size_x;
-        let expanded_y = galaxy.y as usize + expansion_size_y;
+    fn solve(map: CharMap, params: &Params) -> Result<String, AocError> {
+        let expansion_factor = params.get::<usize>("expansion_factor")?;
 
-        expanded_galaxies.push(Point::new(expanded_x as i64, expanded_y as i64));
-    }
+        let galaxies = map.find_all('#');
+
+        let (empty_cols, empty_rows) = find_expansions(&map);
+        let mut expanded_galaxies = vec![];
+
+        for galaxy in galaxies {
+            let empty_to_left = empty_cols.iter().filter(|x| **x < galaxy.x as usize);
+            let empty_to_top = empty_rows.iter().filter(|y| **y < galaxy.y as usize);
+
+            let expansion_size_x = empty_to_left.clone().count() * (expansion_factor - 1);
+            let expansion_size_y = empty_to_top.clone().count() * (expansion_factor - 1);
+
+            let expanded_x = galaxy.x as usize + expansion_size_x;
+            let expanded_y = galaxy.y as usize + expansion_size_y;
 
-    let mut sum = 0;
-    for i in 0..expanded_galaxies.len() {
-        let src = expanded_galaxies[i];
-        for j in i + 1..expanded_galaxies.len() {
-            let dst = expanded_galaxies[j];
-            sum += src.manhattan_distance(&dst);
+            expanded_galaxies.push(Point::new(expanded_x as i64, expanded_y as i64));
         }
+
+        let mut sum = 0;
+        for (i, &src) in expanded_galaxies.iter().enumerate() {
+            for &dst in &expanded_galaxies[i + 1..] {
+                sum += src.manhattan_distance(&dst);
+            }
+        }
+
+        Ok(sum.to_string())
     }
+}
 
-    Ok(sum.to_string())
+#[tracing::instrument]
+pub fn process(input: &str, expansion_factor: usize) -> miette::Result<String, AocError> {
+    Part2::run(
+        input,
+        &Params::new().with("expansion_factor", expansion_factor),
+    )
 }
 
 #[cfg(test)]
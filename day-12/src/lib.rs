@@ -2,4 +2,3 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
-pub mod utils;
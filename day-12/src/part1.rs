@@ -1,4 +1,10 @@
-use itertools::Itertools;
+use aoc_utils::{list1, number, parse_lines, symbol, token, IResult, NomError, Params, Solution};
+use nom::{
+    character::complete::space1,
+    combinator::{peek, recognize},
+    multi::many_till,
+    sequence::preceded,
+};
 
 use crate::custom_error::AocError;
 
@@ -53,9 +59,9 @@ impl MaskGenerator {
         let mut result = vec![false; self.mask_len];
         let mut bit_counter = self.bit_counter;
 
-        for i in 0..self.mask_len {
+        for (i, bit) in result.iter_mut().enumerate() {
             if self.mask & (1 << i) != 0 {
-                result[i] = bit_counter % 2 == 1;
+                *bit = bit_counter % 2 == 1;
                 bit_counter /= 2;
             }
         }
@@ -64,7 +70,7 @@ impl MaskGenerator {
     }
 }
 
-fn count_arrangements(records: &str, bad_records: &Vec<u8>) -> u64 {
+fn count_arrangements(records: &str, bad_records: &[u8]) -> u64 {
     let record_chars = records.chars().collect::<Vec<_>>();
 
     let mut count = 0;
@@ -86,7 +92,7 @@ fn count_arrangements(records: &str, bad_records: &Vec<u8>) -> u64 {
         }
 
         if check_bad_records(&result, bad_records) {
-            println!("result: {}", result.iter().collect::<String>());
+            tracing::trace!(result = %result.iter().collect::<String>(), "arrangement");
             count += 1;
         }
     }
@@ -94,31 +100,25 @@ fn count_arrangements(records: &str, bad_records: &Vec<u8>) -> u64 {
     count
 }
 
-fn check_bad_records(result: &[char], expected: &Vec<u8>) -> bool {
-    // println!("expected: {:?}", expected);
+fn check_bad_records(result: &[char], expected: &[u8]) -> bool {
     let mut bad_record_idx = 0;
     let bad_record_count = expected.len();
     let mut count = 0;
     for c in result {
-        // println!("c: {}", c);
         if *c == '#' {
             count += 1;
             if bad_record_idx >= bad_record_count {
-                // println!("bad_record_idx >= bad_record_count");
                 return false;
             }
 
             if count > expected[bad_record_idx] {
-                // println!("count > expected[bad_record_idx]");
                 return false;
             }
             continue;
         }
 
         if count > 0 {
-            // println!("count: {}, expected: {}", count, expected[bad_record_idx]);
             if count != expected[bad_record_idx] {
-                // println!("count != expected[bad_record_idx]");
                 return false;
             }
 
@@ -128,9 +128,7 @@ fn check_bad_records(result: &[char], expected: &Vec<u8>) -> bool {
     }
 
     if count > 0 {
-        // println!("count: {}, expected: {}", count, expected[bad_record_idx]);
         if count != expected[bad_record_idx] {
-            // println!("count != expected[bad_record_idx]");
             return false;
         }
 
@@ -138,26 +136,79 @@ fn check_bad_records(result: &[char], expected: &Vec<u8>) -> bool {
     }
 
     if bad_record_idx != bad_record_count {
-        // println!("bad_record_idx != bad_record_count");
         return false;
     }
 
     true
 }
 
+// A row of springs and the sizes of the groups of damaged ones in it
+#[derive(Debug)]
+pub struct Row {
+    records: String,
+    bad_groups: Vec<u8>,
+}
+
+impl std::fmt::Display for Row {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let groups = self.bad_groups.iter().map(|g| g.to_string());
+        write!(
+            f,
+            "{} {}",
+            self.records,
+            groups.collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+// `???.### 1,1,3`
+fn row(input: &str) -> IResult<'_, Row> {
+    let spring = symbol(".#?", "`.`, `#` or `?`");
+    let (input, records) = recognize(many_till(spring, peek(space1)))(input)?;
+    // Every unknown spring doubles the arrangements to try, so keep them countable
+    if records.len() > 64 {
+        let err = NomError::at(records, "at most 64 springs in a row");
+        return Err(nom::Err::Failure(err));
+    }
+    let (rest, bad_groups) = preceded(space1, list1(token(","), number))(input)?;
+    let row = Row {
+        records: records.to_string(),
+        bad_groups,
+    };
+    Ok((rest, row))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 12;
+    const PART: u8 = 1;
+
+    type Input = Vec<Row>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<Row>, AocError> {
+        Ok(parse_lines(input, row)?)
+    }
+
+    fn solve(rows: Vec<Row>, _params: &Params) -> Result<String, AocError> {
+        let mut total = 0;
+        for Row {
+            records,
+            bad_groups,
+        } in rows
+        {
+            let arrangements = count_arrangements(&records, &bad_groups);
+            tracing::debug!(records, ?bad_groups, arrangements, "row");
+            total += arrangements;
+        }
+        Ok(total.to_string())
+    }
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut total = 0;
-    for line in input.lines() {
-        let line = line.trim();
-        let chunks = line.split_whitespace().collect_vec();
-        let records = chunks[0];
-        let bad_groups = chunks[1].split(',').map(|s| s.parse().unwrap()).collect();
-        let arrangements = count_arrangements(records, &bad_groups);
-        println!("{} => {}", line, arrangements);
-        total += arrangements;
-    }
-    Ok(total.to_string())
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -176,20 +227,17 @@ mod tests {
 
     #[test]
     fn test_check_bad_records() {
-        assert_eq!(
-            true,
-            check_bad_records(
-                &"#####..#..##.#....##".chars().collect::<Vec<_>>(),
-                &vec![5, 1, 2, 1, 5]
-            )
-        );
+        assert!(check_bad_records(
+            &"#####..#..##.#....##".chars().collect::<Vec<_>>(),
+            &[5, 1, 2, 1, 5]
+        ));
     }
 
     #[test]
     fn test_count_arrangements() {
         assert_eq!(
             37,
-            count_arrangements("#.#?????..??????#?", &vec![1, 1, 1, 1, 1, 3])
+            count_arrangements("#.#?????..??????#?", &[1, 1, 1, 1, 1, 3])
         );
         // assert_eq!(4, count_arrangements(".??..??...?##.", &vec![1, 1, 3]));
         // assert_eq!(1, count_arrangements("?#?#?#?#?#?#?#?", &vec![1, 3, 1, 6]));
@@ -198,18 +246,24 @@ mod tests {
         // assert_eq!(10, count_arrangements("?###????????", &vec![3, 2, 1]));
     }
 
+    const EXAMPLE: &str = "???.### 1,1,3
+                           .??..??...?##. 1,1,3
+                           ?#?#?#?#?#?#?#? 1,3,1,6
+                           ????.#...#... 4,1,1
+                           ????.######..#####. 1,6,5
+                           ?###???????? 3,2,1";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "???.### 1,1,3
-                     .??..??...?##. 1,1,3
-                     ?#?#?#?#?#?#?#? 1,3,1,6
-                     ????.#...#... 4,1,1
-                     ????.######..#####. 1,6,5
-                     ?###???????? 3,2,1";
-        assert_eq!("21", process(input)?);
+        assert_eq!("21", process(EXAMPLE)?);
         Ok(())
     }
-}
 
-// Submissions:
-// 7716 - correct
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let rows = Part1::parse(EXAMPLE)?;
+        let printed = rows.iter().map(Row::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
+        Ok(())
+    }
+}
@@ -1,103 +1,61 @@
-use fxhash::{self, hash64};
-use itertools::Itertools;
-use std::collections::HashMap;
+use aoc_utils::{list1, number, parse_lines, symbol, token, IResult, Params, Solution};
+use nom::{
+    character::complete::space1,
+    combinator::{peek, recognize},
+    multi::many_till,
+    sequence::preceded,
+};
 
 use crate::custom_error::AocError;
 
-struct Cache {
-    results: HashMap<u64, u64>,
-}
-
-impl Cache {
-    fn new() -> Self {
-        Self {
-            results: HashMap::new(),
-        }
-    }
-
-    fn hash(spring_map: &str, bad_records: &[usize]) -> u64 {
-        hash64(spring_map).wrapping_add(hash64(bad_records))
-    }
-
-    fn insert(&mut self, spring_map: &str, bad_records: &[usize], result: u64) -> u64 {
-        self.results
-            .insert(Self::hash(spring_map, bad_records), result);
-        result
-    }
-
-    fn get(&self, spring_map: &str, bad_records: &[usize]) -> Option<&u64> {
-        self.results.get(&Self::hash(spring_map, bad_records))
-    }
-}
-
-fn solve(spring_map: &str, bad_records: &[usize], cache: &mut Cache) -> u64 {
-    if let Some(result) = cache.get(spring_map, bad_records) {
-        return *result;
-    }
-
-    // We ran out of records, so we are done. Just need to check if all remaining items are empty.
-    if bad_records.is_empty() {
-        let all_spaces = spring_map.chars().all(|c| c == '?' || c == '.');
-        return cache.insert(spring_map, bad_records, if all_spaces { 1 } else { 0 });
-    }
-
-    // Skip to the next non-empty character and abort of there are none (since we still have records)
-    let start_pos = spring_map.chars().position(|c| c != '.');
-    if start_pos.is_none() {
-        return cache.insert(spring_map, bad_records, 0);
-    }
-
-    // Shift the start of the spring map to the first non-empty symbol
-    let start_pos = start_pos.unwrap();
-    let spring_map = &spring_map[start_pos..];
-
-    // Check if we have enough space left to solve the problem
-    let min_solution_len = bad_records.iter().sum::<usize>() + bad_records.len() - 1;
-    if spring_map.len() < min_solution_len {
-        return cache.insert(spring_map, bad_records, 0);
-    }
-
-    // Check if we can fit the first record into the beginning of the map
-    // If we can, continue trying to find a solution down that path
-    let record = bad_records[0];
-    let mut skip_chars = record;
-    let mut record_fits = can_fit_record(&spring_map, record);
-
-    // If we want to continue, we need to make sure it is possible to get a space in
-    if record_fits && bad_records.len() > 1 {
-        let next_char = spring_map[skip_chars..].chars().next().unwrap();
-        if next_char != '.' && next_char != '?' {
-            record_fits = false;
+// Counts the ways the unknown springs can be filled in to match the groups of damaged springs.
+// Walks the map one spring at a time, keeping a table of how many arrangements of the springs
+// seen so far have completed a given number of groups and end with a run of damaged springs of
+// a given length. Every state is an exact (position, group, run) triple, so nothing is hashed.
+fn solve(spring_map: &str, bad_records: &[usize]) -> u64 {
+    let runs = bad_records.iter().max().copied().unwrap_or(0) + 1;
+    let state = |group: usize, run: usize| group * runs + run;
+
+    let mut ways = vec![0u64; (bad_records.len() + 1) * runs];
+    ways[state(0, 0)] = 1;
+
+    for c in spring_map.chars() {
+        let mut next = vec![0u64; ways.len()];
+        for group in 0..=bad_records.len() {
+            for run in 0..runs {
+                let count = ways[state(group, run)];
+                if count == 0 {
+                    continue;
+                }
+
+                // An operational spring either continues a gap or closes a finished group
+                if c == '.' || c == '?' {
+                    if run == 0 {
+                        next[state(group, 0)] += count;
+                    } else if run == bad_records[group] {
+                        next[state(group + 1, 0)] += count;
+                    }
+                }
+
+                // A damaged spring extends the current group if it is not full yet
+                if (c == '#' || c == '?') && group < bad_records.len() && run < bad_records[group] {
+                    next[state(group, run + 1)] += count;
+                }
+            }
         }
-        skip_chars += 1; // account for the space
+        ways = next;
     }
 
-    let mut solution = 0;
-
-    // If the first record fits, go deeper
-    if record_fits {
-        solution = solve(&spring_map[skip_chars..], &bad_records[1..], cache)
+    // At the end, either all groups are closed or the last one has just been finished
+    let closed = ways[state(bad_records.len(), 0)];
+    let finishing = match bad_records.last() {
+        Some(&last) => ways[state(bad_records.len() - 1, last)],
+        None => 0,
     };
-
-    // Only attempt solution at the next position if it starts with an empty character
-    if !spring_map.is_empty() {
-        let next_char = spring_map.chars().next().unwrap();
-        if next_char == '.' || next_char == '?' {
-            solution = solution + solve(&spring_map[1..], bad_records, cache)
-        }
-    }
-
-    cache.insert(spring_map, bad_records, solution)
-}
-
-// Receives a slice of a string from the spring map and checks if the slice can
-// fit an uninterrupted group of broken springs
-fn can_fit_record(spring_map: &str, record_len: usize) -> bool {
-    spring_map[0..record_len]
-        .chars()
-        .all(|c| c == '#' || c == '?')
+    closed + finishing
 }
 
+#[tracing::instrument(level = "trace", skip(og_bad_records))]
 fn count_arrangements(records: &str, og_bad_records: &Vec<usize>) -> u64 {
     let records = format!(
         "{}?{}?{}?{}?{}",
@@ -110,20 +68,70 @@ fn count_arrangements(records: &str, og_bad_records: &Vec<usize>) -> u64 {
     bad_records.extend(og_bad_records);
     bad_records.extend(og_bad_records);
 
-    let mut cache = Cache::new();
-    solve(&records, &bad_records, &mut cache)
+    solve(&records, &bad_records)
+}
+
+// A row of springs and the sizes of the groups of damaged ones in it
+#[derive(Debug)]
+pub struct Row {
+    records: String,
+    bad_groups: Vec<usize>,
+}
+
+impl std::fmt::Display for Row {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let groups = self.bad_groups.iter().map(|g| g.to_string());
+        write!(
+            f,
+            "{} {}",
+            self.records,
+            groups.collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+// `???.### 1,1,3`
+fn row(input: &str) -> IResult<'_, Row> {
+    let spring = symbol(".#?", "`.`, `#` or `?`");
+    let (input, records) = recognize(many_till(spring, peek(space1)))(input)?;
+    let (rest, bad_groups) = preceded(space1, list1(token(","), number))(input)?;
+    let row = Row {
+        records: records.to_string(),
+        bad_groups,
+    };
+    Ok((rest, row))
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 12;
+    const PART: u8 = 2;
+
+    type Input = Vec<Row>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<Row>, AocError> {
+        Ok(parse_lines(input, row)?)
+    }
+
+    fn solve(rows: Vec<Row>, _params: &Params) -> Result<String, AocError> {
+        let mut total = 0;
+        for Row {
+            records,
+            bad_groups,
+        } in rows
+        {
+            let arrangements = count_arrangements(&records, &bad_groups);
+            total += arrangements;
+        }
+        Ok(total.to_string())
+    }
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut total = 0;
-    for line in input.lines() {
-        let chunks = line.trim().split_whitespace().collect_vec();
-        let records = chunks[0];
-        let bad_groups = chunks[1].split(',').map(|s| s.parse().unwrap()).collect();
-        total += count_arrangements(records, &bad_groups);
-    }
-    Ok(total.to_string())
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -132,13 +140,15 @@ mod tests {
 
     #[test]
     fn test_solve() {
-        let mut cache = Cache::new();
-        assert_eq!(1, solve("???.###", &vec![1, 1, 3], &mut cache));
-        assert_eq!(4, solve(".??..??...?##.", &vec![1, 1, 3], &mut cache));
-        assert_eq!(1, solve("?#?#?#?#?#?#?#?", &vec![1, 3, 1, 6], &mut cache));
-        assert_eq!(1, solve("????.#...#...", &vec![4, 1, 1], &mut cache));
-        assert_eq!(4, solve("????.######..#####.", &vec![1, 6, 5], &mut cache));
-        assert_eq!(10, solve("?###????????", &vec![3, 2, 1], &mut cache));
+        assert_eq!(1, solve("???.###", &[1, 1, 3]));
+        assert_eq!(4, solve(".??..??...?##.", &[1, 1, 3]));
+        assert_eq!(1, solve("?#?#?#?#?#?#?#?", &[1, 3, 1, 6]));
+        assert_eq!(1, solve("????.#...#...", &[4, 1, 1]));
+        assert_eq!(4, solve("????.######..#####.", &[1, 6, 5]));
+        assert_eq!(10, solve("?###????????", &[3, 2, 1]));
+        assert_eq!(1, solve("...", &[]));
+        assert_eq!(0, solve(".#.", &[]));
+        assert_eq!(0, solve("###", &[2]));
     }
 
     #[test]
@@ -154,15 +164,33 @@ mod tests {
         assert_eq!(506250, count_arrangements("?###????????", &vec![3, 2, 1]));
     }
 
+    const EXAMPLE: &str = "???.### 1,1,3
+                           .??..??...?##. 1,1,3
+                           ?#?#?#?#?#?#?#? 1,3,1,6
+                           ????.#...#... 4,1,1
+                           ????.######..#####. 1,6,5
+                           ?###???????? 3,2,1";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "???.### 1,1,3
-                     .??..??...?##. 1,1,3
-                     ?#?#?#?#?#?#?#? 1,3,1,6
-                     ????.#...#... 4,1,1
-                     ????.######..#####. 1,6,5
-                     ?###???????? 3,2,1";
-        assert_eq!("525152", process(input)?);
+        assert_eq!("525152", process(EXAMPLE)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let rows = Part2::parse(EXAMPLE)?;
+        let printed = rows.iter().map(Row::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("???.### 1,1,3\n.??..??...?##. 1,x\n"));
+        assert_eq!(miette::SourceSpan::from((31, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("?#!# 1,1\n"));
+        assert_eq!(miette::SourceSpan::from((2, 1)), err.span);
+    }
 }
@@ -1,5 +1,5 @@
-
-use miette::Diagnostic;
+use aoc_utils::{ParamError, ParseError};
+use miette::{Diagnostic, SourceSpan};
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
@@ -7,4 +7,30 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParamError(#[from] ParamError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] ParseError),
+
+    #[error("no mirror found in the pattern")]
+    #[diagnostic(code(aoc::no_mirror))]
+    NoMirror {
+        #[source_code]
+        input: String,
+        #[label("no row or column reflects this pattern")]
+        span: SourceSpan,
+    },
+
+    #[error("no smudge found in the pattern")]
+    #[diagnostic(code(aoc::no_smudge))]
+    NoSmudge {
+        #[source_code]
+        input: String,
+        #[label("fixing any single cell doesn't reveal a different mirror")]
+        span: SourceSpan,
+    },
+}
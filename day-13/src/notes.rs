@@ -0,0 +1,55 @@
+use miette::SourceSpan;
+
+use aoc_utils::{blocks, CharMap};
+
+use crate::custom_error::AocError;
+
+// A pattern of ash and rocks, along with where it is in the notes
+pub struct Pattern {
+    pub map: CharMap,
+    pub span: SourceSpan,
+}
+
+impl Pattern {
+    pub fn no_mirror(&self, input: &str) -> AocError {
+        AocError::NoMirror {
+            input: input.to_string(),
+            span: self.span,
+        }
+    }
+
+    pub fn no_smudge(&self, input: &str) -> AocError {
+        AocError::NoSmudge {
+            input: input.to_string(),
+            span: self.span,
+        }
+    }
+}
+
+// The patterns in the notes, keeping the input around so errors can point at them
+pub struct Notes {
+    pub input: String,
+    pub patterns: Vec<Pattern>,
+}
+
+impl Notes {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let patterns = blocks(input)
+            .into_iter()
+            .map(|block| {
+                let map = CharMap::parse(block, ' ', |c| c == '.' || c == '#')
+                    .map_err(|err| err.with_source(input))?;
+                let offset = block.as_ptr() as usize - input.as_ptr() as usize;
+                Ok(Pattern {
+                    map,
+                    span: (offset, block.len()).into(),
+                })
+            })
+            .collect::<Result<_, AocError>>()?;
+
+        Ok(Self {
+            input: input.to_string(),
+            patterns,
+        })
+    }
+}
@@ -2,38 +2,41 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, CharRow},
-};
+use aoc_utils::{CharMap, CharRow, Params, Solution};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut maps = vec![];
-    let mut map_lines = vec![];
-    for line in input.lines() {
-        if line.is_empty() {
-            let map = CharMap::from_iter(map_lines.iter(), ' ');
-            maps.push(map);
-            map_lines = vec![]
-        } else {
-            map_lines.push(line)
-        }
+use crate::{custom_error::AocError, notes::Notes};
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 13;
+    const PART: u8 = 1;
+
+    type Input = Notes;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Notes, AocError> {
+        Notes::parse(input)
     }
 
-    if !map_lines.is_empty() {
-        let map = CharMap::from_iter(map_lines.iter(), ' ');
-        maps.push(map);
+    fn solve(notes: Notes, _params: &Params) -> Result<String, AocError> {
+        let mut result = 0;
+        for pattern in &notes.patterns {
+            result += process_map(&pattern.map).ok_or_else(|| pattern.no_mirror(&notes.input))?;
+        }
+        Ok(result.to_string())
     }
+}
 
-    let result = maps.iter().map(|m| process_map(&m)).sum::<usize>();
-    Ok(result.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 fn process_map_internal(map: &CharMap) -> HashSet<usize> {
     let mirrors_per_row = map
         .lines()
-        .map(|l| potential_mirror_positions(l))
+        .map(|l| potential_mirror_positions(&l))
         .collect_vec();
 
     let mut iter = mirrors_per_row.iter();
@@ -43,7 +46,7 @@ fn process_map_internal(map: &CharMap) -> HashSet<usize> {
     })
 }
 
-fn process_map(map: &CharMap) -> usize {
+fn process_map(map: &CharMap) -> Option<usize> {
     let mut result = process_map_internal(map);
     let mut result_multiplier = 1;
 
@@ -51,13 +54,9 @@ fn process_map(map: &CharMap) -> usize {
         let map = map.transpose();
         result_multiplier = 100;
         result = process_map_internal(&map);
-        if result.is_empty() {
-            map.print();
-            panic!("No mirrors found");
-        }
     }
 
-    *result.iter().next().unwrap() * result_multiplier
+    result.iter().next().map(|pos| pos * result_multiplier)
 }
 
 fn potential_mirror_positions(line: &CharRow) -> HashSet<usize> {
@@ -74,7 +73,7 @@ fn is_mirror_at(mirror_pos: usize, line: &CharRow) -> bool {
     let mirror_pos = mirror_pos as i64;
     let row_len = line.len();
     let range_size = (row_len as f64 / 2.0).ceil() as i64;
-    let left_range_start = mirror_pos - range_size as i64;
+    let left_range_start = mirror_pos - range_size;
     let left_range = left_range_start..mirror_pos;
 
     for left_pos in left_range {
@@ -94,10 +93,10 @@ mod tests {
 
     #[test]
     fn test_is_mirror_at() {
-        assert_eq!(true, is_mirror_at(1, &CharRow::from_str("##", ' ')));
-        assert_eq!(true, is_mirror_at(2, &CharRow::from_str(".##.", ' ')));
-        assert_eq!(true, is_mirror_at(3, &CharRow::from_str("..##.", ' ')));
-        assert_eq!(true, is_mirror_at(5, &CharRow::from_str("#.##..##", ' ')));
+        assert!(is_mirror_at(1, &CharMap::from_str("##", ' ').line(0)));
+        assert!(is_mirror_at(2, &CharMap::from_str(".##.", ' ').line(0)));
+        assert!(is_mirror_at(3, &CharMap::from_str("..##.", ' ').line(0)));
+        assert!(is_mirror_at(5, &CharMap::from_str("#.##..##", ' ').line(0)));
     }
 
     #[test]
@@ -110,7 +109,7 @@ mod tests {
                      ..##..###
                      #....#..#";
         let map = CharMap::from_iter(input.lines().map(|l| l.trim()), ' ');
-        assert_eq!(400, process_map(&map))
+        assert_eq!(Some(400), process_map(&map))
     }
 
     #[test]
@@ -123,7 +122,7 @@ mod tests {
                      ..##..##.
                      #.#.##.#.";
         let map = CharMap::from_iter(input.lines().map(|l| l.trim()), ' ');
-        assert_eq!(5, process_map(&map))
+        assert_eq!(Some(5), process_map(&map))
     }
 
     #[test]
@@ -165,10 +164,20 @@ mod tests {
                      ####..#
                      ####..#";
         let map = CharMap::from_iter(input.lines().map(|l| l.trim()), ' ');
-        assert_eq!(1400, process_map(&map))
+        assert_eq!(Some(1400), process_map(&map))
     }
-}
 
-// Submissions:
-// 11344 - too low
-// 31956 - correct
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("#.#\n..#\n\n\n#?.\n"));
+        assert_eq!(miette::SourceSpan::from((11, 1)), err.span);
+    }
+
+    #[test]
+    fn test_no_mirror() {
+        let Err(AocError::NoMirror { span, .. }) = process("#.#\n#.#\n\n#.\n..\n") else {
+            panic!("expected a missing mirror");
+        };
+        assert_eq!(miette::SourceSpan::from((9, 5)), span);
+    }
+}
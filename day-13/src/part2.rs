@@ -1,43 +1,47 @@
-use core::panic;
 use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, CharRow},
-};
+use aoc_utils::{CharMap, CharRow, Params, Solution};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut maps = vec![];
-    let mut map_lines = vec![];
-    for line in input.lines() {
-        if line.is_empty() {
-            let map = CharMap::from_iter(map_lines.iter(), ' ');
-            maps.push(map);
-            map_lines = vec![]
-        } else {
-            map_lines.push(line)
-        }
+use crate::{custom_error::AocError, notes::Notes};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 13;
+    const PART: u8 = 2;
+
+    type Input = Notes;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Notes, AocError> {
+        Notes::parse(input)
     }
 
-    if !map_lines.is_empty() {
-        let map = CharMap::from_iter(map_lines.iter(), ' ');
-        maps.push(map);
+    fn solve(notes: Notes, _params: &Params) -> Result<String, AocError> {
+        let Notes {
+            input,
+            mut patterns,
+        } = notes;
+        let mut result = 0;
+        for pattern in &mut patterns {
+            result += process_map_with_smudge(&mut pattern.map)
+                .ok_or_else(|| pattern.no_smudge(&input))?;
+        }
+        Ok(result.to_string())
     }
+}
 
-    let result = maps
-        .iter_mut()
-        .map(|m| process_map_with_smudge(m))
-        .sum::<usize>();
-    Ok(result.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 fn process_map_internal(map: &CharMap) -> HashSet<usize> {
     let mirrors_per_row = map
         .lines()
-        .map(|l| potential_mirror_positions(l))
+        .map(|l| potential_mirror_positions(&l))
         .collect_vec();
 
     let mut iter = mirrors_per_row.iter();
@@ -68,7 +72,7 @@ fn process_map(map: &CharMap, ignore_result: Option<usize>) -> usize {
     *result.iter().next().unwrap_or(&0) * result_multiplier
 }
 
-fn process_map_with_smudge(map: &mut CharMap) -> usize {
+fn process_map_with_smudge(map: &mut CharMap) -> Option<usize> {
     let original_score = process_map(map, None);
 
     // now try to change every single cell to a mirror to a different one
@@ -80,14 +84,13 @@ fn process_map_with_smudge(map: &mut CharMap) -> usize {
             map.set_cell(x as usize, y as usize, new_char);
             let new_score = process_map(map, Some(original_score));
             if new_score != 0 {
-                return new_score;
+                return Some(new_score);
             }
             map.set_cell(x as usize, y as usize, old_char);
         }
     }
 
-    map.print();
-    panic!("No smudge found");
+    None
 }
 
 fn potential_mirror_positions(line: &CharRow) -> HashSet<usize> {
@@ -104,7 +107,7 @@ fn is_mirror_at(mirror_pos: usize, line: &CharRow) -> bool {
     let mirror_pos = mirror_pos as i64;
     let row_len = line.len();
     let range_size = (row_len as f64 / 2.0).ceil() as i64;
-    let left_range_start = mirror_pos - range_size as i64;
+    let left_range_start = mirror_pos - range_size;
     let left_range = left_range_start..mirror_pos;
 
     for left_pos in left_range {
@@ -145,7 +148,7 @@ mod tests {
                      ..##..##.
                      #.#.##.#.";
         let mut map = CharMap::from_iter(input.lines().map(|l| l.trim()), ' ');
-        assert_eq!(300, process_map_with_smudge(&mut map))
+        assert_eq!(Some(300), process_map_with_smudge(&mut map))
     }
 
     #[test]
@@ -171,7 +174,7 @@ mod tests {
                      ..##..###
                      #....#..#";
         let mut map = CharMap::from_iter(input.lines().map(|l| l.trim()), ' ');
-        assert_eq!(100, process_map_with_smudge(&mut map))
+        assert_eq!(Some(100), process_map_with_smudge(&mut map))
     }
 
     #[test]
@@ -194,4 +197,12 @@ mod tests {
         assert_eq!("400", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_no_smudge() {
+        let Err(AocError::NoSmudge { span, .. }) = process("#\n") else {
+            panic!("expected a missing smudge");
+        };
+        assert_eq!(miette::SourceSpan::from((0, 1)), span);
+    }
 }
@@ -1,51 +1,75 @@
-use crate::{custom_error::AocError, utils::CharMap};
+use aoc_utils::{CharMap, Params, Solution};
+
+use crate::{
+    custom_error::AocError,
+    platform::{Platform, Tile},
+};
 
 // Gets a map depicting rocks (round as O and square as #) and updates the map
 // to calculate how it would look like if the platform was tilted north and all
 // the round rocks rolled until stopping.
-fn tilt(map: &mut CharMap) {
+fn tilt(map: &mut Platform) {
     for col in 0..map.width() {
         let mut stop = 0; // place where a rock would stop if it were to roll north
         for row in 0..map.height() {
-            let c = *map.cell(col as i64, row as i64);
-            if c == '#' {
+            let tile = *map.cell(col as i64, row as i64);
+            if tile == Tile::Square {
                 stop = row + 1;
             }
 
-            if c == 'O' {
-                map.set_cell(col, row, '.');
-                map.set_cell(col, stop, c);
-                stop = stop + 1;
+            if tile == Tile::Round {
+                map.set_cell(col, row, Tile::Empty);
+                map.set_cell(col, stop, tile);
+                stop += 1;
             }
         }
     }
 }
 
-fn load(map: &CharMap) -> usize {
+fn load(map: &Platform) -> usize {
     let mut total_load = 0;
     for col in 0..map.width() {
         for row in 0..map.height() {
-            if *map.cell(col as i64, row as i64) == 'O' {
+            if *map.cell(col as i64, row as i64) == Tile::Round {
                 let load = map.height() - row;
-                total_load = total_load + load;
+                total_load += load;
             }
         }
     }
-    return total_load;
+    total_load
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 14;
+    const PART: u8 = 1;
+
+    type Input = Platform;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Platform, AocError> {
+        let map = CharMap::parse(input, '@', |c| ".#O".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        Ok(map.map(Tile::from_char))
+    }
+
+    fn solve(mut map: Platform, _params: &Params) -> Result<String, AocError> {
+        tilt(&mut map);
+        tracing::trace!(%map, "tilted");
+        let total_load = load(&map);
+        Ok(total_load.to_string())
+    }
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut map = CharMap::from_str(input, '@');
-    map.print();
-    tilt(&mut map);
-    let total_load = load(&map);
-    return Ok(total_load.to_string());
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::CharMap;
+    use aoc_utils::CharMap;
 
     use super::*;
 
@@ -62,7 +86,7 @@ mod tests {
                      #....###..
                      #OO..#....";
 
-        let mut map = CharMap::from_iter(input.lines().map(|s| s.trim()), '@');
+        let mut map = CharMap::from_iter(input.lines().map(|s| s.trim()), '@').map(Tile::from_char);
         tilt(&mut map);
 
         let expected = "OOOO.#.O..
@@ -75,17 +99,15 @@ mod tests {
                         ..O.......
                         #....###..
                         #....#....";
-        let expected_map = CharMap::from_iter(expected.lines().map(|s| s.trim()), '@');
+        let expected_map =
+            CharMap::from_iter(expected.lines().map(|s| s.trim()), '@').map(Tile::from_char);
 
         if map == expected_map {
             return;
         }
 
-        println!("Actual:");
-        map.print();
-
-        println!("Expected:");
-        expected_map.print();
+        println!("Actual:\n{}\n", map);
+        println!("Expected:\n{}\n", expected_map);
 
         for row in 0..map.height() {
             for col in 0..map.width() {
@@ -100,8 +122,6 @@ mod tests {
         }
 
         assert_eq!(expected_map, map);
-
-        map.print();
     }
 
     #[test]
@@ -116,7 +136,7 @@ mod tests {
                      ..O.......
                      #....###..
                      #....#....";
-        let map = CharMap::from_iter(input.lines().map(|s| s.trim()), '@');
+        let map = CharMap::from_iter(input.lines().map(|s| s.trim()), '@').map(Tile::from_char);
         assert_eq!(136, load(&map));
     }
 
@@ -1,120 +1,113 @@
-use crate::{custom_error::AocError, utils::CharMap};
+use aoc_utils::{state_after, CharMap, Params, Solution};
+
+use crate::{
+    custom_error::AocError,
+    platform::{Platform, Tile},
+};
 
 // Gets a map depicting rocks (round as O and square as #) and updates the map
 // to calculate how it would look like if the platform was tilted north and all
 // the round rocks rolled until stopping.
-fn tilt(map: &mut CharMap) {
+fn tilt(map: &mut Platform) {
     for col in 0..map.width() {
         let mut stop = 0; // place where a rock would stop if it were to roll north
         for row in 0..map.height() {
-            let c = *map.cell(col as i64, row as i64);
-            if c == '#' {
+            let tile = *map.cell(col as i64, row as i64);
+            if tile == Tile::Square {
                 stop = row + 1;
             }
 
-            if c == 'O' {
-                map.set_cell(col, row, '.');
-                map.set_cell(col, stop, c);
-                stop = stop + 1;
+            if tile == Tile::Round {
+                map.set_cell(col, row, Tile::Empty);
+                map.set_cell(col, stop, tile);
+                stop += 1;
             }
         }
     }
 }
 
-fn load(map: &CharMap) -> usize {
+fn load(map: &Platform) -> usize {
     let mut total_load = 0;
     for col in 0..map.width() {
         for row in 0..map.height() {
-            if *map.cell(col as i64, row as i64) == 'O' {
+            if *map.cell(col as i64, row as i64) == Tile::Round {
                 let load = map.height() - row;
-                total_load = total_load + load;
+                total_load += load;
             }
         }
     }
-    return total_load;
+    total_load
 }
 
-fn spin_cycle(map: &CharMap) -> CharMap {
+fn spin_cycle(map: &Platform) -> Platform {
     let mut map = map.clone();
     for _ in 0..4 {
         tilt(&mut map);
         map = map.rotate_right();
     }
-    return map;
+    map
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut map = CharMap::from_str_with_trim(input, '@');
-
-    let mut seen = std::collections::HashMap::new();
-    seen.insert(map.hash64(), 0);
-    let mut cycle_start = 0;
-    let mut cycle_len = 0;
-
-    let max_cycles = 1000000000;
-    for i in 1..=max_cycles {
-        map = spin_cycle(&map);
-        if seen.contains_key(&map.hash64()) {
-            cycle_start = seen[&map.hash64()];
-            cycle_len = i - cycle_start;
-            println!(
-                "Found a cycle after {} spins! Cycle start: {}",
-                i, cycle_start
-            );
-            break;
-        } else {
-            seen.insert(map.hash64(), i);
-        }
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 14;
+    const PART: u8 = 2;
+
+    type Input = Platform;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Platform, AocError> {
+        let map = CharMap::parse(input, '@', |c| ".#O".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        Ok(map.map(Tile::from_char))
     }
 
-    let left_cycles = (max_cycles - cycle_start) % cycle_len;
-    println!(
-        "Left cycles: {} ({} - {} % {})",
-        left_cycles, max_cycles, cycle_start, cycle_len
-    );
-    for _ in 0..left_cycles {
-        map = spin_cycle(&map);
+    fn solve(map: Platform, _params: &Params) -> Result<String, AocError> {
+        // The rocks settle into a loop pretty quickly, so we only need to spin until the first repeat
+        let map = state_after(map, spin_cycle, 1000000000);
+
+        let total_load = load(&map);
+        Ok(total_load.to_string())
     }
+}
 
-    let total_load = load(&map);
-    return Ok(total_load.to_string());
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use core::panic;
 
-    use crate::utils::CharMap;
+    use aoc_utils::CharMap;
 
     use super::*;
 
-    fn assert_maps_eq(map1: &CharMap, map2: &CharMap) {
+    fn assert_maps_eq(map1: &Platform, map2: &Platform) {
         if map1 == map2 {
             return;
         }
 
-        let square_rocks1 = map1.count('#');
-        let square_rocks2 = map2.count('#');
+        let square_rocks1 = map1.count(Tile::Square);
+        let square_rocks2 = map2.count(Tile::Square);
         if square_rocks1 != square_rocks2 {
             println!("The maps have different number of square rocks:");
             println!(" - real: {}", square_rocks1);
             println!(" - expected: {}", square_rocks2);
         }
 
-        let round_rocks1 = map1.count('O');
-        let round_rocks2 = map2.count('O');
+        let round_rocks1 = map1.count(Tile::Round);
+        let round_rocks2 = map2.count(Tile::Round);
         if round_rocks1 != round_rocks2 {
             println!("The maps have different number of round rocks:");
             println!(" - real: {}", round_rocks1);
             println!(" - expected: {}", round_rocks2);
         }
 
-        println!("Actual:");
-        map1.print();
-
-        println!("Expected:");
-        map2.print();
+        println!("Actual:\n{}\n", map1);
+        println!("Expected:\n{}\n", map2);
 
         for row in 0..map1.height() {
             for col in 0..map1.width() {
@@ -144,7 +137,7 @@ mod tests {
                      #....###..
                      #OO..#....";
 
-        let map = CharMap::from_str_with_trim(input, '@');
+        let map = CharMap::from_str_with_trim(input, '@').map(Tile::from_char);
 
         // After 1 cycle
         let map = spin_cycle(&map);
@@ -158,7 +151,7 @@ mod tests {
                         ......OOOO
                         #...O###..
                         #..OO#....";
-        let expected_map = CharMap::from_str_with_trim(expected, '@');
+        let expected_map = CharMap::from_str_with_trim(expected, '@').map(Tile::from_char);
         assert_maps_eq(&map, &expected_map);
 
         // After 2 cycles
@@ -173,7 +166,7 @@ mod tests {
                         .......OOO
                         #..OO###..
                         #.OOO#...O";
-        let expected_map = CharMap::from_str_with_trim(expected, '@');
+        let expected_map = CharMap::from_str_with_trim(expected, '@').map(Tile::from_char);
         assert_maps_eq(&map, &expected_map);
 
         // After 3 cycles
@@ -188,7 +181,7 @@ mod tests {
                         .......OOO
                         #...O###.O
                         #.OOO#...O";
-        let expected_map = CharMap::from_str_with_trim(expected, '@');
+        let expected_map = CharMap::from_str_with_trim(expected, '@').map(Tile::from_char);
         assert_maps_eq(&map, &expected_map);
     }
 
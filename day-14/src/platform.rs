@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use aoc_utils::Grid;
+
+// A spot on the platform, either empty or holding a rock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Empty,  // .
+    Round,  // O, rolls when the platform is tilted
+    Square, // #, stays in place
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Self {
+        match c {
+            'O' => Self::Round,
+            '#' => Self::Square,
+            _ => Self::Empty,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Empty => '.',
+            Self::Round => 'O',
+            Self::Square => '#',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+pub type Platform = Grid<Tile>;
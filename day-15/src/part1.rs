@@ -1,3 +1,6 @@
+use aoc_utils::{list1, parse_all, token, Params, Solution};
+use nom::{bytes::complete::is_not, combinator::map, error::context};
+
 use crate::custom_error::AocError;
 
 fn aoc_hash(input: &str) -> u64 {
@@ -5,13 +8,35 @@ fn aoc_hash(input: &str) -> u64 {
     for c in input.chars() {
         hash = ((hash + c as u64) * 17) % 256;
     }
-    return hash;
+    hash
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 15;
+    const PART: u8 = 1;
+
+    type Input = Vec<String>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<String>, AocError> {
+        let steps = list1(
+            token(","),
+            context("a step", map(is_not(","), str::to_string)),
+        );
+        Ok(parse_all(input.trim(), steps).map_err(|err| err.with_source(input))?)
+    }
+
+    fn solve(steps: Vec<String>, _params: &Params) -> Result<String, AocError> {
+        let result = steps.iter().map(|s| aoc_hash(s)).sum::<u64>();
+        Ok(result.to_string())
+    }
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let result = input.trim().split(",").map(|s| aoc_hash(s)).sum::<u64>();
-    return Ok(result.to_string());
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -23,10 +48,24 @@ mod tests {
         assert_eq!(52, aoc_hash("HASH"));
     }
 
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!("1320", process(input)?);
+        assert_eq!("1320", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        assert_eq!(EXAMPLE, Part1::parse(EXAMPLE)?.join(","));
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("rn=1,,qp=3"));
+        assert_eq!("expected a step, found `,`", err.message);
+        assert_eq!(miette::SourceSpan::from((5, 1)), err.span);
+    }
 }
@@ -1,3 +1,6 @@
+use aoc_utils::{list1, number, parse_all, symbol, token, IResult, Params, Solution};
+use nom::character::complete::alpha1;
+
 use crate::custom_error::AocError;
 
 fn aoc_hash(input: &str) -> u64 {
@@ -5,36 +8,31 @@ fn aoc_hash(input: &str) -> u64 {
     for c in input.chars() {
         hash = ((hash + c as u64) * 17) % 256;
     }
-    return hash;
+    hash
 }
 
 #[derive(Clone)]
-struct Lens {
+pub struct Lens {
     label: String,
     focal_length: u64,
 }
 
-impl Lens {
-    fn from_rule(rule: &str) -> (Lens, char) {
-        let mut rule_len = rule.len();
-        let mut focal_length = 0;
-        if rule.chars().last().unwrap().is_numeric() {
-            focal_length = rule[rule.len() - 1..].parse::<u64>().unwrap();
-            rule_len = rule_len - 1;
-        }
-
-        let label = rule[..rule_len - 1].to_string();
-        let operation = rule.chars().nth(rule_len - 1).unwrap();
-
-        return (
-            Lens {
-                label,
-                focal_length,
-            },
-            operation,
-        );
-    }
+// `rn=1` puts a lens into a box, `cm-` takes the one with that label out
+fn rule(input: &str) -> IResult<'_, (Lens, char)> {
+    let (input, label) = alpha1(input)?;
+    let (input, operation) = symbol("=-", "`=` or `-`")(input)?;
+    let (rest, focal_length) = match operation {
+        '=' => number(input)?,
+        _ => (input, 0),
+    };
+    let lens = Lens {
+        label: label.to_string(),
+        focal_length,
+    };
+    Ok((rest, (lens, operation)))
+}
 
+impl Lens {
     fn box_number(&self) -> u64 {
         aoc_hash(&self.label)
     }
@@ -46,38 +44,59 @@ impl std::fmt::Debug for Lens {
     }
 }
 
-fn print_boxes(boxes: &Vec<Vec<Lens>>) {
-    for i in 0..boxes.len() {
-        if boxes[i].len() > 0 {
-            println!("Box {}: {:?}", i, boxes[i]);
+fn trace_boxes(boxes: &[Vec<Lens>]) {
+    if !tracing::enabled!(tracing::Level::TRACE) {
+        return;
+    }
+
+    for (box_number, lenses) in boxes.iter().enumerate() {
+        if !lenses.is_empty() {
+            tracing::trace!(box_number, ?lenses, "box");
         }
     }
-    println!();
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut boxes: Vec<Vec<Lens>> = vec![vec![]; 256];
-
-    let rules = input.trim().split(",");
-    for rule in rules {
-        let (lens, operation) = Lens::from_rule(rule);
-        println!("After {:?}", rule);
-        handle_operation(&mut boxes, &lens, operation);
-        print_boxes(&boxes);
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 15;
+    const PART: u8 = 2;
+
+    type Input = Vec<(Lens, char)>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<(Lens, char)>, AocError> {
+        Ok(parse_all(input.trim(), list1(token(","), rule))
+            .map_err(|err| err.with_source(input))?)
     }
 
-    let mut total_power = 0;
-    for (box_number, bx) in boxes.iter().enumerate() {
-        for (slot_number, lens) in bx.iter().enumerate() {
-            let lens_power = (1 + box_number as u64) * (1 + slot_number as u64) * lens.focal_length;
-            total_power = total_power + lens_power;
+    fn solve(rules: Vec<(Lens, char)>, _params: &Params) -> Result<String, AocError> {
+        let mut boxes: Vec<Vec<Lens>> = vec![vec![]; 256];
+
+        for (lens, operation) in rules {
+            tracing::trace!(?lens, %operation, "applying rule");
+            handle_operation(&mut boxes, &lens, operation);
+            trace_boxes(&boxes);
+        }
+
+        let mut total_power = 0;
+        for (box_number, bx) in boxes.iter().enumerate() {
+            for (slot_number, lens) in bx.iter().enumerate() {
+                let lens_power =
+                    (1 + box_number as u64) * (1 + slot_number as u64) * lens.focal_length;
+                total_power += lens_power;
+            }
         }
+        Ok(total_power.to_string())
     }
-    return Ok(total_power.to_string());
 }
 
-fn handle_operation(boxes: &mut Vec<Vec<Lens>>, lens: &Lens, operation: char) {
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
+}
+
+fn handle_operation(boxes: &mut [Vec<Lens>], lens: &Lens, operation: char) {
     let box_number = lens.box_number();
     let current_box = boxes.get_mut(box_number as usize).unwrap();
 
@@ -88,24 +107,14 @@ fn handle_operation(boxes: &mut Vec<Vec<Lens>>, lens: &Lens, operation: char) {
                 if current_box[i].label == lens.label {
                     current_box.remove(i);
                 } else {
-                    i = i + 1;
-                }
-            }
-        }
-        '=' => {
-            let mut replaced = false;
-            for i in 0..current_box.len() {
-                if current_box[i].label == lens.label {
-                    current_box[i] = lens.clone();
-                    replaced = true;
-                    break;
+                    i += 1;
                 }
             }
-
-            if !replaced {
-                current_box.push(lens.clone());
-            }
         }
+        '=' => match current_box.iter_mut().find(|l| l.label == lens.label) {
+            Some(existing) => *existing = lens.clone(),
+            None => current_box.push(lens.clone()),
+        },
         _ => {
             panic!("Unknown operation: {}", operation)
         }
@@ -121,10 +130,34 @@ mod tests {
         assert_eq!(52, aoc_hash("HASH"));
     }
 
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!("145", process(input)?);
+        assert_eq!("145", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let printed = Part2::parse(EXAMPLE)?
+            .iter()
+            .map(|(lens, operation)| match operation {
+                '=' => format!("{}={}", lens.label, lens.focal_length),
+                _ => format!("{}-", lens.label),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(EXAMPLE, printed.join(","));
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("rn=1,cm+,qp=3"));
+        assert_eq!("expected `=` or `-`, found `+`", err.message);
+        assert_eq!(miette::SourceSpan::from((7, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("rn=x"));
+        assert_eq!(miette::SourceSpan::from((3, 1)), err.span);
+    }
 }
@@ -1,9 +1,8 @@
 use std::collections::HashSet;
 
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, Direction, Point},
-};
+use aoc_utils::{CharMap, Direction, Grid, Params, Point, Solution};
+
+use crate::{custom_error::AocError, tile::Tile};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Position {
@@ -13,7 +12,7 @@ struct Position {
 
 // Recursively simulate the beam
 fn simulate(
-    map: &CharMap,
+    map: &Grid<Tile>,
     mut position: Position,
     seen: &mut HashSet<Position>,
     energized: &mut HashSet<Point<i64>>,
@@ -30,7 +29,7 @@ fn simulate(
     let next_cell = map.cell_for_point(&next_point);
 
     // If the next cell is a wall, stop
-    if *next_cell == '#' {
+    if *next_cell == Tile::Wall {
         return;
     }
 
@@ -38,13 +37,13 @@ fn simulate(
     position.point = next_point;
 
     // If the next cell is empty, continue
-    if *next_cell == '.' {
+    if *next_cell == Tile::Empty {
         return simulate(map, position, seen, energized);
     }
 
     // If we hit a mirror, change direction
     let dir = position.direction;
-    if *next_cell == '/' {
+    if *next_cell == Tile::ForwardMirror {
         position.direction = if dir == Direction::North || dir == Direction::South {
             dir.turn_right()
         } else {
@@ -54,7 +53,7 @@ fn simulate(
     }
 
     // Another mirror
-    if *next_cell == '\\' {
+    if *next_cell == Tile::BackwardMirror {
         position.direction = if dir == Direction::North || dir == Direction::South {
             dir.turn_left()
         } else {
@@ -64,21 +63,24 @@ fn simulate(
     }
 
     // If the next cell is a splitter and we hit it from the pointy end, treat it as empty
-    if (*next_cell == '|' && (dir == Direction::North || dir == Direction::South))
-        || (*next_cell == '-' && (dir == Direction::East || dir == Direction::West))
+    if (*next_cell == Tile::VerticalSplitter
+        && (dir == Direction::North || dir == Direction::South))
+        || (*next_cell == Tile::HorizontalSplitter
+            && (dir == Direction::East || dir == Direction::West))
     {
         return simulate(map, position, seen, energized);
     }
 
     // If the next cell is a splitter and we hit it from the flat end, split into two beams
-    if (*next_cell == '|' && (dir == Direction::East || dir == Direction::West))
-        || (*next_cell == '-' || (dir == Direction::North || dir == Direction::South))
+    if (*next_cell == Tile::VerticalSplitter && (dir == Direction::East || dir == Direction::West))
+        || (*next_cell == Tile::HorizontalSplitter
+            && (dir == Direction::North || dir == Direction::South))
     {
-        let mut left = position.clone();
+        let mut left = position;
         left.direction = position.direction.turn_left();
         simulate(map, left, seen, energized);
 
-        let mut right = position.clone();
+        let mut right = position;
         right.direction = position.direction.turn_right();
         simulate(map, right, seen, energized);
         return;
@@ -87,21 +89,40 @@ fn simulate(
     unreachable!("We should have handled all cases by now");
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str_with_trim(input, '#');
-    map.print();
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 16;
+    const PART: u8 = 1;
 
-    let start = Position {
-        point: Point::new(-1, 0),
-        direction: Direction::East,
-    };
-    let mut seen = HashSet::new();
-    let mut energized = HashSet::new();
+    type Input = Grid<Tile>;
+    type Error = AocError;
 
-    simulate(&map, start, &mut seen, &mut energized);
+    fn parse(input: &str) -> Result<Grid<Tile>, AocError> {
+        let map = CharMap::parse(input, '#', |c| ".|-/\\".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        Ok(map.map(Tile::from_char))
+    }
+
+    fn solve(map: Grid<Tile>, _params: &Params) -> Result<String, AocError> {
+        tracing::trace!(%map, "contraption");
+
+        let start = Position {
+            point: Point::new(-1, 0),
+            direction: Direction::East,
+        };
+        let mut seen = HashSet::new();
+        let mut energized = HashSet::new();
 
-    Ok((energized.iter().count() - 1).to_string())
+        simulate(&map, start, &mut seen, &mut energized);
+
+        Ok((energized.len() - 1).to_string())
+    }
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -124,7 +145,3 @@ mod tests {
         Ok(())
     }
 }
-
-// Submissions:
-// 6796 - too high
-// 6795 - correct! (off by one error due to the fact we started outside the map)
@@ -1,9 +1,8 @@
 use std::collections::HashSet;
 
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, Direction, Point},
-};
+use aoc_utils::{CharMap, Direction, Grid, Params, Point, Solution};
+
+use crate::{custom_error::AocError, tile::Tile};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Position {
@@ -13,7 +12,7 @@ struct Position {
 
 // Recursively simulate the beam
 fn simulate(
-    map: &CharMap,
+    map: &Grid<Tile>,
     mut position: Position,
     seen: &mut HashSet<Position>,
     energized: &mut HashSet<Point<i64>>,
@@ -30,7 +29,7 @@ fn simulate(
     let next_cell = map.cell_for_point(&next_point);
 
     // If the next cell is a wall, stop
-    if *next_cell == '#' {
+    if *next_cell == Tile::Wall {
         return;
     }
 
@@ -38,13 +37,13 @@ fn simulate(
     position.point = next_point;
 
     // If the next cell is empty, continue
-    if *next_cell == '.' {
+    if *next_cell == Tile::Empty {
         return simulate(map, position, seen, energized);
     }
 
     // If we hit a mirror, change direction
     let dir = position.direction;
-    if *next_cell == '/' {
+    if *next_cell == Tile::ForwardMirror {
         position.direction = if dir == Direction::North || dir == Direction::South {
             dir.turn_right()
         } else {
@@ -54,7 +53,7 @@ fn simulate(
     }
 
     // Another mirror
-    if *next_cell == '\\' {
+    if *next_cell == Tile::BackwardMirror {
         position.direction = if dir == Direction::North || dir == Direction::South {
             dir.turn_left()
         } else {
@@ -64,21 +63,24 @@ fn simulate(
     }
 
     // If the next cell is a splitter and we hit it from the pointy end, treat it as empty
-    if (*next_cell == '|' && (dir == Direction::North || dir == Direction::South))
-        || (*next_cell == '-' && (dir == Direction::East || dir == Direction::West))
+    if (*next_cell == Tile::VerticalSplitter
+        && (dir == Direction::North || dir == Direction::South))
+        || (*next_cell == Tile::HorizontalSplitter
+            && (dir == Direction::East || dir == Direction::West))
     {
         return simulate(map, position, seen, energized);
     }
 
     // If the next cell is a splitter and we hit it from the flat end, split into two beams
-    if (*next_cell == '|' && (dir == Direction::East || dir == Direction::West))
-        || (*next_cell == '-' || (dir == Direction::North || dir == Direction::South))
+    if (*next_cell == Tile::VerticalSplitter && (dir == Direction::East || dir == Direction::West))
+        || (*next_cell == Tile::HorizontalSplitter
+            && (dir == Direction::North || dir == Direction::South))
     {
-        let mut left = position.clone();
+        let mut left = position;
         left.direction = position.direction.turn_left();
         simulate(map, left, seen, energized);
 
-        let mut right = position.clone();
+        let mut right = position;
         right.direction = position.direction.turn_right();
         simulate(map, right, seen, energized);
         return;
@@ -87,47 +89,68 @@ fn simulate(
     unreachable!("We should have handled all cases by now");
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str_with_trim(input, '#');
-    map.print();
-
-    // We will start on all borders facing inwards and see which one leads to the most energized map
-    let mut starts = Vec::new();
-    for col in 0..map.width() {
-        starts.push(Position {
-            point: Point::new(col as i64, -1),
-            direction: Direction::South,
-        });
-        starts.push(Position {
-            point: Point::new(col as i64, map.height() as i64),
-            direction: Direction::North,
-        });
-    }
-    for row in 0..map.height() {
-        starts.push(Position {
-            point: Point::new(-1, row as i64),
-            direction: Direction::East,
-        });
-        starts.push(Position {
-            point: Point::new(map.width() as i64, row as i64),
-            direction: Direction::West,
-        });
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 16;
+    const PART: u8 = 2;
+
+    type Input = Grid<Tile>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, AocError> {
+        let map = CharMap::parse(input, '#', |c| ".|-/\\".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        Ok(map.map(Tile::from_char))
     }
 
-    let mut max_energized = 0;
-    for start in starts {
-        let mut seen = HashSet::new();
-        let mut energized = HashSet::new();
+    fn solve(map: Grid<Tile>, _params: &Params) -> Result<String, AocError> {
+        tracing::trace!(%map, "contraption");
+
+        // We will start on all borders facing inwards and see which one leads to the most energized map
+        let mut starts = Vec::new();
+        for col in 0..map.width() {
+            starts.push(Position {
+                point: Point::new(col as i64, -1),
+                direction: Direction::South,
+            });
+            starts.push(Position {
+                point: Point::new(col as i64, map.height() as i64),
+                direction: Direction::North,
+            });
+        }
+        for row in 0..map.height() {
+            starts.push(Position {
+                point: Point::new(-1, row as i64),
+                direction: Direction::East,
+            });
+            starts.push(Position {
+                point: Point::new(map.width() as i64, row as i64),
+                direction: Direction::West,
+            });
+        }
 
-        simulate(&map, start, &mut seen, &mut energized);
+        let mut max_energized = 0;
+        for start in starts {
+            let _beam =
+                tracing::trace_span!("beam", x = start.point.x, y = start.point.y).entered();
+            let mut seen = HashSet::new();
+            let mut energized = HashSet::new();
 
-        if energized.len() > max_energized {
-            max_energized = energized.len();
+            simulate(&map, start, &mut seen, &mut energized);
+
+            if energized.len() > max_energized {
+                max_energized = energized.len();
+            }
         }
+
+        Ok((max_energized - 1).to_string())
     }
+}
 
-    Ok((max_energized - 1).to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -0,0 +1,39 @@
+use std::fmt::Display;
+
+// What a beam can run into on the contraption
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Empty,              // .
+    Wall,               // outside of the contraption
+    ForwardMirror,      // /
+    BackwardMirror,     // \
+    VerticalSplitter,   // |
+    HorizontalSplitter, // -
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Self {
+        match c {
+            '.' => Self::Empty,
+            '/' => Self::ForwardMirror,
+            '\\' => Self::BackwardMirror,
+            '|' => Self::VerticalSplitter,
+            '-' => Self::HorizontalSplitter,
+            _ => Self::Wall,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Empty => '.',
+            Self::Wall => '#',
+            Self::ForwardMirror => '/',
+            Self::BackwardMirror => '\\',
+            Self::VerticalSplitter => '|',
+            Self::HorizontalSplitter => '-',
+        };
+        write!(f, "{}", c)
+    }
+}
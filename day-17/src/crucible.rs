@@ -0,0 +1,258 @@
+use aoc_utils::{dijkstra_indexed, CharMap, Direction, Grid, Point};
+
+// A change of direction a crucible is allowed to make
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+    Reverse,
+}
+
+impl Turn {
+    fn apply(&self, dir: Direction) -> Direction {
+        match self {
+            Turn::Left => dir.turn_left(),
+            Turn::Right => dir.turn_right(),
+            Turn::Reverse => dir.opposite(),
+        }
+    }
+}
+
+// Movement rules for a crucible: how far it must (and can) go in a straight line
+// before turning or stopping, and which turns it can make
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub min_run: usize,
+    pub max_run: usize,
+    pub turns: Vec<Turn>,
+}
+
+impl Rules {
+    pub fn new(min_run: usize, max_run: usize) -> Self {
+        Self {
+            min_run,
+            max_run,
+            turns: vec![Turn::Left, Turn::Right],
+        }
+    }
+
+    pub fn with_turns(mut self, turns: &[Turn]) -> Self {
+        self.turns = turns.to_vec();
+        self
+    }
+
+    // Regular crucible: at most 3 blocks in a straight line
+    pub fn crucible() -> Self {
+        Self::new(1, 3)
+    }
+
+    // Ultra crucible: at least 4 and at most 10 blocks in a straight line
+    pub fn ultra_crucible() -> Self {
+        Self::new(4, 10)
+    }
+}
+
+// The optimal way to get from the top left to the bottom right corner of the map
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: i64,
+    pub directions: Vec<Direction>,
+}
+
+impl Route {
+    // Draws the route onto the map the same way the puzzle does, marking each entered block
+    // with an arrow pointing in the direction of movement
+    pub fn render(&self, map: &CharMap) -> CharMap {
+        let mut map = map.clone();
+        let mut pos = map.top_left();
+        for dir in self.directions.iter() {
+            pos = pos + dir.delta();
+            map.set_cell_for_point(&pos, dir.to_char());
+        }
+        map
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct Step {
+    pos: Point<i64>,
+    dir: Direction,
+    steps: usize,
+}
+
+// Returns all steps we can take from the current one along with the heat loss for entering the block
+fn next_steps<'a>(
+    map: &'a Grid<u8>,
+    rules: &'a Rules,
+    current: Step,
+) -> impl Iterator<Item = (Step, i64)> + 'a {
+    // We can only turn after going far enough in the same direction (or at the very start)
+    let can_turn = current.steps == 0 || current.steps >= rules.min_run;
+    let turns = rules.turns.iter().filter(move |_| can_turn);
+    let directions = turns.map(move |turn| turn.apply(current.dir));
+
+    std::iter::once(current.dir)
+        .chain(directions)
+        .filter_map(move |dir| {
+            let steps = if dir == current.dir {
+                current.steps + 1
+            } else {
+                1
+            };
+
+            // Cannot go too far in the same direction
+            if steps > rules.max_run {
+                return None;
+            }
+
+            // Cannot go out of bounds
+            let pos = current.pos + dir.delta();
+            if map.out_of_bounds(&pos) {
+                return None;
+            }
+
+            let block_loss = *map.cell_for_point(&pos) as i64;
+            Some((Step { pos, dir, steps }, block_loss))
+        })
+}
+
+// Finds the route from the top left to the bottom right corner with the least heat loss
+pub fn find_route(map: &Grid<u8>, rules: &Rules) -> Option<Route> {
+    let finish = map.bottom_right();
+
+    // We start in the top left corner without having moved yet, so we can go in any direction
+    let start = Step {
+        pos: map.top_left(),
+        dir: Direction::East,
+        steps: 0,
+    };
+
+    // Every step gets a slot by its position, its direction (crucibles only move in the four
+    // cardinal ones) and how far it has gone in a straight line
+    let runs = rules.max_run + 1;
+    let size = map.width() * map.height() * 4 * runs;
+    let index = |step: &Step| {
+        let block = step.pos.y as usize * map.width() + step.pos.x as usize;
+        (block * 4 + step.dir as usize) * runs + step.steps
+    };
+
+    // The crucible needs to go far enough in a straight line before it can stop at the end
+    let best = dijkstra_indexed(
+        start,
+        size,
+        index,
+        |step| next_steps(map, rules, *step),
+        |step| step.pos == finish && step.steps >= rules.min_run,
+    )?;
+
+    Some(Route {
+        heat_loss: best.cost,
+        directions: best.path.iter().skip(1).map(|step| step.dir).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2413432311323
+                           3215453535623
+                           3255245654254
+                           3446585845452
+                           4546657867536
+                           1438598798454
+                           4457876987766
+                           3637877979653
+                           4654967986887
+                           4564679986453
+                           1224686865563
+                           2546548887735
+                           4322674655533";
+
+    // Checks that the route ends in the bottom right corner, follows the rules and
+    // that its heat loss matches the blocks it goes through
+    fn assert_valid_route(map: &CharMap, rules: &Rules, route: &Route) {
+        let digits = map.to_digits();
+        let mut pos = map.top_left();
+        let mut heat_loss = 0;
+        for dir in route.directions.iter() {
+            pos = pos + dir.delta();
+            assert!(!map.out_of_bounds(&pos));
+            heat_loss += *digits.cell_for_point(&pos) as i64;
+        }
+        assert_eq!(map.bottom_right(), pos);
+        assert_eq!(route.heat_loss, heat_loss);
+
+        let runs = route.directions.chunk_by(|a, b| a == b);
+        for run in runs {
+            assert!(run.len() >= rules.min_run && run.len() <= rules.max_run);
+        }
+    }
+
+    #[test]
+    fn test_crucible() {
+        let map = CharMap::from_str_with_trim(EXAMPLE, '#');
+        let rules = Rules::crucible();
+        let route = find_route(&map.to_digits(), &rules).unwrap();
+        assert_eq!(102, route.heat_loss);
+        assert_valid_route(&map, &rules, &route);
+    }
+
+    #[test]
+    fn test_ultra_crucible() {
+        let map = CharMap::from_str_with_trim(EXAMPLE, '#');
+        let rules = Rules::ultra_crucible();
+        let route = find_route(&map.to_digits(), &rules).unwrap();
+        assert_eq!(94, route.heat_loss);
+        assert_valid_route(&map, &rules, &route);
+    }
+
+    #[test]
+    fn test_reverse() {
+        // Going straight down the right column is cheap, but we can only go 2 blocks in a row.
+        // Without reversing we have to go through the expensive column on the left at least once.
+        let map = CharMap::from_str_with_trim(
+            "11
+             91
+             91
+             91",
+            '#',
+        );
+        let digits = map.to_digits();
+
+        let rules = Rules::new(1, 2);
+        let route = find_route(&digits, &rules).unwrap();
+        assert_eq!(12, route.heat_loss);
+
+        let rules = Rules::new(1, 2).with_turns(&[Turn::Left, Turn::Right, Turn::Reverse]);
+        let route = find_route(&digits, &rules).unwrap();
+        assert_eq!(6, route.heat_loss);
+        assert_valid_route(&map, &rules, &route);
+    }
+
+    #[test]
+    fn test_no_route() {
+        let map = CharMap::from_str("1111", '#').to_digits();
+        assert_eq!(None, find_route(&map, &Rules::ultra_crucible()));
+    }
+
+    #[test]
+    fn test_render() {
+        let map = CharMap::from_str_with_trim(
+            "111
+             991
+             991",
+            '#',
+        );
+        let route = find_route(&map.to_digits(), &Rules::crucible()).unwrap();
+        assert_eq!(4, route.heat_loss);
+
+        let expected = CharMap::from_str_with_trim(
+            "1>>
+             99v
+             99v",
+            '#',
+        );
+        assert_eq!(expected, route.render(&map));
+    }
+}
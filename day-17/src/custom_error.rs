@@ -1,4 +1,4 @@
-
+use aoc_utils::{ParamError, ParseError};
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -7,4 +7,16 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParamError(#[from] ParamError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] ParseError),
+
+    #[error("there is no route from the lava pool to the factory")]
+    #[diagnostic(code(aoc::no_route))]
+    NoRoute,
+}
@@ -1,108 +1,34 @@
-use std::collections::HashMap;
+use aoc_utils::{CharMap, Grid, Params, Solution};
 
 use crate::{
+    crucible::{find_route, Rules},
     custom_error::AocError,
-    utils::Direction,
-    utils::{CharMap, Point},
 };
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-struct Step {
-    pos: Point<i64>,
-    dir: Direction,
-    steps: usize,
-}
-
-fn find_min_loss(
-    map: &CharMap,
-    min_loss: &mut HashMap<Step, i64>,
-    best_total_loss: &mut i64,
-    total_loss: i64,
-    current: Step,
-) {
-    // Cannot take more than 3 steps in the same direction
-    if current.steps > 3 {
-        return;
-    }
+pub struct Part1;
 
-    // Cannot go out of bounds
-    if map.out_of_bounds(&current.pos) {
-        return;
-    }
+impl Solution for Part1 {
+    const DAY: u8 = 17;
+    const PART: u8 = 1;
 
-    // Calculate the loss for the current path (including the current block)
-    let current_block_loss = map.cell_digit_for_point(&current.pos);
-    let total_loss = total_loss + current_block_loss;
+    type Input = Grid<u8>;
+    type Error = AocError;
 
-    // Check against the current known best total loss
-    if total_loss >= *best_total_loss {
-        return;
+    fn parse(input: &str) -> Result<Grid<u8>, AocError> {
+        let map = CharMap::parse(input, '#', |c| c.is_ascii_digit())
+            .map_err(|err| err.with_source(input))?;
+        Ok(map.to_digits())
     }
 
-    // Check min loss for the current position
-    if let Some(previous_min) = min_loss.get(&current) {
-        if total_loss >= *previous_min {
-            return;
-        }
+    fn solve(map: Grid<u8>, _params: &Params) -> Result<String, AocError> {
+        let route = find_route(&map, &Rules::crucible()).ok_or(AocError::NoRoute)?;
+        Ok(route.heat_loss.to_string())
     }
-    min_loss.insert(current, total_loss);
-
-    // Check if we reached the end
-    if current.pos == map.bottom_right() {
-        if total_loss < *best_total_loss {
-            println!("New best loss: {}", total_loss);
-            *best_total_loss = total_loss;
-        }
-        return;
-    }
-
-    // Now try going forward, left and right
-    let forward = Step {
-        dir: current.dir,
-        pos: current.pos + current.dir.delta(),
-        steps: current.steps + 1,
-    };
-    find_min_loss(map, min_loss, best_total_loss, total_loss, forward);
-
-    let left = Step {
-        dir: current.dir.turn_left(),
-        pos: current.pos + current.dir.turn_left().delta(),
-        steps: 1,
-    };
-    find_min_loss(map, min_loss, best_total_loss, total_loss, left);
-
-    let right = Step {
-        dir: current.dir.turn_right(),
-        pos: current.pos + current.dir.turn_right().delta(),
-        steps: 1,
-    };
-    find_min_loss(map, min_loss, best_total_loss, total_loss, right);
 }
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str_with_trim(input, '#');
-
-    let mut best_total_loss = std::i64::MAX;
-    let mut min_loss_per_step = HashMap::new();
-
-    // Go east
-    let east = Step {
-        pos: Point::new(1, 0),
-        dir: Direction::East,
-        steps: 1,
-    };
-    find_min_loss(&map, &mut min_loss_per_step, &mut best_total_loss, 0, east);
-
-    // Go south
-    let south = Step {
-        pos: Point::new(0, 1),
-        dir: Direction::South,
-        steps: 1,
-    };
-    find_min_loss(&map, &mut min_loss_per_step, &mut best_total_loss, 0, south);
-
-    Ok(best_total_loss.to_string())
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -130,10 +56,3 @@ mod tests {
         Ok(())
     }
 }
-
-// Submissions:
-// 2203 - too high
-// 1185 - too high
-// 1183 - too high
-// 1155 - correct!
-// 1132 - too low
@@ -1,3 +1,4 @@
+use aoc_utils::{ParamError, ParseError};
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -6,4 +7,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParamError(#[from] ParamError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] ParseError),
 }
@@ -1,107 +1,139 @@
-use itertools::Itertools;
-
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, Point},
+use aoc_utils::{
+    lattice_points, number, parse_lines, symbol, token, Direction, IResult, Params, Point, Solution,
+};
+use nom::{
+    bytes::complete::take_while_m_n,
+    character::complete::space1,
+    combinator::map,
+    error::context,
+    sequence::{delimited, preceded, tuple},
 };
 
+use crate::custom_error::AocError;
+
+// One line of the dig plan: `R 6 (#70c710)`
 #[derive(Debug)]
-struct Dig {
-    map: CharMap,
-    pos: Point<i64>,
-    max_x: i64,
-    max_y: i64,
+pub struct DigStep {
+    dir: Direction,
+    distance: i64,
+    color: u32,
 }
 
-impl Dig {
-    fn new() -> Self {
-        let map_size = 10000;
-        let mut dig = Self {
-            map: CharMap::from_dimensions(map_size, map_size, '.'),
-            pos: Point::new(0, 0),
-            max_x: 0,
-            max_y: 0,
+impl std::fmt::Display for DigStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dir = match self.dir {
+            Direction::East => 'R',
+            Direction::West => 'L',
+            Direction::North => 'U',
+            _ => 'D',
         };
-        dig.map
-            .set_cell(dig.pos.x as usize, dig.pos.y as usize, '#');
-        dig
+        write!(f, "{} {} (#{:06x})", dir, self.distance, self.color)
     }
+}
 
-    fn dig(&mut self, dir: &str, steps: i64, _color: &str) {
-        for _ in 0..steps {
-            match dir {
-                "R" => self.pos.x += 1,
-                "L" => self.pos.x -= 1,
-                "U" => self.pos.y -= 1,
-                "D" => self.pos.y += 1,
-                _ => panic!("Unknown direction {}", dir),
-            }
-
-            if self.pos.x < 0 || self.pos.y < 0 {
-                panic!("Out of bounds");
-            }
-
-            if self.pos.x > self.max_x {
-                self.max_x = self.pos.x;
-            }
-
-            if self.pos.y > self.max_y {
-                self.max_y = self.pos.y;
-            }
-
-            println!("Point::new({},{})", self.pos.x, self.pos.y);
-            self.map
-                .set_cell(self.pos.x as usize, self.pos.y as usize, '#');
-        }
-    }
+fn direction(input: &str) -> IResult<'_, Direction> {
+    map(symbol("RLUD", "`R`, `L`, `U` or `D`"), |dir| match dir {
+        'R' => Direction::East,
+        'L' => Direction::West,
+        'U' => Direction::North,
+        _ => Direction::South,
+    })(input)
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut dig = Dig::new();
-    for line in input.lines() {
-        let line = line.trim();
-        let parts = line.split(' ').collect_vec();
-        let dir = parts[0];
-        let steps = parts[1].parse::<i64>().unwrap();
-        let color = parts[2].trim_start_matches('(').trim_end_matches(')');
-
-        // println!("{} {} {}", dir, steps, color);
-        dig.dig(dir, steps, color);
+// `(#70c710)`
+fn color(input: &str) -> IResult<'_, u32> {
+    let hex = context(
+        "six hex digits",
+        take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit()),
+    );
+    map(delimited(token("(#"), hex, token(")")), |hex| {
+        u32::from_str_radix(hex, 16).unwrap()
+    })(input)
+}
+
+fn dig_step(input: &str) -> IResult<'_, DigStep> {
+    let (rest, (dir, distance, color)) =
+        tuple((direction, preceded(space1, number), preceded(space1, color)))(input)?;
+    Ok((
+        rest,
+        DigStep {
+            dir,
+            distance,
+            color,
+        },
+    ))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 18;
+    const PART: u8 = 1;
+
+    type Input = Vec<DigStep>;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Vec<DigStep>, AocError> {
+        Ok(parse_lines(input, dig_step)?)
     }
 
-    // println!("Max x: {}", dig.max_x);
-    // println!("Max y: {}", dig.max_y);
+    fn solve(plan: Vec<DigStep>, _params: &Params) -> Result<String, AocError> {
+        let mut vertices = Vec::with_capacity(plan.len());
+        let mut pos = Point::new(0, 0);
+
+        for DigStep {
+            dir,
+            distance: steps,
+            ..
+        } in plan
+        {
+            let delta = dir.delta();
+            pos = pos.with_offset(delta.x * steps, delta.y * steps);
+            vertices.push(pos);
+        }
 
-    // dig.map.print();
-    let mut count = dig.map.count('#');
-    dig.map.flood_fill(dig.map.top_left(), 'O');
-    count = count + dig.map.count('.');
+        // Same as part 2: count the cubes inside the trench polygon and on the trench itself
+        let count = lattice_points(&vertices);
 
-    Ok(count.to_string())
+        Ok(count.to_string())
+    }
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "R 6 (#70c710)
+                           D 5 (#0dc571)
+                           L 2 (#5713f0)
+                           D 2 (#d2c081)
+                           R 2 (#59c680)
+                           D 2 (#411b91)
+                           L 5 (#8ceee2)
+                           U 2 (#caa173)
+                           L 1 (#1b58a2)
+                           U 2 (#caa171)
+                           R 2 (#7807d2)
+                           U 3 (#a77fa3)
+                           L 2 (#015232)
+                           U 2 (#7a21e3)";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "R 6 (#70c710)
-                     D 5 (#0dc571)
-                     L 2 (#5713f0)
-                     D 2 (#d2c081)
-                     R 2 (#59c680)
-                     D 2 (#411b91)
-                     L 5 (#8ceee2)
-                     U 2 (#caa173)
-                     L 1 (#1b58a2)
-                     U 2 (#caa171)
-                     R 2 (#7807d2)
-                     U 3 (#a77fa3)
-                     L 2 (#015232)
-                     U 2 (#7a21e3)";
-        assert_eq!("62", process(input)?);
+        assert_eq!("62", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let plan = Part1::parse(EXAMPLE)?;
+        let printed = plan.iter().map(DigStep::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
         Ok(())
     }
 }
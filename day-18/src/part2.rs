@@ -1,115 +1,123 @@
-use crate::{
-    custom_error::AocError,
-    utils::{Direction, Point},
+use aoc_utils::{
+    lattice_points, number, parse_lines, symbol, token, Direction, IResult, NomError, Params,
+    Point, Solution,
+};
+use nom::{
+    bytes::complete::take_while_m_n,
+    character::complete::space1,
+    combinator::map,
+    error::context,
+    sequence::{delimited, preceded, tuple},
 };
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut vertices = Vec::with_capacity(input.lines().count());
-
-    let mut pos = Point::new(0, 0);
-    let mut perimeter = 0;
-    let mut directions = Vec::new();
-
-    for line in input.lines() {
-        let color = line.trim().split(' ').last().unwrap();
-        let color = color.trim_start_matches('(').trim_end_matches(')');
-        let (steps, dir) = parse_color(color);
-
-        match dir {
-            Direction::East => pos.x += steps,
-            Direction::South => pos.y += steps,
-            Direction::West => pos.x -= steps,
-            Direction::North => pos.y -= steps,
-        }
+use crate::custom_error::AocError;
 
-        perimeter += steps;
-        vertices.push(pos);
-        directions.push(dir);
-    }
+// One line of the dig plan: `R 6 (#70c710)`
+#[derive(Debug)]
+pub struct DigStep {
+    dir: Direction,
+    distance: i64,
+    color: u32,
+}
 
-    let shoelace_area = polygon_area(&vertices);
+impl std::fmt::Display for DigStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dir = match self.dir {
+            Direction::East => 'R',
+            Direction::West => 'L',
+            Direction::North => 'U',
+            _ => 'D',
+        };
+        write!(f, "{} {} (#{:06x})", dir, self.distance, self.color)
+    }
+}
 
-    // Manual solution on top of shoelace formula:
-    // let corner_area = corner_area(&directions);
-    // let corner_count = directions.len() as i64;
-    // let perimeter_without_corners = perimeter - corner_count;
-    // let result = shoelace_area + (perimeter_without_corners / 2) + (corner_count - corner_area);
+fn direction(input: &str) -> IResult<'_, Direction> {
+    map(symbol("RLUD", "`R`, `L`, `U` or `D`"), |dir| match dir {
+        'R' => Direction::East,
+        'L' => Direction::West,
+        'U' => Direction::North,
+        _ => Direction::South,
+    })(input)
+}
 
-    // Pick's theorem solution: https://en.wikipedia.org/wiki/Pick%27s_theorem
-    let result = shoelace_area + perimeter / 2 + 1;
+// `(#70c710)`
+fn color(input: &str) -> IResult<'_, u32> {
+    let hex = context(
+        "six hex digits",
+        take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit()),
+    );
+    map(delimited(token("(#"), hex, token(")")), |hex| {
+        u32::from_str_radix(hex, 16).unwrap()
+    })(input)
+}
 
-    Ok(result.to_string())
+fn dig_step(input: &str) -> IResult<'_, DigStep> {
+    let (rest, (dir, distance, color)) =
+        tuple((direction, preceded(space1, number), preceded(space1, color)))(input)?;
+    // The real instructions are hidden in the color, its last digit being the direction
+    if color & 0xf > 3 {
+        let digit = &input[input.len() - rest.len() - 2..][..1];
+        let err = NomError::at(digit, "a direction from 0 to 3");
+        return Err(nom::Err::Failure(err));
+    }
+    Ok((
+        rest,
+        DigStep {
+            dir,
+            distance,
+            color,
+        },
+    ))
 }
 
-// For each corner, determines the area of the interior angle we have already accounted for.
-// For an external corner, the area is 0.25.
-// For an internal corner, the area is 0.75.
-// Returns the sum of all corner areas.
-fn corner_area(directions: &[Direction]) -> i64 {
-    let mut sum = 0.0;
-
-    for corner in 0..directions.len() {
-        let prev = if corner == 0 {
-            directions.len() - 1
-        } else {
-            corner - 1
+impl DigStep {
+    // The distance and the direction encoded in the color
+    fn hidden_step(&self) -> (i64, Direction) {
+        let dir = match self.color & 0xf {
+            0 => Direction::East,
+            1 => Direction::South,
+            2 => Direction::West,
+            _ => Direction::North,
         };
-        let cur_dir = directions[corner];
-        let prev_dir = directions[prev];
-
-        let area = match (prev_dir, cur_dir) {
-            (Direction::East, Direction::South) => 0.25, // external
-            (Direction::East, Direction::North) => 0.75, // internal
-
-            (Direction::West, Direction::South) => 0.75, // internal
-            (Direction::West, Direction::North) => 0.25, // external
+        ((self.color >> 4) as i64, dir)
+    }
+}
 
-            (Direction::South, Direction::West) => 0.25, // external
-            (Direction::South, Direction::East) => 0.75, // internal
+pub struct Part2;
 
-            (Direction::North, Direction::East) => 0.25, // external
-            (Direction::North, Direction::West) => 0.75, // internal
+impl Solution for Part2 {
+    const DAY: u8 = 18;
+    const PART: u8 = 2;
 
-            _ => panic!("Unknown corner: {:?} {:?}", prev_dir, cur_dir),
-        };
+    type Input = Vec<DigStep>;
+    type Error = AocError;
 
-        sum += area;
+    fn parse(input: &str) -> Result<Vec<DigStep>, AocError> {
+        Ok(parse_lines(input, dig_step)?)
     }
 
-    return sum as i64;
-}
+    fn solve(plan: Vec<DigStep>, _params: &Params) -> Result<String, AocError> {
+        let mut vertices = Vec::with_capacity(plan.len());
+        let mut pos = Point::new(0, 0);
 
-// Shoelace formula (aka Gauss's area formula): https://en.wikipedia.org/wiki/Shoelace_formula
-fn polygon_area(vertices: &[Point<i64>]) -> i64 {
-    let mut area = 0;
+        for (steps, dir) in plan.iter().map(DigStep::hidden_step) {
+            let delta = dir.delta();
+            pos = pos.with_offset(delta.x * steps, delta.y * steps);
+            vertices.push(pos);
+        }
 
-    for i in 0..vertices.len() {
-        let (xi, yi) = (vertices[i].x, vertices[i].y);
-        let (x_next, y_next) = if i == vertices.len() - 1 {
-            (vertices[0].x, vertices[0].y)
-        } else {
-            (vertices[i + 1].x, vertices[i + 1].y)
-        };
+        // The trench is a polygon going through the centers of the dug out cubes, so the lagoon
+        // is made of all the lattice points inside that polygon or on its edges
+        let result = lattice_points(&vertices);
 
-        area += (xi * y_next) - (yi * x_next);
+        Ok(result.to_string())
     }
-
-    (area / 2).abs()
 }
 
-fn parse_color(color: &str) -> (i64, Direction) {
-    let distance_hex = color[1..6].to_string();
-    let distance = i64::from_str_radix(&distance_hex, 16).unwrap();
-    let dir_hex = color.chars().nth(6).unwrap();
-    let dir = match dir_hex {
-        '0' => Direction::East,
-        '1' => Direction::South,
-        '2' => Direction::West,
-        '3' => Direction::North,
-        _ => panic!("Unknown direction: {}", dir_hex),
-    };
-    (distance, dir)
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -117,74 +125,54 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_corner_area() {
-        let rectangle = vec![
-            Direction::East,
-            Direction::South,
-            Direction::West,
-            Direction::North,
-        ];
-        assert_eq!(1, corner_area(&rectangle));
-
-        //  #######
-        //  #.....#
-        //  #.###.#
-        //  #.#.#.#
-        //  ###.###
-        let pants = vec![
-            Direction::East,
-            Direction::South,
-            Direction::West,
-            Direction::North,
-            Direction::West,
-            Direction::South,
-            Direction::West,
-            Direction::North,
-        ];
-        assert_eq!(3, corner_area(&pants));
+    fn test_hidden_step() {
+        let step = |color| DigStep {
+            dir: Direction::East,
+            distance: 1,
+            color,
+        };
+        assert_eq!((461937, Direction::East), step(0x70c710).hidden_step());
+        assert_eq!((56407, Direction::South), step(0x0dc571).hidden_step());
+        assert_eq!((577262, Direction::West), step(0x8ceee2).hidden_step());
+        assert_eq!((829975, Direction::North), step(0xcaa173).hidden_step());
     }
 
+    const EXAMPLE: &str = "R 6 (#70c710)
+                           D 5 (#0dc571)
+                           L 2 (#5713f0)
+                           D 2 (#d2c081)
+                           R 2 (#59c680)
+                           D 2 (#411b91)
+                           L 5 (#8ceee2)
+                           U 2 (#caa173)
+                           L 1 (#1b58a2)
+                           U 2 (#caa171)
+                           R 2 (#7807d2)
+                           U 3 (#a77fa3)
+                           L 2 (#015232)
+                           U 2 (#7a21e3)";
+
     #[test]
-    fn test_polygon_area() {
-        let points = vec![
-            Point::new(0, 0),
-            Point::new(2, 0),
-            Point::new(2, 2),
-            Point::new(0, 2),
-        ];
-        assert_eq!(4, polygon_area(&points));
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("952408144115", process(EXAMPLE)?);
+        Ok(())
     }
 
     #[test]
-    fn test_parse_color() {
-        assert_eq!((461937, Direction::East), parse_color("#70c710"));
-        assert_eq!((56407, Direction::South), parse_color("#0dc571"));
-        assert_eq!((577262, Direction::West), parse_color("#8ceee2"));
-        assert_eq!((829975, Direction::North), parse_color("#caa173"));
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let plan = Part2::parse(EXAMPLE)?;
+        let printed = plan.iter().map(DigStep::to_string).collect::<Vec<_>>();
+        assert_eq!(EXAMPLE.lines().map(str::trim).collect::<Vec<_>>(), printed);
+        Ok(())
     }
 
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "R 6 (#70c710)
-                     D 5 (#0dc571)
-                     L 2 (#5713f0)
-                     D 2 (#d2c081)
-                     R 2 (#59c680)
-                     D 2 (#411b91)
-                     L 5 (#8ceee2)
-                     U 2 (#caa173)
-                     L 1 (#1b58a2)
-                     U 2 (#caa171)
-                     R 2 (#7807d2)
-                     U 3 (#a77fa3)
-                     L 2 (#015232)
-                     U 2 (#7a21e3)";
-        assert_eq!("952408144115", process(input)?);
-        Ok(())
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("R 6 (#70c710)\nD 5 (#0dc575)\n"));
+        assert_eq!("expected a direction from 0 to 3, found `5`", err.message);
+        assert_eq!(miette::SourceSpan::from((25, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("R 6 (#70c7)\n"));
+        assert_eq!("expected six hex digits, found `70c7`", err.message);
     }
 }
-
-// Submissions:
-// 92291574648776 - too high
-// 92291468914147 - correct
-// 92291363177928 - too low
@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use aoc_utils::{
+    blocks, list1, number, parse_all, parse_lines, symbol, token, IResult, NomError, ParseError,
+};
+use nom::{
+    branch::alt,
+    character::complete::alpha1,
+    combinator::{consumed, cut, map},
+    error::context,
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
+};
+
+// Workflow names are made of letters, `A` and `R` being the final decisions
+fn workflow_name(input: &str) -> IResult<'_, &str> {
+    context("a workflow name", alpha1)(input)
+}
+
+#[derive(Debug)]
+pub enum Rule {
+    Condition {
+        dim: char,
+        op: char,
+        value: i64,
+        dest: String,
+    },
+    Move(String),
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Condition {
+                dim,
+                op,
+                value,
+                dest,
+            } => write!(f, "{}{}{}:{}", dim, op, value, dest),
+            Self::Move(dest) => write!(f, "{}", dest),
+        }
+    }
+}
+
+// `a<2006:qkq` or just `qkq`. Once there's a category and a comparison, the rest of the
+// condition has to follow.
+pub fn rule(input: &str) -> IResult<'_, Rule> {
+    let condition = map(
+        tuple((
+            symbol("xmas", "a category (x, m, a or s)"),
+            symbol("<>", "`<` or `>`"),
+            cut(number),
+            cut(preceded(token(":"), workflow_name)),
+        )),
+        |(dim, op, value, dest)| Rule::Condition {
+            dim,
+            op,
+            value,
+            dest: dest.to_string(),
+        },
+    );
+    let fallback = map(workflow_name, |dest| Rule::Move(dest.to_string()));
+    alt((condition, fallback))(input)
+}
+
+impl Rule {
+    // The workflow or the final decision a rule sends parts to
+    pub fn dest_name(&self) -> &str {
+        match self {
+            Self::Move(dest) | Self::Condition { dest, .. } => dest,
+        }
+    }
+
+    pub fn dest(&self) -> String {
+        match self {
+            Self::Move(dest) | Self::Condition { dest, .. } => dest.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<Rule>,
+}
+
+impl std::fmt::Display for Workflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules = self.rules.iter().map(Rule::to_string).collect::<Vec<_>>();
+        write!(f, "{}{{{}}}", self.name, rules.join(","))
+    }
+}
+
+// px{a<2006:qkq,m>2090:A,rfg}
+pub fn workflow(input: &str) -> IResult<'_, Workflow> {
+    let rules = delimited(
+        token("{"),
+        list1(token(","), consumed(rule)),
+        symbol("}", "`,` or `}`"),
+    );
+    let (rest, (name, rules)) = pair(workflow_name, rules)(input)?;
+
+    // Parts that don't match any condition have to go somewhere
+    if let Some((last, Rule::Condition { .. })) = rules.last() {
+        return Err(nom::Err::Failure(NomError::at(last, "a fallback rule")));
+    }
+    let workflow = Workflow {
+        name: name.to_string(),
+        rules: rules.into_iter().map(|(_, rule)| rule).collect(),
+    };
+    Ok((rest, workflow))
+}
+
+#[derive(Debug)]
+pub struct Part {
+    pub dimensions: HashMap<char, i64>,
+}
+
+// Prints the ratings in the usual x, m, a, s order
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ratings = "xmas"
+            .chars()
+            .map(|dim| format!("{}={}", dim, self.dimensions[&dim]))
+            .collect::<Vec<_>>();
+        write!(f, "{{{}}}", ratings.join(","))
+    }
+}
+
+// {x=787,m=2655,a=1222,s=2876}
+fn part(input: &str) -> IResult<'_, Part> {
+    let rating = separated_pair(
+        symbol("xmas", "a category (x, m, a or s)"),
+        token("="),
+        number,
+    );
+    let (rest, (text, ratings)) =
+        consumed(delimited(token("{"), list1(token(","), rating), token("}")))(input)?;
+    let dimensions = ratings.into_iter().collect::<HashMap<_, _>>();
+
+    // Rules can look at any of the categories
+    if let Some(missing) = "xmas".chars().find(|dim| !dimensions.contains_key(dim)) {
+        let expected = format!("the `{}` rating", missing);
+        return Err(nom::Err::Failure(NomError::at(text, expected)));
+    }
+    Ok((rest, Part { dimensions }))
+}
+
+#[derive(Default)]
+pub struct System {
+    workflows: Vec<String>,
+    pub workflow_map: HashMap<String, Workflow>,
+}
+
+// The workflows in the order they came in
+impl std::fmt::Display for System {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let workflows = self
+            .workflows
+            .iter()
+            .map(|name| self.workflow_map[name].to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", workflows.join("\n"))
+    }
+}
+
+impl System {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_workflow(&mut self, workflow: Workflow) {
+        let name = workflow.name.clone();
+        self.workflows.push(name.clone());
+        self.workflow_map.insert(name, workflow);
+    }
+
+    // Makes sure every part ends up accepted or rejected: the workflows all exist and
+    // don't send parts around in circles. Errors point at the line of the workflow.
+    fn check(&self, lines: &HashMap<String, &str>, input: &str) -> Result<(), ParseError> {
+        if !self.workflow_map.contains_key("in") {
+            return Err(ParseError::new(
+                "no `in` workflow",
+                input,
+                "expected a workflow to start from",
+            ));
+        }
+
+        for workflow in self.workflow_map.values() {
+            for rule in &workflow.rules {
+                let dest = rule.dest_name();
+                if dest != "A" && dest != "R" && !self.workflow_map.contains_key(dest) {
+                    return Err(ParseError::new(
+                        format!("unknown workflow `{}`", dest),
+                        lines[&workflow.name],
+                        format!("sends parts to `{}`", dest),
+                    ));
+                }
+            }
+        }
+
+        let mut visited = HashMap::new();
+        match self.find_loop("in", &mut visited) {
+            Some(name) => Err(ParseError::new(
+                format!("workflow `{}` is part of a loop", name),
+                lines[name],
+                "parts can come back here",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // Depth-first search for a workflow that leads back to itself (`false` marks the
+    // workflows still being explored)
+    fn find_loop<'a>(
+        &'a self,
+        name: &'a str,
+        visited: &mut HashMap<&'a str, bool>,
+    ) -> Option<&'a str> {
+        match visited.get(name) {
+            Some(true) => return None,
+            Some(false) => return Some(name),
+            None => {}
+        }
+        let workflow = self.workflow_map.get(name)?;
+
+        visited.insert(name, false);
+        for rule in &workflow.rules {
+            if let Some(found) = self.find_loop(rule.dest_name(), visited) {
+                return Some(found);
+            }
+        }
+        visited.insert(name, true);
+        None
+    }
+}
+
+// The workflows, then a blank line and the parts to sort
+pub fn parse_system(input: &str) -> Result<(System, Vec<Part>), ParseError> {
+    let mut sys = System::new();
+
+    let blocks = blocks(input);
+    let workflows = blocks.first().copied().unwrap_or(input);
+    let mut lines = HashMap::new();
+    for line in workflows.lines().map(str::trim) {
+        let workflow = parse_all(line, workflow)?;
+        lines.insert(workflow.name.clone(), line);
+        sys.add_workflow(workflow);
+    }
+    sys.check(&lines, input)?;
+
+    let parts = match blocks.get(1) {
+        Some(parts) => parse_lines(parts, part)?,
+        None => Vec::new(),
+    };
+    Ok((sys, parts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+                           pv{a>1716:R,A}
+                           lnx{m>1548:A,A}
+                           rfg{s<537:gd,x>2440:R,A}
+                           qs{s>3448:A,lnx}
+                           qkq{x<1416:A,crn}
+                           crn{x>2662:A,R}
+                           in{s<1351:px,qqz}
+                           qqz{s>2770:qs,m<1801:hdj,R}
+                           gd{a>3333:R,R}
+                           hdj{m>838:A,pv}
+
+                           {x=787,m=2655,a=1222,s=2876}
+                           {x=1679,m=44,a=2067,s=496}
+                           {x=2036,m=264,a=79,s=2244}
+                           {x=2461,m=1339,a=466,s=291}
+                           {x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let (sys, parts) = parse_system(EXAMPLE)?;
+        let parts = parts.iter().map(Part::to_string).collect::<Vec<_>>();
+        let printed = format!("{}\n\n{}", sys, parts.join("\n"));
+        let expected = EXAMPLE.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(expected.join("\n"), printed);
+        Ok(())
+    }
+}
@@ -1,47 +1,11 @@
-use itertools::Itertools;
-use std::collections::HashMap;
-
-use crate::custom_error::AocError;
-
-#[derive(Debug)]
-enum Rule {
-    Condition {
-        dim: char,
-        op: char,
-        value: i64,
-        dest: String,
-    },
-    Move(String),
-}
-
-impl Rule {
-    fn from_str(s: &str) -> Self {
-        if !s.contains(':') {
-            return Self::Move(s.to_string());
-        }
+use aoc_utils::{Params, Solution};
 
-        // a<2006:qkq
-        let mut parts = s.split(':');
-        let cond = parts.next().unwrap();
-        let dest = parts.last().unwrap().to_string();
-        let dimension = cond.chars().nth(0).unwrap();
-        let op = cond.chars().nth(1).unwrap();
-        let value = cond[2..].parse::<i64>().unwrap();
-
-        Self::Condition {
-            dim: dimension,
-            op,
-            value,
-            dest,
-        }
-    }
-
-    fn dest(&self) -> String {
-        match self {
-            Self::Move(dest) | Self::Condition { dest, .. } => dest.clone(),
-        }
-    }
+use crate::{
+    custom_error::AocError,
+    parse::{parse_system, Part, Rule, System, Workflow},
+};
 
+impl Rule {
     fn matches(&self, part: &Part) -> bool {
         match self {
             Self::Move { .. } => true,
@@ -57,25 +21,7 @@ impl Rule {
     }
 }
 
-#[derive(Debug)]
-struct Workflow {
-    name: String,
-    rules: Vec<Rule>,
-}
-
 impl Workflow {
-    // px{a<2006:qkq,m>2090:A,rfg}
-    fn from_str(input: &str) -> Self {
-        let mut parts = input.trim().split('{');
-        let name = parts.next().unwrap().to_string();
-        let rules_str = parts.next().unwrap().strip_suffix('}').unwrap();
-        let rules = rules_str
-            .split(',')
-            .map(|r| Rule::from_str(r))
-            .collect_vec();
-        Self { name, rules }
-    }
-
     fn process(&self, part: &Part) -> &Rule {
         for rule in &self.rules {
             if rule.matches(part) {
@@ -83,54 +29,17 @@ impl Workflow {
             }
         }
 
-        panic!(
-            "No matching rule found for part {:?} in workflow {:?}",
-            part, self.name
-        );
+        unreachable!("workflows end with a fallback rule");
     }
 }
 
-#[derive(Debug)]
-struct Part {
-    dimensions: HashMap<char, i64>,
-}
-
 impl Part {
-    // {x=787,m=2655,a=1222,s=2876}
-    fn from_str(l: &str) -> Self {
-        let l = l
-            .trim()
-            .strip_prefix('{')
-            .unwrap()
-            .strip_suffix('}')
-            .unwrap();
-        let mut dimensions = HashMap::new();
-        for part in l.split(',') {
-            let mut parts = part.split('=');
-            let dim = parts.next().unwrap().chars().nth(0).unwrap();
-            let value = parts.next().unwrap().parse::<i64>().unwrap();
-            dimensions.insert(dim, value);
-        }
-        Self { dimensions }
-    }
-
     fn rating(&self) -> i64 {
         self.dimensions.values().sum()
     }
 }
 
-struct System {
-    workflows: Vec<String>,
-    workflow_map: HashMap<String, Workflow>,
-}
-
 impl System {
-    fn add_workflow(&mut self, workflow: Workflow) {
-        let name = workflow.name.clone();
-        self.workflows.push(name.clone());
-        self.workflow_map.insert(name, workflow);
-    }
-
     // Returns true if the part is accepted, false if rejected.
     fn process_part(&self, part: &Part) -> bool {
         let mut cur_name = "in".to_string();
@@ -149,56 +58,91 @@ impl System {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut sys = System {
-        workflows: Vec::new(),
-        workflow_map: HashMap::new(),
-    };
-
-    let mut lines = input.lines();
-    while let Some(line) = lines.next() {
-        if line.is_empty() {
-            break;
-        }
-        sys.add_workflow(Workflow::from_str(line));
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 19;
+    const PART: u8 = 1;
+
+    type Input = (System, Vec<Part>);
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<(System, Vec<Part>), AocError> {
+        parse_system(input).map_err(|err| err.with_source(input).into())
     }
-    let parts = lines.map(|l| Part::from_str(l)).collect_vec();
 
-    let mut total_rating = 0;
-    for part in parts {
-        if sys.process_part(&part) {
-            total_rating += part.rating();
+    fn solve((sys, parts): (System, Vec<Part>), _params: &Params) -> Result<String, AocError> {
+        let mut total_rating = 0;
+        for part in parts {
+            if sys.process_part(&part) {
+                total_rating += part.rating();
+            }
         }
+
+        Ok(total_rating.to_string())
     }
+}
 
-    Ok(total_rating.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+                           pv{a>1716:R,A}
+                           lnx{m>1548:A,A}
+                           rfg{s<537:gd,x>2440:R,A}
+                           qs{s>3448:A,lnx}
+                           qkq{x<1416:A,crn}
+                           crn{x>2662:A,R}
+                           in{s<1351:px,qqz}
+                           qqz{s>2770:qs,m<1801:hdj,R}
+                           gd{a>3333:R,R}
+                           hdj{m>838:A,pv}
+
+                           {x=787,m=2655,a=1222,s=2876}
+                           {x=1679,m=44,a=2067,s=496}
+                           {x=2036,m=264,a=79,s=2244}
+                           {x=2461,m=1339,a=466,s=291}
+                           {x=2127,m=1623,a=2188,s=1013}";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "px{a<2006:qkq,m>2090:A,rfg}
-                     pv{a>1716:R,A}
-                     lnx{m>1548:A,A}
-                     rfg{s<537:gd,x>2440:R,A}
-                     qs{s>3448:A,lnx}
-                     qkq{x<1416:A,crn}
-                     crn{x>2662:A,R}
-                     in{s<1351:px,qqz}
-                     qqz{s>2770:qs,m<1801:hdj,R}
-                     gd{a>3333:R,R}
-                     hdj{m>838:A,pv}
-
-                     {x=787,m=2655,a=1222,s=2876}
-                     {x=1679,m=44,a=2067,s=496}
-                     {x=2036,m=264,a=79,s=2244}
-                     {x=2461,m=1339,a=466,s=291}
-                     {x=2127,m=1623,a=2188,s=1013}";
-        assert_eq!("19114", process(input)?);
+        assert_eq!("19114", process(EXAMPLE)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "in{s<1351:px,A}\npx{q<2006:A,R}\n\n{x=787,m=2655,a=1222,s=2876}\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("expected `,` or `}`, found `<`", err.message);
+        assert_eq!(miette::SourceSpan::from((20, 1)), err.span);
+
+        let input = "in{s<1351:px,A}\npx{a<2006:A,m>1:R}\n\n{x=787,m=2655,a=1222,s=2876}\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("expected a fallback rule, found `m>1:R`", err.message);
+        assert_eq!(miette::SourceSpan::from((28, 5)), err.span);
+
+        let input = "in{s<1351:px,A}\n\n{x=787,m=2655,a=1222,s=2876}\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("unknown workflow `px`", err.message);
+        assert_eq!(miette::SourceSpan::from((0, 15)), err.span);
+
+        let input = "in{s<1351:px,A}\npx{a<10:in,R}\n\n{x=787,m=2655,a=1222,s=2876}\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("workflow `in` is part of a loop", err.message);
+
+        let input = "in{A}\n\n{x=787,m=2655,a=1222}\n";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!(
+            "expected the `s` rating, found `{x=787,m=2655,a=1222}`",
+            err.message
+        );
+        assert_eq!(miette::SourceSpan::from((7, 21)), err.span);
+    }
 }
@@ -1,168 +1,51 @@
-use itertools::Itertools;
-use std::{collections::HashMap, ops::Range};
+use aoc_utils::{IntervalBox, Params, Solution};
 
-use crate::custom_error::AocError;
-
-#[derive(Debug)]
-enum Rule {
-    Condition {
-        dim: char,
-        op: char,
-        value: u128,
-        dest: String,
-    },
-    Move(String),
-}
+use crate::{
+    custom_error::AocError,
+    parse::{parse_system, Rule, System},
+};
 
 impl Rule {
-    fn from_str(s: &str) -> Self {
-        if !s.contains(':') {
-            return Self::Move(s.to_string());
-        }
-
-        // a<2006:qkq
-        let mut parts = s.split(':');
-        let cond = parts.next().unwrap();
-        let dest = parts.last().unwrap().to_string();
-        let dimension = cond.chars().nth(0).unwrap();
-        let op = cond.chars().nth(1).unwrap();
-        let value = cond[2..].parse::<u128>().unwrap();
-
-        Self::Condition {
-            dim: dimension,
-            op,
-            value,
-            dest,
-        }
-    }
-
-    fn dest(&self) -> String {
+    // Splits the part range into the parts that match the rule and the ones that don't
+    fn split(&self, part_range: &PartRange) -> (Option<PartRange>, Option<PartRange>) {
         match self {
-            Self::Move(dest) | Self::Condition { dest, .. } => dest.clone(),
-        }
-    }
-
-    fn supported_value_range(&self) -> Range<u128> {
-        match self {
-            Self::Move(_) => 1..4001,
-            Self::Condition { op, value, .. } => match op {
-                '<' => 1..*value,
-                '>' => *value + 1..4001,
-                _ => panic!("Unknown operator {} for {:?}", op, self),
-            },
-        }
-    }
-
-    // Returns a part range supported by the rule (a subset of the given range)
-    fn supported_range(&self, part_range: &PartRange) -> Option<PartRange> {
-        match self {
-            Self::Move(_) => Some(part_range.clone()),
-            Self::Condition { dim, .. } => {
-                let mut supported_range = part_range.clone();
-
-                // Get the current limits on the given dimension
-                let dim_range = supported_range.dimensions.get(dim).unwrap();
-
-                // If the current range is empty, the range is not supported by the rule
-                if dim_range.start == dim_range.end {
-                    return None;
+            Self::Move(_) => (Some(part_range.clone()), None),
+            Self::Condition { dim, op, value, .. } => {
+                let dim = dimension_index(*dim);
+                let value = *value as u128;
+                match op {
+                    '<' => part_range.split_at(dim, value),
+                    '>' => {
+                        let (below, above) = part_range.split_at(dim, value + 1);
+                        (above, below)
+                    }
+                    _ => panic!("Unknown operator {} for {:?}", op, self),
                 }
-
-                // Create a range defined by the rule
-                let op_range = self.supported_value_range();
-
-                // Intersect the current range with the rule range
-                let supported_value_range =
-                    dim_range.start.max(op_range.start)..dim_range.end.min(op_range.end);
-
-                // If the intersection is empty, the range is not supported by the rule
-                if supported_value_range.start == supported_value_range.end {
-                    return None;
-                }
-
-                // Update the range for the given dimension
-                supported_range
-                    .dimensions
-                    .insert(*dim, supported_value_range);
-
-                Some(supported_range)
             }
         }
     }
 }
 
-#[derive(Debug)]
-struct Workflow {
-    name: String,
-    rules: Vec<Rule>,
-}
-
-impl Workflow {
-    // px{a<2006:qkq,m>2090:A,rfg}
-    fn from_str(input: &str) -> Self {
-        let mut parts = input.trim().split('{');
-        let name = parts.next().unwrap().to_string();
-        let rules_str = parts.next().unwrap().strip_suffix('}').unwrap();
-        let rules = rules_str
-            .split(',')
-            .map(|r| Rule::from_str(r))
-            .collect_vec();
-        Self { name, rules }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct PartRange {
-    dimensions: HashMap<char, Range<u128>>,
-}
-
-impl PartRange {
-    fn full() -> Self {
-        let mut dimensions = HashMap::new();
-        for dim in ['x', 'm', 'a', 's'] {
-            dimensions.insert(dim, 1..4001 as u128);
-        }
-        Self { dimensions }
-    }
-
-    fn size(&self) -> u128 {
-        self.dimensions.values().map(|r| r.end - r.start).product()
-    }
-
-    fn exclude(&self, other: Self) -> Self {
-        let mut dimensions = HashMap::new();
-        for (dim, range) in &self.dimensions {
-            let other_range = other.dimensions.get(dim).unwrap();
-            let mut new_range = range.clone();
+// Ranges of ratings for each category of a part (x, m, a and s)
+type PartRange = IntervalBox<u128, 4>;
 
-            if other_range.start > range.start {
-                new_range.end = other_range.start;
-            }
-            if other_range.end < range.end {
-                new_range.start = other_range.end;
-            }
-
-            dimensions.insert(*dim, new_range);
-        }
-        Self { dimensions }
+fn dimension_index(dim: char) -> usize {
+    match dim {
+        'x' => 0,
+        'm' => 1,
+        'a' => 2,
+        's' => 3,
+        _ => panic!("Unknown dimension {}", dim),
     }
 }
 
-struct System {
-    workflows: Vec<String>,
-    workflow_map: HashMap<String, Workflow>,
+fn full_part_range() -> PartRange {
+    IntervalBox::new([1..4001, 1..4001, 1..4001, 1..4001])
 }
 
 impl System {
-    fn add_workflow(&mut self, workflow: Workflow) {
-        let name = workflow.name.clone();
-        self.workflows.push(name.clone());
-        self.workflow_map.insert(name, workflow);
-    }
-
     // Returns a number of different parts supported by the workflow from a given range
     fn supported_by_workflow(&self, name: &String, part_range: &PartRange) -> u128 {
-        let mut part_range = part_range.clone();
         if name == "R" {
             return 0;
         }
@@ -173,77 +56,87 @@ impl System {
 
         let workflow = self.workflow_map.get(name).unwrap();
         let mut supported = 0u128;
+        let mut part_range = Some(part_range.clone());
         for rule in &workflow.rules {
-            if let Some(supported_range) = rule.supported_range(&part_range) {
-                supported += self.supported_by_workflow(&rule.dest(), &supported_range);
-                part_range = part_range.exclude(supported_range);
+            // Whatever is not matched by a rule goes on to the next one
+            let Some(current) = part_range else {
+                break;
+            };
+            let (matched, rest) = rule.split(&current);
+            if let Some(matched) = matched {
+                supported += self.supported_by_workflow(&rule.dest(), &matched);
             }
+            part_range = rest;
         }
 
-        return supported;
+        supported
     }
+}
 
-    fn new() -> Self {
-        Self {
-            workflows: Vec::new(),
-            workflow_map: HashMap::new(),
-        }
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 19;
+    const PART: u8 = 2;
+
+    type Input = System;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<System, AocError> {
+        let (sys, _) = parse_system(input).map_err(|err| err.with_source(input))?;
+        Ok(sys)
     }
 
-    fn from_str(input: &str) -> Self {
-        let mut sys = Self::new();
-        for line in input.lines() {
-            if line.is_empty() {
-                break;
-            }
-            sys.add_workflow(Workflow::from_str(line));
-        }
-        sys
+    fn solve(sys: System, _params: &Params) -> Result<String, AocError> {
+        let total_supported = sys.supported_by_workflow(&"in".to_string(), &full_part_range());
+        Ok(total_supported.to_string())
     }
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let sys = System::from_str(input);
-    let total_supported = sys.supported_by_workflow(&"in".to_string(), &PartRange::full());
-    Ok(total_supported.to_string())
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_utils::parse_all;
+
+    use crate::parse::{self, workflow};
+
     use super::*;
 
     #[test]
     fn test_rule_supported_range() {
-        let rule = Rule::from_str("a<2006:qkq");
-        let part_range = PartRange::full();
-        let supported_range = rule.supported_range(&part_range).unwrap();
-        assert_eq!(1..2006, *supported_range.dimensions.get(&'a').unwrap());
+        let rule = parse_all("a<2006:qkq", parse::rule).unwrap();
+        let part_range = full_part_range();
+        let (supported_range, rest) = rule.split(&part_range);
+        assert_eq!(1..2006, *supported_range.unwrap().dim(2));
+        assert_eq!(2006..4001, *rest.unwrap().dim(2));
+
+        let rule = parse_all("a>2006:qkq", parse::rule).unwrap();
+        let (supported_range, rest) = rule.split(&part_range);
+        assert_eq!(2007..4001, *supported_range.unwrap().dim(2));
+        assert_eq!(1..2007, *rest.unwrap().dim(2));
 
-        let rule = Rule::from_str("a>2006:qkq");
-        let part_range = PartRange::full();
-        let supported_range = rule.supported_range(&part_range).unwrap();
-        assert_eq!(2007..4001, *supported_range.dimensions.get(&'a').unwrap());
+        let rule = parse_all("qkq", parse::rule).unwrap();
+        assert_eq!((Some(part_range.clone()), None), rule.split(&part_range));
     }
 
     #[test]
     fn test_range_size() {
-        let part_range = PartRange::full();
+        let part_range = full_part_range();
         assert_eq!(4000 * 4000 * 4000 * 4000, part_range.size());
 
-        let part_range = PartRange {
-            dimensions: vec![('x', 1..5), ('m', 1..7), ('a', 1..9), ('s', 1..11)]
-                .into_iter()
-                .collect(),
-        };
+        let part_range = PartRange::new([1..5, 1..7, 1..9, 1..11]);
         assert_eq!(4 * 6 * 8 * 10, part_range.size());
     }
 
     #[test]
     fn test_supported_by_workflow_simple() {
         let mut sys = System::new();
-        sys.add_workflow(Workflow::from_str("px{a<2006:R,A}"));
-        let part_range = PartRange::full();
+        sys.add_workflow(parse_all("px{a<2006:R,A}", workflow).unwrap());
+        let part_range = full_part_range();
         let supported = sys.supported_by_workflow(&"px".to_string(), &part_range);
         // x = 2006..4001, m = 1..4001, a = 1..4001, s = 1..4001
         assert_eq!(1995 * 4000 * 4000 * 4000, supported);
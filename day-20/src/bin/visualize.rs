@@ -1,9 +1,13 @@
+use aoc_utils::InputLoader;
 use day_20::visualize::process;
 use miette::Context;
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
-    let file = include_str!("../../input1.txt");
-    process(file).context("visualize")?;
+    let input = InputLoader::new(20, 1)
+        .path(std::env::args().nth(1))
+        .dir(env!("CARGO_MANIFEST_DIR"))
+        .load()?;
+    process(&input.text).context("visualize")?;
     Ok(())
 }
@@ -1,6 +1,6 @@
 pub mod custom_error;
 
+pub mod parse;
 pub mod part1;
 pub mod part2;
-pub mod utils;
 pub mod visualize;
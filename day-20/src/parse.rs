@@ -0,0 +1,78 @@
+use aoc_utils::{list1, name, symbol, token, ws, IResult};
+use nom::{
+    combinator::{map, opt},
+    sequence::{preceded, tuple},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleType {
+    Broadcast,
+    FlipFlop,
+    Conjunction,
+}
+
+// A line of the configuration, like `%a -> inv, con`
+#[derive(Debug)]
+pub struct ModuleConfig {
+    pub module_type: ModuleType,
+    pub name: String,
+    pub conns: Vec<String>,
+}
+
+impl std::fmt::Display for ModuleConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let prefix = match self.module_type {
+            ModuleType::Broadcast => "",
+            ModuleType::FlipFlop => "%",
+            ModuleType::Conjunction => "&",
+        };
+        write!(f, "{}{} -> {}", prefix, self.name, self.conns.join(", "))
+    }
+}
+
+// The module type is in front of the name, a plain name is the broadcaster
+pub fn module(input: &str) -> IResult<'_, ModuleConfig> {
+    let module_type = map(opt(symbol("%&", "`%` or `&`")), |prefix| match prefix {
+        Some('%') => ModuleType::FlipFlop,
+        Some(_) => ModuleType::Conjunction,
+        None => ModuleType::Broadcast,
+    });
+    let conns = list1(token(", "), map(name, str::to_string));
+    map(
+        tuple((module_type, name, preceded(ws(token("->")), conns))),
+        |(module_type, name, conns)| ModuleConfig {
+            module_type,
+            name: name.to_string(),
+            conns,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::parse_lines;
+
+    use super::*;
+
+    const EXAMPLE: &str = "broadcaster -> a, b, c
+                           %a -> b
+                           %b -> c
+                           %c -> inv
+                           &inv -> a";
+
+    const EXAMPLE_HARDER: &str = "broadcaster -> a
+                                  %a -> inv, con
+                                  &inv -> b
+                                  %b -> con
+                                  &con -> output";
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        for input in [EXAMPLE, EXAMPLE_HARDER] {
+            let configs = parse_lines(input, module)?;
+            let printed = configs.iter().map(ModuleConfig::to_string);
+            assert!(input.lines().map(str::trim).eq(printed));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
 use std::collections::{HashMap, VecDeque};
 
-use crate::custom_error::AocError;
+use aoc_utils::{parse_lines, Params, ParseError, Solution};
+
+use crate::{
+    custom_error::AocError,
+    parse::{module, ModuleConfig, ModuleType},
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum SignalType {
@@ -43,7 +48,7 @@ impl Signal {
 trait Node {
     fn name(&self) -> &str;
     fn out_conns(&self) -> &[String];
-    fn set_incoming(&mut self, incoming: &Vec<String>);
+    fn set_incoming(&mut self, incoming: &[String]);
     fn process_signal(&mut self, signal: &Signal) -> Vec<Signal>;
 }
 
@@ -71,7 +76,7 @@ impl Node for BroadcastNode {
         &self.out_conns
     }
 
-    fn set_incoming(&mut self, _: &Vec<String>) {}
+    fn set_incoming(&mut self, _: &[String]) {}
 
     fn process_signal(&mut self, signal: &Signal) -> Vec<Signal> {
         signal.broadcast(&self.out_conns, None)
@@ -105,7 +110,7 @@ impl Node for FlipFlopNode {
         &self.out_conns
     }
 
-    fn set_incoming(&mut self, _: &Vec<String>) {}
+    fn set_incoming(&mut self, _: &[String]) {}
 
     fn process_signal(&mut self, signal: &Signal) -> Vec<Signal> {
         if signal.signal_type == SignalType::High {
@@ -150,7 +155,7 @@ impl Node for ConjunctNode {
         &self.out_conns
     }
 
-    fn set_incoming(&mut self, in_names: &Vec<String>) {
+    fn set_incoming(&mut self, in_names: &[String]) {
         for name in in_names {
             self.in_state.insert(name.to_string(), false);
         }
@@ -172,17 +177,26 @@ impl Node for ConjunctNode {
 
 type BoxedNode = Box<dyn Node>;
 
-struct Network {
+// Builds this part's node for a line of the configuration
+fn into_node(config: ModuleConfig) -> BoxedNode {
+    match config.module_type {
+        ModuleType::Broadcast => Box::new(BroadcastNode::new(&config.name, config.conns)),
+        ModuleType::FlipFlop => Box::new(FlipFlopNode::new(&config.name, config.conns)),
+        ModuleType::Conjunction => Box::new(ConjunctNode::new(&config.name, config.conns)),
+    }
+}
+
+pub struct Network {
     nodes: HashMap<String, BoxedNode>,
     pulse_counts: HashMap<SignalType, u64>,
 }
 
 impl Network {
-    fn from_str(s: &str) -> Self {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         let mut nodes: HashMap<String, BoxedNode> = HashMap::new();
         let mut in_conns: HashMap<String, Vec<String>> = HashMap::new();
-        for line in s.lines() {
-            let node = Self::node_from_str(line.trim());
+        for config in parse_lines(s.trim(), module)? {
+            let node = into_node(config);
             for conn in node.out_conns() {
                 in_conns
                     .entry(conn.clone())
@@ -197,25 +211,10 @@ impl Network {
             node.set_incoming(&incoming_conns);
         }
 
-        Self {
+        Ok(Self {
             nodes,
             pulse_counts: HashMap::new(),
-        }
-    }
-
-    fn node_from_str(s: &str) -> BoxedNode {
-        let mut parts = s.split(" -> ");
-        let name_with_type = parts.next().unwrap();
-        let node_type = name_with_type.chars().nth(0).unwrap();
-
-        let conns = parts.next().unwrap();
-        let conns = conns.split(", ").map(|s| s.to_string()).collect();
-
-        match node_type {
-            '&' => Box::new(ConjunctNode::new(&name_with_type[1..], conns)),
-            '%' => Box::new(FlipFlopNode::new(&name_with_type[1..], conns)),
-            _ => Box::new(BroadcastNode::new(&name_with_type, conns)),
-        }
+        })
     }
 
     fn score(&self) -> u64 {
@@ -245,44 +244,63 @@ impl Network {
     }
 }
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 20;
+    const PART: u8 = 1;
+
+    type Input = Network;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<Network, AocError> {
+        Ok(Network::from_str(input).map_err(|err| err.with_source(input))?)
+    }
+
+    fn solve(mut net: Network, _params: &Params) -> Result<String, AocError> {
+        for _ in 0..1000 {
+            net.press_button();
+        }
+        Ok(net.score().to_string())
+    }
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut net = Network::from_str(input);
-    for _ in 0..1000 {
-        net.press_button();
-    }
-    Ok(net.score().to_string())
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "broadcaster -> a, b, c
+                           %a -> b
+                           %b -> c
+                           %c -> inv
+                           &inv -> a";
+
+    const EXAMPLE_HARDER: &str = "broadcaster -> a
+                                  %a -> inv, con
+                                  &inv -> b
+                                  %b -> con
+                                  &con -> output";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "broadcaster -> a, b, c
-                     %a -> b
-                     %b -> c
-                     %c -> inv
-                     &inv -> a";
-        assert_eq!("32000000", process(input)?);
+        assert_eq!("32000000", process(EXAMPLE)?);
         Ok(())
     }
 
     #[test]
     fn test_process_harder() {
-        let input = "broadcaster -> a
-                     %a -> inv, con
-                     &inv -> b
-                     %b -> con
-                     &con -> output";
-        assert_eq!("11687500", process(input).unwrap());
+        assert_eq!("11687500", process(EXAMPLE_HARDER).unwrap());
     }
 
     #[test]
     fn test_single_and() {
         let mut node = ConjunctNode::new("and", vec!["output".to_string()]);
-        node.set_incoming(&vec!["a".to_string()]);
+        node.set_incoming(&["a".to_string()]);
 
         let high = Signal {
             src: "a".to_string(),
@@ -314,10 +332,15 @@ mod tests {
         let res = node.process_signal(&high);
         println!("{:?}", res);
     }
-}
 
-// Submissions:
-// 672744417 - too low
-// 697264874 - too low
-// 697264974 - too low
-// 739960225 - correct
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("broadcaster -> a\n%a -> b!\n"));
+        assert_eq!("expected the end of the line, found `!`", err.message);
+        assert_eq!(miette::SourceSpan::from((24, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("broadcaster -> a\n%a b\n"));
+        assert_eq!("expected `->`, found `b`", err.message);
+        assert_eq!(miette::SourceSpan::from((20, 1)), err.span);
+    }
+}
@@ -1,7 +1,12 @@
 use fxhash::FxHashMap;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
 
-use crate::custom_error::AocError;
+use aoc_utils::{parse_lines, Params, ParseError, Solution};
+
+use crate::{
+    custom_error::AocError,
+    parse::{module, ModuleConfig, ModuleType},
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum SignalType {
@@ -35,7 +40,7 @@ impl Signal {
 trait Node {
     fn name(&self) -> &str;
     fn out_conns(&self) -> &[String];
-    fn set_incoming(&mut self, _: &Vec<String>) {}
+    fn set_incoming(&mut self, _: &[String]) {}
     fn process_signal(&mut self, signal: &Signal) -> Vec<Signal>;
 }
 
@@ -138,7 +143,7 @@ impl Node for ConjunctNode {
         &self.out_conns
     }
 
-    fn set_incoming(&mut self, in_names: &Vec<String>) {
+    fn set_incoming(&mut self, in_names: &[String]) {
         for name in in_names {
             self.in_state.insert(name.to_string(), false);
         }
@@ -160,17 +165,36 @@ impl Node for ConjunctNode {
 
 type BoxedNode = Box<dyn Node>;
 
-struct Network {
+// Builds this part's node for a line of the configuration
+fn into_node(config: ModuleConfig) -> BoxedNode {
+    match config.module_type {
+        ModuleType::Broadcast => Box::new(BroadcastNode::new(&config.name, config.conns)),
+        ModuleType::FlipFlop => Box::new(FlipFlopNode::new(&config.name, config.conns)),
+        ModuleType::Conjunction => Box::new(ConjunctNode::new(&config.name, config.conns)),
+    }
+}
+
+// Pressing the button more times than this without every probe firing means the probes
+// don't run in cycles short enough to find
+const MAX_PRESSES: u64 = 1_000_000;
+
+pub struct Network {
     nodes: FxHashMap<String, BoxedNode>,
     pulse_counts: FxHashMap<SignalType, u64>,
+    // The inputs of the conjunction feeding `rx`, each of which sends high pulses in a cycle
+    probes: Vec<String>,
 }
 
 impl Network {
-    fn from_str(s: &str) -> Self {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         let mut nodes: FxHashMap<String, BoxedNode> = FxHashMap::default();
         let mut in_conns: FxHashMap<String, Vec<String>> = FxHashMap::default();
-        for line in s.lines() {
-            let node = Self::node_from_str(line.trim());
+        let mut conjunctions = Vec::new();
+        for config in parse_lines(s.trim(), module)? {
+            if config.module_type == ModuleType::Conjunction {
+                conjunctions.push(config.name.clone());
+            }
+            let node = into_node(config);
             for conn in node.out_conns() {
                 in_conns
                     .entry(conn.clone())
@@ -180,34 +204,45 @@ impl Network {
             nodes.insert(node.name().to_string(), node);
         }
 
+        if !nodes.contains_key("broadcaster") {
+            return Err(ParseError::new(
+                "no broadcaster",
+                s,
+                "expected a `broadcaster -> ...` line",
+            ));
+        }
+
+        // `rx` gets a low pulse once all the inputs of the conjunction feeding it last sent
+        // a high one
+        let probes = match in_conns.get("rx").map(Vec::as_slice) {
+            Some([feeder]) if conjunctions.contains(feeder) => {
+                in_conns.get(feeder).cloned().unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+        if probes.is_empty() {
+            return Err(ParseError::new(
+                "no probes to watch",
+                s,
+                "expected a single conjunction with inputs sending to `rx`",
+            ));
+        }
+
         for node in nodes.values_mut() {
             let incoming_conns = in_conns.remove(node.name()).unwrap_or_default();
             node.set_incoming(&incoming_conns);
         }
 
-        Self {
+        Ok(Self {
             nodes,
             pulse_counts: FxHashMap::default(),
-        }
-    }
-
-    fn node_from_str(s: &str) -> BoxedNode {
-        let mut parts = s.split(" -> ");
-        let name_with_type = parts.next().unwrap();
-        let node_type = name_with_type.chars().nth(0).unwrap();
-
-        let conns = parts.next().unwrap();
-        let conns = conns.split(", ").map(|s| s.to_string()).collect();
-
-        match node_type {
-            '&' => Box::new(ConjunctNode::new(&name_with_type[1..], conns)),
-            '%' => Box::new(FlipFlopNode::new(&name_with_type[1..], conns)),
-            _ => Box::new(BroadcastNode::new(&name_with_type, conns)),
-        }
+            probes,
+        })
     }
 
     // Simulates a single pulse sent through the network, starting at the broadcaster module.
-    fn press_button_and_measure(&mut self, probe_names: &Vec<&str>) -> Vec<String> {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn press_button_and_measure(&mut self) -> Vec<String> {
         let mut signal_queue = VecDeque::new();
         signal_queue.push_back(Signal {
             src: "button".to_string(),
@@ -222,8 +257,7 @@ impl Network {
                 .and_modify(|c| *c += 1)
                 .or_insert(1);
 
-            if signal.signal_type == SignalType::High && probe_names.contains(&signal.src.as_str())
-            {
+            if signal.signal_type == SignalType::High && self.probes.contains(&signal.src) {
                 triggered_probes.push(signal.src.clone());
             }
 
@@ -237,34 +271,82 @@ impl Network {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut net = Network::from_str(input);
+pub struct Part2;
 
-    let mut presses = 0;
-    let mut probes = vec!["kf", "qk", "zs", "kr"];
-    let mut loop_sizes = HashMap::new();
+impl Solution for Part2 {
+    const DAY: u8 = 20;
+    const PART: u8 = 2;
 
-    while probes.len() > 0 {
-        let triggered = net.press_button_and_measure(&probes);
-        presses += 1;
+    type Input = Network;
+    type Error = AocError;
 
-        for probe in triggered {
-            println!("Loop detected for probe: {} => {}", probe, presses);
-            probes.retain(|p| *p != probe);
-            loop_sizes.insert(probe, presses);
-        }
+    fn parse(input: &str) -> Result<Network, AocError> {
+        Ok(Network::from_str(input).map_err(|err| err.with_source(input))?)
     }
 
-    let mut lcm = 1u64;
-    for size in loop_sizes.values() {
-        lcm = num_integer::lcm(lcm, *size);
+    fn solve(mut net: Network, _params: &Params) -> Result<String, AocError> {
+        let mut presses = 0;
+        let mut loop_sizes = HashMap::new();
+
+        while loop_sizes.len() < net.probes.len() {
+            if presses == MAX_PRESSES {
+                return Err(AocError::NoCycle { presses });
+            }
+            let triggered = net.press_button_and_measure();
+            presses += 1;
+
+            for probe in triggered {
+                if let Entry::Vacant(entry) = loop_sizes.entry(probe) {
+                    tracing::debug!(probe = entry.key(), presses, "loop detected");
+                    entry.insert(presses);
+                }
+            }
+        }
+
+        let mut lcm = 1u64;
+        for size in loop_sizes.values() {
+            lcm = num_integer::lcm(lcm, *size);
+        }
+
+        Ok(lcm.to_string())
     }
+}
 
-    Ok(lcm.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
-// Submissions:
-// 179137411 - too low
-// 1241252064 - too low
-// 231897990075517 - correct!
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_probes() {
+        // The example from part 1 has no `rx` at all
+        let input = "broadcaster -> a, b, c
+                     %a -> b
+                     %b -> c
+                     %c -> inv
+                     &inv -> a";
+        let err = aoc_utils::expect_parse_error!(process(input));
+        assert_eq!("no probes to watch", err.message);
+
+        let err = aoc_utils::expect_parse_error!(process("&con -> rx\n%a -> con\n"));
+        assert_eq!("no broadcaster", err.message);
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        // `b` never gets a pulse, so it never sends one to `con`
+        let input = "broadcaster -> a
+                     &con -> rx
+                     %b -> con";
+        assert!(matches!(
+            process(input),
+            Err(AocError::NoCycle {
+                presses: MAX_PRESSES
+            })
+        ));
+    }
+}
@@ -1,16 +1,39 @@
-use day_21::*;
+use aoc_utils::InputLoader;
+use divan::counter::ItemsCount;
+
+fn input(part: u8) -> String {
+    InputLoader::new(21, part)
+        .dir(env!("CARGO_MANIFEST_DIR"))
+        .load()
+        .unwrap()
+        .text
+}
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",)), 64).unwrap();
+aoc_utils::bench_part!(part1, day_21::part1::Part1);
+// The real step count would take forever, see the sweep below
+aoc_utils::bench_part!(part2, day_21::part2::Part2, max_steps = 64);
+
+// Both parts are timed for a range of step counts. Divan also prints the steps per
+// second, which stays flat when the time grows linearly with the steps and drops when
+// it grows faster than that.
+#[divan::bench(consts = [64, 500, 5000], max_time = 10)]
+fn part1_steps<const STEPS: u64>(bencher: divan::Bencher) {
+    let input = input(1);
+    bencher.counter(ItemsCount::new(STEPS)).bench(|| {
+        day_21::part1::process(divan::black_box(&input), STEPS).unwrap();
+    });
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",)), 64).unwrap();
+// The infinite garden keeps growing, so the time grows with the square of the steps here
+#[divan::bench(consts = [64, 500, 5000], max_time = 10)]
+fn part2_steps<const STEPS: u64>(bencher: divan::Bencher) {
+    let input = input(2);
+    bencher.counter(ItemsCount::new(STEPS)).bench(|| {
+        day_21::part2::process(divan::black_box(&input), STEPS).unwrap();
+    });
 }
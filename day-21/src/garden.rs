@@ -0,0 +1,15 @@
+// A garden plot the elf can step on, or a rock in the way
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Plot, // . (and S, where the elf starts)
+    Rock, // #
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Self {
+        match c {
+            '#' => Self::Rock,
+            _ => Self::Plot,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 pub mod custom_error;
 
+pub mod garden;
 pub mod part1;
 pub mod part2;
-pub mod utils;
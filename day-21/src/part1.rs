@@ -1,44 +1,68 @@
 use std::collections::{HashSet, VecDeque};
 
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, Direction, Point},
-};
+use aoc_utils::{CharMap, Direction, Grid, Param, Params, ParseError, Point, Solution};
 
-#[tracing::instrument]
-pub fn process(input: &str, max_steps: u64) -> miette::Result<String, AocError> {
-    let mut map = CharMap::from_str_with_trim(input, '#');
+use crate::{custom_error::AocError, garden::Tile};
 
-    let start = map.find('S').unwrap();
-    map.set_cell_for_point(&start, '.');
+pub struct Part1;
 
-    let mut queue = VecDeque::new();
-    queue.push_back((start, 0));
-    let mut seen: HashSet<(Point<i64>, u64)> = HashSet::new();
-    let mut reached_at_limit: HashSet<Point<i64>> = HashSet::new();
+impl Solution for Part1 {
+    const DAY: u8 = 21;
+    const PART: u8 = 1;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "max_steps",
+        description: "number of steps the elf takes",
+        default: "64",
+    }];
 
-    while let Some((pos, steps)) = queue.pop_front() {
-        if steps > max_steps || map.out_of_bounds(&pos) || seen.contains(&(pos, steps)) {
-            continue;
-        }
+    type Input = (Grid<Tile>, Point<i64>);
+    type Error = AocError;
 
-        let cell = map.cell_for_point(&pos);
-        if *cell == '#' {
-            continue;
-        }
-        if *cell == '.' {
+    fn parse(input: &str) -> Result<(Grid<Tile>, Point<i64>), AocError> {
+        let map = CharMap::parse(input, '#', |c| ".#S".contains(c))
+            .map_err(|err| err.with_source(input))?;
+
+        let Some(start) = map.find('S') else {
+            let err = ParseError::new("no starting position", input, "expected an `S` somewhere");
+            return Err(err.into());
+        };
+
+        Ok((map.map(Tile::from_char), start))
+    }
+
+    fn solve((map, start): (Grid<Tile>, Point<i64>), params: &Params) -> Result<String, AocError> {
+        let max_steps = params.get::<u64>("max_steps")?;
+
+        let mut queue = VecDeque::new();
+        queue.push_back((start, 0));
+        let mut seen: HashSet<(Point<i64>, u64)> = HashSet::new();
+        let mut reached_at_limit: HashSet<Point<i64>> = HashSet::new();
+
+        while let Some((pos, steps)) = queue.pop_front() {
+            if steps > max_steps || map.out_of_bounds(&pos) || seen.contains(&(pos, steps)) {
+                continue;
+            }
+
+            if *map.cell_for_point(&pos) == Tile::Rock {
+                continue;
+            }
             seen.insert((pos, steps));
             if steps == max_steps {
                 reached_at_limit.insert(pos);
             }
-        }
 
-        for dir in Direction::each() {
-            queue.push_back((pos + dir.delta(), steps + 1));
+            for dir in Direction::each() {
+                queue.push_back((pos + dir.delta(), steps + 1));
+            }
         }
+
+        Ok(reached_at_limit.len().to_string())
     }
+}
 
-    Ok(reached_at_limit.iter().count().to_string())
+#[tracing::instrument]
+pub fn process(input: &str, max_steps: u64) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new().with("max_steps", max_steps))
 }
 
 #[cfg(test)]
@@ -61,4 +85,10 @@ mod tests {
         assert_eq!("16", process(input, 6)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("...\n.#.\n", 6));
+        assert_eq!("no starting position", err.message);
+    }
 }
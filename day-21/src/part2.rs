@@ -1,52 +1,80 @@
-use std::collections::{HashSet, VecDeque};
+use fxhash::FxHashSet;
 
-use crate::{
-    custom_error::AocError,
-    utils::{CharMap, Direction, Point},
-};
+use aoc_utils::{Boundary, CharMap, Direction, Grid, Param, Params, ParseError, Point, Solution};
 
-fn to_map_point(pos: Point<i64>, map: &CharMap) -> Point<i64> {
-    let real_x = pos.x.rem_euclid(map.width() as i64);
-    let real_y = pos.y.rem_euclid(map.height() as i64);
-    Point::new(real_x, real_y)
-}
+use crate::{custom_error::AocError, garden::Tile};
 
-#[tracing::instrument]
-pub fn process(input: &str, max_steps: u64) -> miette::Result<String, AocError> {
-    let mut map = CharMap::from_str_with_trim(input, '#');
+pub struct Part2;
 
-    let start = map.find('S').unwrap();
-    map.set_cell_for_point(&start, '.');
+impl Solution for Part2 {
+    const DAY: u8 = 21;
+    const PART: u8 = 2;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "max_steps",
+        description: "number of steps the elf takes",
+        default: "26501365",
+    }];
 
-    let mut queue = VecDeque::new();
-    queue.push_back((start, 0));
-    let mut seen: HashSet<(Point<i64>, u64)> = HashSet::new();
-    let mut reached_at_limit: HashSet<Point<i64>> = HashSet::new();
+    type Input = (Grid<Tile>, Point<i64>);
+    type Error = AocError;
 
-    while let Some((pos, steps)) = queue.pop_front() {
-        if steps > max_steps || seen.contains(&(pos, steps)) {
-            continue;
-        }
+    fn parse(input: &str) -> Result<(Grid<Tile>, Point<i64>), AocError> {
+        // The garden repeats infinitely in every direction
+        let map = CharMap::parse(input, '#', |c| ".#S".contains(c))
+            .map_err(|err| err.with_source(input))?
+            .with_boundary(Boundary::Wrap);
 
-        // Convert to real position by wrapping around the map.
-        let real_pos = to_map_point(pos, &map);
-        let cell = map.cell_for_point(&real_pos);
-        if *cell == '#' {
-            continue;
-        }
-        if *cell == '.' {
-            seen.insert((pos, steps));
-            if steps == max_steps {
-                reached_at_limit.insert(pos);
+        let Some(start) = map.find('S') else {
+            let err = ParseError::new("no starting position", input, "expected an `S` somewhere");
+            return Err(err.into());
+        };
+
+        Ok((map.map(Tile::from_char), start))
+    }
+
+    fn solve((map, start): (Grid<Tile>, Point<i64>), params: &Params) -> Result<String, AocError> {
+        let max_steps = params.get::<u64>("max_steps")?;
+
+        // The plots reachable in exactly `max_steps` are the ones the elf first gets to with
+        // the same parity, since it can always step back and forth. Every step only leads
+        // to the plots one step closer or further away, so a BFS over the garden only has to
+        // remember the last two layers, not every plot it has seen.
+        let deltas = Direction::each()
+            .iter()
+            .map(Direction::delta)
+            .collect::<Vec<_>>();
+        let mut previous: FxHashSet<Point<i64>> = FxHashSet::default();
+        let mut frontier: FxHashSet<Point<i64>> = FxHashSet::default();
+        let mut next: FxHashSet<Point<i64>> = FxHashSet::default();
+        frontier.insert(start);
+        let mut reached = u64::from(max_steps % 2 == 0);
+
+        for steps in 1..=max_steps {
+            next.clear();
+            for pos in &frontier {
+                for delta in &deltas {
+                    let next_pos = *pos + *delta;
+                    if *map.cell_for_point(&next_pos) != Tile::Rock && !previous.contains(&next_pos)
+                    {
+                        next.insert(next_pos);
+                    }
+                }
+            }
+            if steps % 2 == max_steps % 2 {
+                reached += next.len() as u64;
             }
+            // The layer before the last one isn't needed anymore, reuse it for the next step
+            std::mem::swap(&mut previous, &mut frontier);
+            std::mem::swap(&mut frontier, &mut next);
         }
 
-        for dir in Direction::each() {
-            queue.push_back((pos + dir.delta(), steps + 1));
-        }
+        Ok(reached.to_string())
     }
+}
 
-    Ok(reached_at_limit.iter().count().to_string())
+#[tracing::instrument]
+pub fn process(input: &str, max_steps: u64) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new().with("max_steps", max_steps))
 }
 
 #[cfg(test)]
@@ -144,7 +172,7 @@ mod tests {
 
         for i in 0..10 {
             let step = start + i * 2 * map_width;
-            let result = process(input, step as u64).unwrap().parse::<i64>().unwrap();
+            let result = process(input, step).unwrap().parse::<i64>().unwrap();
             println!("Step {} -> {}", step, result,);
         }
     }
@@ -0,0 +1,93 @@
+use aoc_utils::{number, token, IResult, NomError};
+use nom::{
+    combinator::{consumed, map},
+    sequence::{preceded, separated_pair, tuple},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Point3D {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl std::fmt::Display for Point3D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Point3D {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn on_top_of(&self, p2: &Point3D) -> bool {
+        self.x == p2.x && self.y == p2.y && self.z == p2.z + 1
+    }
+}
+
+// `1,0,1`
+fn point(input: &str) -> IResult<'_, Point3D> {
+    map(
+        tuple((
+            number,
+            preceded(token(","), number),
+            preceded(token(","), number),
+        )),
+        |(x, y, z)| Point3D::new(x, y, z),
+    )(input)
+}
+
+// `1,0,1~1,2,1`: the two ends of a brick
+pub fn brick_ends(input: &str) -> IResult<'_, (Point3D, Point3D)> {
+    let (rest, ((left_str, left), (right_str, right))) =
+        separated_pair(consumed(point), token("~"), consumed(point))(input)?;
+
+    // Bricks rest on the ground at z=0, so they can only start above it
+    for (at, point) in [(left_str, &left), (right_str, &right)] {
+        if point.z < 1 {
+            return Err(nom::Err::Failure(NomError::at(
+                at,
+                "a point above the ground",
+            )));
+        }
+    }
+    Ok((rest, (left, right)))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::parse_lines;
+
+    use super::*;
+
+    const EXAMPLE: &str = "1,0,1~1,2,1
+                           0,0,2~2,0,2
+                           0,2,3~2,2,3
+                           0,0,4~0,2,4
+                           2,0,5~2,2,5
+                           0,1,6~2,1,6
+                           1,1,8~1,1,9";
+
+    #[test]
+    fn test_point_on_top_of() {
+        let p1 = Point3D::new(0, 0, 1);
+        let p2 = Point3D::new(0, 0, 0);
+        assert!(p1.on_top_of(&p2));
+
+        let p1 = Point3D::new(0, 1, 0);
+        let p2 = Point3D::new(0, 0, 0);
+        assert!(!p1.on_top_of(&p2));
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let ends = parse_lines(EXAMPLE, brick_ends)?;
+        let printed = ends
+            .iter()
+            .map(|(left, right)| format!("{}~{}", left, right));
+        assert!(EXAMPLE.lines().map(str::trim).eq(printed));
+        Ok(())
+    }
+}
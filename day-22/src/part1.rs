@@ -1,20 +1,29 @@
 use std::cmp::{max, min};
 
-use crate::custom_error::AocError;
+use aoc_utils::{parse_lines, Params, Solution};
+
+use crate::{
+    custom_error::AocError,
+    parse::{brick_ends, Point3D},
+};
 
 #[derive(Debug)]
-struct World {
+pub struct World {
     bricks: Vec<Brick>,
 }
 
 impl World {
     // Let all the bricks settle down
+    #[tracing::instrument(level = "trace", skip_all)]
     fn settle(&mut self) {
         // Sort the bricks by their z coordinate (lowest to highest)
-        self.bricks.sort_by(|a, b| a.end.z.cmp(&b.end.z));
+        self.bricks.sort_by_key(|a| a.end.z);
 
         // Iterate over the bricks and move them down until they can't move anymore
-        let mut max_z_seen = self.bricks.first().unwrap().end.z;
+        let Some(lowest) = self.bricks.first() else {
+            return;
+        };
+        let mut max_z_seen = lowest.end.z;
         for i in 0..self.bricks.len() {
             // Move the brick down to at least the maximum z coordinate seen so far (where we can potentially hit another brick)
             let move_by = self.bricks[i].end.z - max_z_seen - 1;
@@ -78,31 +87,6 @@ impl World {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Point3D {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Point3D {
-    fn new(x: i32, y: i32, z: i32) -> Self {
-        Self { x, y, z }
-    }
-
-    fn from_str(s: &str) -> Self {
-        let mut parts = s.split(',');
-        let x = parts.next().unwrap().parse::<i32>().unwrap();
-        let y = parts.next().unwrap().parse::<i32>().unwrap();
-        let z = parts.next().unwrap().parse::<i32>().unwrap();
-        Self::new(x, y, z)
-    }
-
-    fn on_top_of(&self, p2: &Point3D) -> bool {
-        self.x == p2.x && self.y == p2.y && self.z == p2.z + 1
-    }
-}
-
 #[derive(Debug, PartialEq, Eq)]
 struct Brick {
     id: u64,
@@ -151,49 +135,50 @@ impl Brick {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut world = World { bricks: Vec::new() };
-
-    let mut id = 0;
-    for line in input.lines() {
-        let mut parts = line.trim().split('~');
-        let left_point = Point3D::from_str(parts.next().unwrap());
-        let right_point = Point3D::from_str(parts.next().unwrap());
-        world.bricks.push(Brick::new(id, left_point, right_point));
-        id += 1;
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 22;
+    const PART: u8 = 1;
+
+    type Input = World;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<World, AocError> {
+        let bricks = parse_lines(input, brick_ends)?
+            .into_iter()
+            .enumerate()
+            .map(|(id, (left, right))| Brick::new(id as u64, left, right))
+            .collect();
+        Ok(World { bricks })
     }
 
-    // Let all the bricks settle down
-    world.settle();
-    assert!(world.has_settled(None));
-
-    // Count all bricks that, if removed, would not cause any other brick to fall
-    let mut count = 0;
-    for brick in &world.bricks {
-        if world.has_settled(Some(brick.id)) {
-            count += 1;
+    fn solve(mut world: World, _params: &Params) -> Result<String, AocError> {
+        // Let all the bricks settle down
+        world.settle();
+        assert!(world.has_settled(None));
+
+        // Count all bricks that, if removed, would not cause any other brick to fall
+        let mut count = 0;
+        for brick in &world.bricks {
+            if world.has_settled(Some(brick.id)) {
+                count += 1;
+            }
         }
+
+        Ok(count.to_string())
     }
+}
 
-    Ok(count.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_point_on_top_of() {
-        let p1 = Point3D::new(0, 0, 1);
-        let p2 = Point3D::new(0, 0, 0);
-        assert!(p1.on_top_of(&p2));
-
-        let p1 = Point3D::new(0, 1, 0);
-        let p2 = Point3D::new(0, 0, 0);
-        assert!(!p1.on_top_of(&p2));
-    }
-
     #[test]
     fn test_brick_points() {
         // A line like 2,2,2~2,2,2 means that both ends of the brick are at the same coordinate - in other words, that the brick is a single cube.
@@ -213,19 +198,37 @@ mod tests {
         assert_eq!(10, brick.points().len());
     }
 
+    const EXAMPLE: &str = "1,0,1~1,2,1
+                           0,0,2~2,0,2
+                           0,2,3~2,2,3
+                           0,0,4~0,2,4
+                           2,0,5~2,2,5
+                           0,1,6~2,1,6
+                           1,1,8~1,1,9";
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "1,0,1~1,2,1
-                     0,0,2~2,0,2
-                     0,2,3~2,2,3
-                     0,0,4~0,2,4
-                     2,0,5~2,2,5
-                     0,1,6~2,1,6
-                     1,1,8~1,1,9";
-        assert_eq!("5", process(input)?);
+        assert_eq!("5", process(EXAMPLE)?);
         Ok(())
     }
-}
 
-// Submissions:
-// 393 - correct
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("1,0,1~1,2,1\n0,0,2-2,0,2\n"));
+        assert_eq!("expected `~`, found `-2`", err.message);
+        assert_eq!(miette::SourceSpan::from((17, 2)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("1,0,1~1,x,1\n"));
+        assert_eq!("expected a number, found `x`", err.message);
+        assert_eq!(miette::SourceSpan::from((8, 1)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("1,0,1~1,2,1\n1,1,0~1,1,1\n"));
+        assert_eq!(
+            "expected a point above the ground, found `1,1,0`",
+            err.message
+        );
+        assert_eq!(miette::SourceSpan::from((12, 5)), err.span);
+
+        assert_eq!("0", process("").unwrap());
+    }
+}
@@ -1,20 +1,29 @@
 use std::cmp::{max, min};
 
-use crate::custom_error::AocError;
+use aoc_utils::{parse_lines, Params, Solution};
+
+use crate::{
+    custom_error::AocError,
+    parse::{brick_ends, Point3D},
+};
 
 #[derive(Debug, Clone)]
-struct World {
+pub struct World {
     bricks: Vec<Brick>,
 }
 
 impl World {
     // Let all the bricks settle down, return the number of bricks that moved
+    #[tracing::instrument(level = "trace", skip_all)]
     fn settle(&mut self) -> u64 {
         // Sort the bricks by their z coordinate (lowest to highest)
-        self.bricks.sort_by(|a, b| a.end.z.cmp(&b.end.z));
+        self.bricks.sort_by_key(|a| a.end.z);
 
         // Iterate over the bricks and move them down until they can't move anymore
-        let mut max_z_seen = self.bricks.first().unwrap().end.z;
+        let Some(lowest) = self.bricks.first() else {
+            return 0;
+        };
+        let mut max_z_seen = lowest.end.z;
         let mut total_moved = 0;
         for i in 0..self.bricks.len() {
             let mut moved = false;
@@ -88,31 +97,6 @@ impl World {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct Point3D {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Point3D {
-    fn new(x: i32, y: i32, z: i32) -> Self {
-        Self { x, y, z }
-    }
-
-    fn from_str(s: &str) -> Self {
-        let mut parts = s.split(',');
-        let x = parts.next().unwrap().parse::<i32>().unwrap();
-        let y = parts.next().unwrap().parse::<i32>().unwrap();
-        let z = parts.next().unwrap().parse::<i32>().unwrap();
-        Self::new(x, y, z)
-    }
-
-    fn on_top_of(&self, p2: &Point3D) -> bool {
-        self.x == p2.x && self.y == p2.y && self.z == p2.z + 1
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct Brick {
     id: u64,
@@ -161,52 +145,53 @@ impl Brick {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut world = World { bricks: Vec::new() };
-
-    let mut id = 0;
-    for line in input.lines() {
-        let mut parts = line.trim().split('~');
-        let left_point = Point3D::from_str(parts.next().unwrap());
-        let right_point = Point3D::from_str(parts.next().unwrap());
-        world.bricks.push(Brick::new(id, left_point, right_point));
-        id += 1;
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 22;
+    const PART: u8 = 2;
+
+    type Input = World;
+    type Error = AocError;
+
+    fn parse(input: &str) -> Result<World, AocError> {
+        let bricks = parse_lines(input, brick_ends)?
+            .into_iter()
+            .enumerate()
+            .map(|(id, (left, right))| Brick::new(id as u64, left, right))
+            .collect();
+        Ok(World { bricks })
     }
 
-    // Let all the bricks settle down
-    world.settle();
-    assert!(world.has_settled(None));
-
-    //Find all the bricks that, when removed, would cause an avalanche,
-    //then simulate the avalanche and count the number of bricks that moved.
-    let mut total_moved = 0;
-    for brick in &world.bricks {
-        if !world.has_settled(Some(brick.id)) {
-            let mut simulation = world.clone();
-            simulation.bricks.retain(|b| b.id != brick.id);
-            total_moved += simulation.settle();
+    fn solve(mut world: World, _params: &Params) -> Result<String, AocError> {
+        // Let all the bricks settle down
+        world.settle();
+        assert!(world.has_settled(None));
+
+        //Find all the bricks that, when removed, would cause an avalanche,
+        //then simulate the avalanche and count the number of bricks that moved.
+        let mut total_moved = 0;
+        for brick in &world.bricks {
+            if !world.has_settled(Some(brick.id)) {
+                let mut simulation = world.clone();
+                simulation.bricks.retain(|b| b.id != brick.id);
+                total_moved += simulation.settle();
+            }
         }
+
+        Ok(total_moved.to_string())
     }
+}
 
-    Ok(total_moved.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_point_on_top_of() {
-        let p1 = Point3D::new(0, 0, 1);
-        let p2 = Point3D::new(0, 0, 0);
-        assert!(p1.on_top_of(&p2));
-
-        let p1 = Point3D::new(0, 1, 0);
-        let p2 = Point3D::new(0, 0, 0);
-        assert!(!p1.on_top_of(&p2));
-    }
-
     #[test]
     fn test_brick_points() {
         // A line like 2,2,2~2,2,2 means that both ends of the brick are at the same coordinate - in other words, that the brick is a single cube.
@@ -239,6 +224,3 @@ mod tests {
         Ok(())
     }
 }
-
-// Submissions:
-// 393 - correct
@@ -1,5 +1,5 @@
-
-use miette::Diagnostic;
+use aoc_utils::{ParamError, ParseError};
+use miette::{Diagnostic, SourceSpan};
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
@@ -7,4 +7,27 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParamError(#[from] ParamError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] ParseError),
+
+    #[error("the map has {count} junctions, but at most {max} of them can be tracked")]
+    #[diagnostic(code(aoc::too_many_junctions))]
+    TooManyJunctions {
+        count: usize,
+        max: usize,
+        #[source_code]
+        map: String,
+        #[label("this is the first junction over the limit")]
+        span: SourceSpan,
+    },
+
+    #[error("there is no hike from the start to the finish")]
+    #[diagnostic(code(aoc::no_route))]
+    NoRoute,
+}
@@ -2,4 +2,4 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
-pub mod utils;
+pub mod trails;
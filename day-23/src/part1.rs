@@ -1,70 +1,36 @@
-use std::collections::{HashMap, HashSet};
+use aoc_utils::{CharMap, Params, Solution};
 
 use crate::{
     custom_error::AocError,
-    utils::{CharMap, Direction, Point},
+    trails::{Tile, TrailMap, Trails},
 };
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = CharMap::from_str_with_trim(input, '#');
+pub struct Part1;
 
-    let start = Point::new(1, 0);
-    let steps = 0;
-    let visited = HashSet::new();
-    let mut best_steps: HashMap<Point<i64>, u64> = HashMap::new();
-    let longest = hike(&map, start, steps, visited, &mut best_steps);
-
-    Ok(longest.to_string())
-}
+impl Solution for Part1 {
+    const DAY: u8 = 23;
+    const PART: u8 = 1;
 
-fn hike(
-    map: &CharMap,
-    pos: Point<i64>,
-    steps: u64,
-    visited: HashSet<Point<i64>>,
-    best: &mut HashMap<Point<i64>, u64>, // Best known steps to get to a point (longest)
-) -> u64 {
-    let finish = Point::new(map.width() as i64 - 2, map.height() as i64 - 1);
-    let best_steps = best.get(&pos).copied().unwrap_or(0);
-    if steps < best_steps {
-        println!("Skipping {} because {} < {}", pos, steps, best_steps);
-        return 0;
-    }
-    best.insert(pos, steps);
+    type Input = TrailMap;
+    type Error = AocError;
 
-    if pos == finish {
-        println!("Found finish at {} in {} steps", pos, steps);
-        return steps;
+    fn parse(input: &str) -> Result<TrailMap, AocError> {
+        let map = CharMap::parse(input, '#', |c| ".#<>^v".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        Ok(map.map(Tile::from_char))
     }
 
-    let mut visited = visited.clone();
-    visited.insert(pos);
-
-    let mut candidates = vec![];
-
-    for dir in Direction::each() {
-        let next = pos + dir.delta();
-        if visited.contains(&next) {
-            continue;
-        }
-
-        let cell = *map.cell_for_point(&next);
-        if cell == '#' {
-            continue;
-        }
+    fn solve(map: TrailMap, _params: &Params) -> Result<String, AocError> {
+        let trails = Trails::from_map(&map, true)?;
+        let longest = trails.longest_hike().ok_or(AocError::NoRoute)?;
 
-        if cell == '<' || cell == '>' || cell == '^' || cell == 'v' {
-            if cell != dir.to_char() {
-                continue;
-            }
-        }
-
-        let steps = hike(map, next, steps + 1, visited.clone(), best);
-        candidates.push(steps);
+        Ok(longest.to_string())
     }
+}
 
-    *candidates.iter().max().unwrap_or(&0)
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -99,4 +65,14 @@ mod tests {
         assert_eq!("94", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_no_route() {
+        // The only way down goes up a slope
+        let input = "#.###
+                     #...#
+                     ###^#
+                     ###.#";
+        assert!(matches!(process(input), Err(AocError::NoRoute)));
+    }
 }
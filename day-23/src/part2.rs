@@ -1,105 +1,36 @@
-use std::collections::{HashMap, HashSet};
+use aoc_utils::{CharMap, Params, Solution};
 
 use crate::{
     custom_error::AocError,
-    utils::{CharMap, Direction, Point},
+    trails::{Tile, TrailMap, Trails},
 };
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let input = input
-        .replace("<", ".")
-        .replace(">", ".")
-        .replace("^", ".")
-        .replace("v", ".");
-
-    let map = CharMap::from_str_with_trim(&input, '#');
+pub struct Part2;
 
-    let start = Point::new(1, 0);
-    let visited = HashSet::new();
+impl Solution for Part2 {
+    const DAY: u8 = 23;
+    const PART: u8 = 2;
 
-    let mut best_steps = BestVisits::new();
-    best_steps.insert((start, Direction::South), 0);
-
-    let longest = hike(&map, start, Direction::South, visited, &mut best_steps);
-
-    Ok(longest.to_string())
-}
+    type Input = TrailMap;
+    type Error = AocError;
 
-type Visit = (Point<i64>, Direction);
-type BestVisits = HashMap<Visit, u64>;
-
-fn hike(
-    map: &CharMap,
-    pos: Point<i64>,
-    dir: Direction,
-    visited: HashSet<Point<i64>>,
-    best: &mut BestVisits, // Best known steps to get to a point (longest)
-) -> u64 {
-    let finish = Point::new(map.width() as i64 - 2, map.height() as i64 - 1);
-
-    let visit = (pos, dir);
-    let steps = visited.iter().count() as u64;
-
-    if let Some(best_steps) = best.get(&visit) {
-        if steps < *best_steps {
-            // println!(
-            //     "Skipping visit {:?} after {} steps because previously reached it in {}",
-            //     visit, steps, best_steps
-            // );
-            // return 0;
-        } else {
-            best.insert(visit, steps);
-        }
-    } else {
-        best.insert(visit, steps);
+    fn parse(input: &str) -> Result<TrailMap, AocError> {
+        let map = CharMap::parse(input, '#', |c| ".#<>^v".contains(c))
+            .map_err(|err| err.with_source(input))?;
+        Ok(map.map(Tile::from_char))
     }
 
-    let mut visited = visited.clone();
-    visited.insert(pos);
+    fn solve(map: TrailMap, _params: &Params) -> Result<String, AocError> {
+        let trails = Trails::from_map(&map, false)?;
+        let longest = trails.longest_hike().ok_or(AocError::NoRoute)?;
 
-    if pos == finish {
-        println!(
-            "Found finish at {} in {} steps. Best = {}",
-            pos,
-            steps,
-            best.get(&visit).unwrap()
-        );
-        // print_visits(map, &pos, &visited, best);
-        return steps;
+        Ok(longest.to_string())
     }
-
-    // Take next steps
-    let mut candidates = vec![];
-    for dir in Direction::each() {
-        let next = pos + dir.delta();
-
-        if visited.contains(&next) {
-            continue;
-        }
-
-        if *map.cell_for_point(&next) == '#' {
-            continue;
-        }
-
-        let res = hike(map, next, dir, visited.clone(), best);
-        candidates.push(res);
-    }
-
-    candidates.iter().max().copied().unwrap_or(0)
 }
 
-fn print_visits(map: &CharMap, pos: &Point<i64>, visited: &HashSet<Point<i64>>) {
-    println!("---------------------------------------------------------------------------------------------------------------------------");
-    // Create a map where all visited points are marked with a direction char from the best visits map
-    let mut visited_map = map.clone();
-
-    for pos in visited {
-        visited_map.set_cell_for_point(&pos, 'O');
-    }
-
-    visited_map.print_with_current(pos, 'X');
-    println!("---------------------------------------------------------------------------------------------------------------------------");
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -135,11 +66,3 @@ mod tests {
         Ok(())
     }
 }
-
-// Submissions:
-// 5130 - ?
-// 5466 - too low
-// 6055 - too low
-// 6079 - too low
-// 6122 - incorrect
-// 6410 - correct!
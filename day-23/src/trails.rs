@@ -0,0 +1,288 @@
+use std::fmt::Display;
+
+use aoc_utils::{Direction, Grid, Point};
+
+use crate::custom_error::AocError;
+
+// Visited junctions are tracked in a u64 bitmask during the search
+const MAX_JUNCTIONS: usize = 64;
+
+// A corridor between two junctions and the number of steps it takes to walk it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trail {
+    pub to: usize,
+    pub steps: u64,
+}
+
+// The trail map contracted into a graph: nodes are the start, the finish and every cell
+// where the trail branches, edges are the corridors between them
+#[derive(Clone, Debug)]
+pub struct Trails {
+    pub junctions: Vec<Point<i64>>,
+    pub trails: Vec<Vec<Trail>>,
+    pub start: usize,
+    pub finish: usize,
+}
+
+// A spot on the trail map
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Path,             // .
+    Forest,           // #
+    Slope(Direction), // one of ^>v<, pointing downhill
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Self {
+        match c {
+            '#' => Self::Forest,
+            '^' => Self::Slope(Direction::North),
+            'v' => Self::Slope(Direction::South),
+            '<' => Self::Slope(Direction::West),
+            '>' => Self::Slope(Direction::East),
+            _ => Self::Path,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Path => '.',
+            Self::Forest => '#',
+            Self::Slope(dir) => dir.to_char(),
+        };
+        write!(f, "{}", c)
+    }
+}
+
+pub type TrailMap = Grid<Tile>;
+
+impl Trails {
+    // Builds the graph for the map. When slopes are honored, a slope can only be entered
+    // going in the direction it points to, so corridors with slopes become one-way.
+    #[tracing::instrument(skip_all)]
+    pub fn from_map(map: &TrailMap, slopes: bool) -> Result<Self, AocError> {
+        let start = Point::new(1, 0);
+        let finish = Point::new(map.width() as i64 - 2, map.height() as i64 - 1);
+
+        let mut junctions = vec![start, finish];
+        for (pos, cell) in map.iter() {
+            if *cell == Tile::Forest || pos == start || pos == finish {
+                continue;
+            }
+            let exits = pos
+                .neighbours_list()
+                .iter()
+                .filter(|n| *map.cell_for_point(n) != Tile::Forest)
+                .count();
+            if exits > 2 {
+                junctions.push(pos);
+            }
+        }
+
+        if let Some(junction) = junctions.get(MAX_JUNCTIONS) {
+            let offset = junction.y as usize * (map.width() + 1) + junction.x as usize;
+            return Err(AocError::TooManyJunctions {
+                count: junctions.len(),
+                max: MAX_JUNCTIONS,
+                map: map.to_string(),
+                span: (offset, 1).into(),
+            });
+        }
+
+        let trails = junctions
+            .iter()
+            .map(|junction| {
+                Direction::each()
+                    .into_iter()
+                    .filter_map(|dir| walk(map, &junctions, *junction, dir, slopes))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            junctions,
+            trails,
+            start: 0,
+            finish: 1,
+        })
+    }
+
+    // Length of the longest hike from the start to the finish that never visits the same
+    // junction twice (and so never steps onto the same tile twice).
+    // Returns None if the finish cannot be reached at all.
+    #[tracing::instrument(skip_all)]
+    pub fn longest_hike(&self) -> Option<u64> {
+        // The finish is usually a dead end reachable from a single junction. Once we get to
+        // that junction, we have to go to the finish: any other way would block it off.
+        let mut entries = (0..self.junctions.len())
+            .filter(|&from| self.trails[from].iter().any(|t| t.to == self.finish));
+        let last = match (entries.next(), entries.next()) {
+            (Some(last), None) => last,
+            _ => self.finish,
+        };
+
+        self.hike(self.start, last, 1 << self.start)
+    }
+
+    fn hike(&self, from: usize, last: usize, visited: u64) -> Option<u64> {
+        if from == self.finish {
+            return Some(0);
+        }
+
+        self.trails[from]
+            .iter()
+            .filter(|trail| visited & (1 << trail.to) == 0)
+            .filter(|trail| from != last || trail.to == self.finish)
+            .filter_map(|trail| {
+                let rest = self.hike(trail.to, last, visited | (1 << trail.to))?;
+                Some(trail.steps + rest)
+            })
+            .max()
+    }
+}
+
+// Follows the corridor leaving the junction in the given direction up to the next junction.
+// Returns None for dead ends and for corridors that cannot be walked because of a slope.
+fn walk(
+    map: &TrailMap,
+    junctions: &[Point<i64>],
+    junction: Point<i64>,
+    dir: Direction,
+    slopes: bool,
+) -> Option<Trail> {
+    let can_enter = |pos: &Point<i64>, dir: Direction| match *map.cell_for_point(pos) {
+        Tile::Path => true,
+        Tile::Forest => false,
+        Tile::Slope(slope) => !slopes || slope == dir,
+    };
+
+    let mut prev = junction;
+    let mut pos = junction + dir.delta();
+    if !can_enter(&pos, dir) {
+        return None;
+    }
+
+    let mut steps = 1;
+    loop {
+        if let Some(to) = junctions.iter().position(|j| *j == pos) {
+            return Some(Trail { to, steps });
+        }
+
+        // Not a junction, so there is at most one way forward
+        let dir = Direction::each().into_iter().find(|dir| {
+            pos + dir.delta() != prev && *map.cell_for_point(&(pos + dir.delta())) != Tile::Forest
+        })?;
+        let next = pos + dir.delta();
+        if !can_enter(&next, dir) {
+            return None;
+        }
+
+        prev = pos;
+        pos = next;
+        steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::CharMap;
+
+    use super::*;
+
+    const EXAMPLE: &str = "#.#####################
+                           #.......#########...###
+                           #######.#########.#.###
+                           ###.....#.>.>.###.#.###
+                           ###v#####.#v#.###.#.###
+                           ###.>...#.#.#.....#...#
+                           ###v###.#.#.#########.#
+                           ###...#.#.#.......#...#
+                           #####.#.#.#######.#.###
+                           #.....#.#.#.......#...#
+                           #.#####.#.#.#########v#
+                           #.#...#...#...###...>.#
+                           #.#.#v#######v###.###v#
+                           #...#.>.#...>.>.#.###.#
+                           #####v#.#.###v#.#.###.#
+                           #.....#...#...#.#.#...#
+                           #.#########.###.#.#.###
+                           #...###...#...#...#.###
+                           ###.###.#.###v#####v###
+                           #...#...#.#.>.>.#.>.###
+                           #.###.###.#.###.#.#v###
+                           #.....###...###...#...#
+                           #####################.#";
+
+    #[test]
+    fn test_graph() {
+        let map = CharMap::from_str_with_trim(EXAMPLE, '#').map(Tile::from_char);
+
+        // Start, finish and 7 junctions in between
+        let dry = Trails::from_map(&map, false).unwrap();
+        assert_eq!(9, dry.junctions.len());
+        let first = dry.trails[dry.start][0];
+        assert_eq!(1, dry.trails[dry.start].len());
+        assert_eq!(Point::new(3, 5), dry.junctions[first.to]);
+        assert_eq!(15, first.steps);
+
+        // Every corridor can be walked both ways when ignoring slopes
+        let total = |trails: &Trails| trails.trails.iter().map(|t| t.len()).sum::<usize>();
+        assert_eq!(24, total(&dry));
+
+        // Slopes make every corridor in the example one-way
+        let icy = Trails::from_map(&map, true).unwrap();
+        assert_eq!(dry.junctions, icy.junctions);
+        assert_eq!(12, total(&icy));
+    }
+
+    #[test]
+    fn test_longest_hike() {
+        let map = CharMap::from_str_with_trim(EXAMPLE, '#').map(Tile::from_char);
+        assert_eq!(
+            Some(94),
+            Trails::from_map(&map, true).unwrap().longest_hike()
+        );
+        assert_eq!(
+            Some(154),
+            Trails::from_map(&map, false).unwrap().longest_hike()
+        );
+    }
+
+    #[test]
+    fn test_blocked_by_slope() {
+        // The only way down goes up a slope
+        let map = CharMap::from_str_with_trim(
+            "#.###
+             #...#
+             ###^#
+             ###.#",
+            '#',
+        )
+        .map(Tile::from_char);
+        assert_eq!(None, Trails::from_map(&map, true).unwrap().longest_hike());
+        assert_eq!(
+            Some(5),
+            Trails::from_map(&map, false).unwrap().longest_hike()
+        );
+    }
+
+    #[test]
+    fn test_too_many_junctions() {
+        // Almost every cell of an open field is a junction
+        let field = std::iter::once("#.##########".to_string())
+            .chain((0..10).map(|_| format!("#{}#", ".".repeat(10))))
+            .chain(std::iter::once("##########.#".to_string()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let map = CharMap::from_str_with_trim(&field, '#').map(Tile::from_char);
+
+        let Err(AocError::TooManyJunctions { count, span, .. }) = Trails::from_map(&map, false)
+        else {
+            panic!("expected too many junctions");
+        };
+        assert_eq!(100, count);
+        assert_eq!(miette::SourceSpan::from((13 * 7 + 4, 1)), span);
+    }
+}
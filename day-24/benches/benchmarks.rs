@@ -1,21 +1,50 @@
-use day_24::*;
+use aoc_utils::{InputLoader, Params, Solution};
+use divan::counter::ItemsCount;
+
+fn input(part: u8) -> String {
+    InputLoader::new(24, part)
+        .dir(env!("CARGO_MANIFEST_DIR"))
+        .load()
+        .unwrap()
+        .text
+}
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(
-        divan::black_box(include_str!("../input1.txt",)),
-        200000000000000.0,
-        400000000000000.0,
-    )
-    .unwrap();
+aoc_utils::bench_part!(part1, day_24::part1::Part1);
+aoc_utils::bench_part!(part2, day_24::part2::Part2);
+
+// Every pair of hailstones is checked, so divan prints the hailstones per second, which
+// halves each time their number doubles.
+#[divan::bench(consts = [75, 150, 300])]
+fn part1_hailstones<const HAILSTONES: usize>(bencher: divan::Bencher) {
+    let input = input(1)
+        .lines()
+        .take(HAILSTONES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    bencher.counter(ItemsCount::new(HAILSTONES)).bench(|| {
+        day_24::part1::process(
+            divan::black_box(&input),
+            200000000000000.0,
+            400000000000000.0,
+        )
+        .unwrap();
+    });
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
+// The test area spans from MIN to twice as much along both axes, from the example's bounds
+// up to the real ones. Only the crossings inside it are checked for being in the past, so
+// with all the hailstones counted, the throughput drops as more crossings land in the area.
+#[divan::bench(consts = [7, 1000000, 200000000000000])]
+fn part1_test_area<const MIN: u64>(bencher: divan::Bencher) {
+    let input = input(1);
+    let hailstones = input.lines().count();
+    let params = Params::new().with("min", MIN).with("max", 2 * MIN);
+    bencher.counter(ItemsCount::new(hailstones)).bench(|| {
+        day_24::part1::Part1::run(divan::black_box(&input), &params).unwrap();
+    });
 }
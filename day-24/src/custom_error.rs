@@ -1,4 +1,5 @@
 
+use aoc_utils::{ParamError, ParseError};
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -7,4 +8,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParamError(#[from] ParamError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] ParseError),
 }
\ No newline at end of file
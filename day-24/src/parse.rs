@@ -0,0 +1,30 @@
+use aoc_utils::{point3d, token, ws, IResult, Point3D};
+use nom::sequence::separated_pair;
+
+// Parses a `px, py, pz @ vx, vy, vz` line into the position and the velocity
+pub fn hailstone(input: &str) -> IResult<'_, (Point3D, Point3D)> {
+    separated_pair(point3d, ws(token("@")), point3d)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_utils::parse_lines;
+
+    use super::*;
+
+    const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+                           18, 19, 22 @ -1, -1, -2
+                           20, 25, 34 @ -2, -2, -4
+                           12, 31, 28 @ -1, -2, -1
+                           20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let hailstones = parse_lines(EXAMPLE, hailstone)?;
+        let printed = hailstones
+            .iter()
+            .map(|(position, speed)| format!("{:2} @ {:2}", position, speed));
+        assert!(EXAMPLE.lines().map(str::trim).eq(printed));
+        Ok(())
+    }
+}
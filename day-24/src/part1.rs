@@ -1,64 +1,84 @@
-use crate::{
-    custom_error::AocError,
-    utils::{Line, Point3D},
-};
+use aoc_utils::{parse_lines, Line, Param, Params, Solution};
 
-#[tracing::instrument]
-pub fn process(input: &str, min: f64, max: f64) -> miette::Result<String, AocError> {
-    let mut lines = Vec::new();
+use crate::{custom_error::AocError, parse::hailstone};
+
+pub struct Part1;
 
-    for line in input.lines() {
-        let mut parts = line.trim().split('@');
-        let coords = parts.next().unwrap().trim();
-        let speeds = parts.next().unwrap().trim();
+impl Solution for Part1 {
+    const DAY: u8 = 24;
+    const PART: u8 = 1;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "min",
+            description: "lower bound of the test area along both axes",
+            default: "200000000000000",
+        },
+        Param {
+            name: "max",
+            description: "upper bound of the test area along both axes",
+            default: "400000000000000",
+        },
+    ];
 
-        let p1 = Point3D::from_str(coords).xy();
-        let speed = Point3D::from_str(speeds).xy();
-        let p2 = p1 + speed;
+    type Input = Vec<Line>;
+    type Error = AocError;
 
-        lines.push(Line::new(p1, p2));
+    fn parse(input: &str) -> Result<Vec<Line>, AocError> {
+        let lines = parse_lines(input, hailstone)?
+            .into_iter()
+            .map(|(position, speed)| {
+                let p1 = position.xy();
+                let p2 = p1 + speed.xy();
+                Line::new(p1, p2)
+            })
+            .collect();
+        Ok(lines)
     }
 
-    let coord_range = min..=max;
-
-    let mut count = 0;
-    for l1 in 0..lines.len() - 1 {
-        for l2 in l1 + 1..lines.len() {
-            let line1 = &lines[l1];
-            let line2 = &lines[l2];
-
-            if let Some(intersect) = line1.intersects(line2) {
-                if coord_range.contains(&intersect.x) && coord_range.contains(&intersect.y) {
-                    let v1 = line1.vector();
-                    let v2 = line1.p1.vector_to(&intersect);
-                    let in_the_past = v1.x * v2.x + v1.y * v2.y <= 0.0;
-                    if in_the_past {
-                        println!(
-                            "A={:?} intersects with B={:?} in the past for A",
-                            line1, line2
-                        );
-                        continue;
-                    }
+    fn solve(lines: Vec<Line>, params: &Params) -> Result<String, AocError> {
+        let min = params.get::<f64>("min")?;
+        let max = params.get::<f64>("max")?;
 
-                    let v1 = line2.vector();
-                    let v2 = line2.p1.vector_to(&intersect);
-                    let in_the_past = v1.x * v2.x + v1.y * v2.y <= 0.0;
-                    if in_the_past {
-                        println!(
-                            "A={:?} intersects with B={:?} in the past for B",
-                            line1, line2
-                        );
-                        continue;
-                    }
+        let coord_range = min..=max;
 
-                    println!("{:?} intersects with {:?} at {}", line1, line2, intersect);
-                    count += 1;
+        let mut count = 0;
+        for l1 in 0..lines.len().saturating_sub(1) {
+            for l2 in l1 + 1..lines.len() {
+                let line1 = &lines[l1];
+                let line2 = &lines[l2];
+
+                if let Some(intersect) = line1.intersects(line2) {
+                    if coord_range.contains(&intersect.x) && coord_range.contains(&intersect.y) {
+                        let v1 = line1.vector();
+                        let v2 = line1.p1.vector_to(&intersect);
+                        let in_the_past = v1.x * v2.x + v1.y * v2.y <= 0.0;
+                        if in_the_past {
+                            tracing::trace!(?line1, ?line2, "crossed in the past of the first");
+                            continue;
+                        }
+
+                        let v1 = line2.vector();
+                        let v2 = line2.p1.vector_to(&intersect);
+                        let in_the_past = v1.x * v2.x + v1.y * v2.y <= 0.0;
+                        if in_the_past {
+                            tracing::trace!(?line1, ?line2, "crossed in the past of the second");
+                            continue;
+                        }
+
+                        tracing::trace!(?line1, ?line2, %intersect, "crossed in the test area");
+                        count += 1;
+                    }
                 }
             }
         }
+
+        Ok(count.to_string())
     }
+}
 
-    Ok(count.to_string())
+#[tracing::instrument]
+pub fn process(input: &str, min: f64, max: f64) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new().with("min", min).with("max", max))
 }
 
 #[cfg(test)]
@@ -75,8 +95,12 @@ mod tests {
         assert_eq!("2", process(input, 7.0, 27.0)?);
         Ok(())
     }
-}
 
-// Submissions:
-// 21562 - too low
-// 29142 - correct
+    #[test]
+    fn test_parse_error() {
+        let input = "19, 13, 30 @ -2, 1, -2\n18, 19 @ -1, -1, -2\n";
+        let err = aoc_utils::expect_parse_error!(process(input, 7.0, 27.0));
+        assert_eq!("expected `,`, found `@`", err.message);
+        assert_eq!(miette::SourceSpan::from((30, 1)), err.span);
+    }
+}
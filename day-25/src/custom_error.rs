@@ -1,4 +1,5 @@
 
+use aoc_utils::{ParamError, ParseError};
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -7,4 +8,19 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParamError(#[from] ParamError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] ParseError),
+
+    #[error("day 25 has no part 2")]
+    #[diagnostic(
+        code(aoc::no_part2),
+        help("the last star is given for finishing all the other puzzles")
+    )]
+    NoPart2,
+}
@@ -2,97 +2,150 @@ use coupe::Partition as _;
 use sprs::CsMat;
 use std::collections::HashMap;
 
+use aoc_utils::{list1, name, parse_lines, token, IResult, Params, Solution};
+use nom::{
+    character::complete::space1,
+    error::context,
+    sequence::{preceded, separated_pair},
+};
+
 use crate::custom_error::AocError;
 
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut nodes: HashMap<String, usize> = HashMap::default();
-    let mut node_id = 0;
+pub struct Part1;
 
-    let mut adjacency = CsMat::empty(sprs::CSR, 0);
+impl Solution for Part1 {
+    const DAY: u8 = 25;
+    const PART: u8 = 1;
 
-    for line in input.lines() {
-        let mut parts = line.trim().split(':');
-        let component = parts.next().unwrap().trim().to_string();
-        let connected_to = parts.next().unwrap().trim().split(' ').collect::<Vec<_>>();
+    type Input = CsMat<i64>;
+    type Error = AocError;
 
-        for connection in connected_to {
-            let connection = connection.trim().to_string();
+    fn parse(input: &str) -> Result<CsMat<i64>, AocError> {
+        let mut nodes: HashMap<String, usize> = HashMap::default();
+        let mut node_id = 0;
 
-            if !nodes.contains_key(&component) {
-                nodes.insert(component.clone(), node_id);
-                node_id += 1;
-            }
+        let mut adjacency = CsMat::empty(sprs::CSR, 0);
 
-            if !nodes.contains_key(&connection) {
-                nodes.insert(connection.clone(), node_id);
-                node_id += 1;
-            }
+        for (component, connected_to) in parse_lines(input, connections)? {
+            let component = component.to_string();
+
+            for connection in connected_to {
+                let connection = connection.trim().to_string();
+
+                if !nodes.contains_key(&component) {
+                    nodes.insert(component.clone(), node_id);
+                    node_id += 1;
+                }
+
+                if !nodes.contains_key(&connection) {
+                    nodes.insert(connection.clone(), node_id);
+                    node_id += 1;
+                }
 
-            let source_id = nodes.get(&component).unwrap();
-            let target_id = nodes.get(&connection).unwrap();
+                let source_id = nodes.get(&component).unwrap();
+                let target_id = nodes.get(&connection).unwrap();
 
-            // Add edge to adjacency matrix (undirected)
-            adjacency.insert(*source_id, *target_id, 1);
-            adjacency.insert(*target_id, *source_id, 1);
+                // Add edge to adjacency matrix (undirected)
+                adjacency.insert(*source_id, *target_id, 1);
+                adjacency.insert(*target_id, *source_id, 1);
+            }
         }
+
+        Ok(adjacency)
     }
 
-    let node_count = nodes.len();
-    println!("Node count: {}", node_count);
+    fn solve(adjacency: CsMat<i64>, _params: &Params) -> Result<String, AocError> {
+        let node_count = adjacency.rows();
+        tracing::debug!(node_count, "built the graph");
 
-    // Assign all nodes to one side
-    let mut partition = vec![0; node_count];
+        // Assign all nodes to one side
+        let mut partition = vec![0; node_count];
 
-    // Flip half of the nodes to the other side
-    for i in 0..node_count / 2 {
-        partition[i] = 1;
-    }
+        // Flip half of the nodes to the other side
+        partition[..node_count / 2].fill(1);
+
+        // All nodes have the same weight
+        let weights = vec![1.; node_count];
+
+        // Run the partitioning algorithm
+        coupe::FiducciaMattheyses {
+            max_imbalance: Some(0.25),
+            max_bad_move_in_a_row: 10000,
+            ..Default::default()
+        }
+        .partition(&mut partition, (adjacency.view(), &weights))
+        .unwrap();
 
-    // All nodes have the same weight
-    let weights = vec![1.; node_count];
+        let left_count = partition.iter().filter(|&&x| x == 0).count();
+        let right_count = partition.iter().filter(|&&x| x == 1).count();
+        tracing::debug!(left_count, right_count, "partitioned");
 
-    // Run the partitioning algorithm
-    coupe::FiducciaMattheyses {
-        max_imbalance: Some(0.25),
-        max_bad_move_in_a_row: 10000,
-        ..Default::default()
+        let result = left_count * right_count;
+        Ok(result.to_string())
     }
-    .partition(&mut partition, (adjacency.view(), &weights))
-    .unwrap();
+}
 
-    let left_count = partition.iter().filter(|&&x| x == 0).count();
-    let right_count = partition.iter().filter(|&&x| x == 1).count();
-    println!("Left: {}, Right: {}", left_count, right_count);
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part1::run(input, &Params::new())
+}
 
-    let result = left_count * right_count;
-    Ok(result.to_string())
+// A `name: other1 other2 ...` line: the component and the ones it's connected to
+fn connections(input: &str) -> IResult<'_, (&str, Vec<&str>)> {
+    let connected_to = context(
+        "a list of components",
+        preceded(space1, list1(space1, name)),
+    );
+    separated_pair(name, token(":"), connected_to)(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "jqt: rhn xhk nvd
+                           rsh: frs pzl lsr
+                           xhk: hfx
+                           cmg: qnr nvd lhk bvb
+                           rhn: xhk bvb hfx
+                           bvb: xhk hfx
+                           pzl: lsr hfx nvd
+                           qnr: nvd
+                           ntq: jqt hfx bvb xhk
+                           nvd: lhk
+                           lsr: lhk
+                           rzs: qnr cmg lsr rsh
+                           frs: qnr lhk lsr";
+
     #[test]
     fn test_process() -> miette::Result<()> {
         tracing_subscriber::fmt().init();
 
-        let input = "jqt: rhn xhk nvd
-                     rsh: frs pzl lsr
-                     xhk: hfx
-                     cmg: qnr nvd lhk bvb
-                     rhn: xhk bvb hfx
-                     bvb: xhk hfx
-                     pzl: lsr hfx nvd
-                     qnr: nvd
-                     ntq: jqt hfx bvb xhk
-                     nvd: lhk
-                     lsr: lhk
-                     rzs: qnr cmg lsr rsh
-                     frs: qnr lhk lsr";
-        assert_eq!("54", process(input)?);
+        assert_eq!("54", process(EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round_trip() -> miette::Result<()> {
+        let lines = parse_lines(EXAMPLE, connections)?;
+        let printed = lines
+            .iter()
+            .map(|(component, connected_to)| format!("{}: {}", component, connected_to.join(" ")));
+        assert!(EXAMPLE.lines().map(str::trim).eq(printed));
         Ok(())
     }
-}
 
-// Submissions:
-// 614655 - correct
+    #[test]
+    fn test_parse_error() {
+        let err = aoc_utils::expect_parse_error!(process("jqt: rhn\nxhk hfx\n"));
+        assert_eq!("expected `:`, found `hfx`", err.message);
+        assert_eq!(miette::SourceSpan::from((13, 3)), err.span);
+
+        let err = aoc_utils::expect_parse_error!(process("jqt: rhn\nxhk:\n"));
+        assert_eq!(
+            "expected a list of components, found the end of the line",
+            err.message
+        );
+        assert_eq!(miette::SourceSpan::from((13, 0)), err.span);
+    }
+}
@@ -1,8 +1,28 @@
+use aoc_utils::{Params, Solution};
+
 use crate::custom_error::AocError;
 
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 25;
+    const PART: u8 = 2;
+
+    type Input = ();
+    type Error = AocError;
+
+    fn parse(_input: &str) -> Result<(), AocError> {
+        Ok(())
+    }
+
+    fn solve(_input: (), _params: &Params) -> Result<String, AocError> {
+        Err(AocError::NoPart2)
+    }
+}
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    Part2::run(input, &Params::new())
 }
 
 #[cfg(test)]
@@ -10,9 +30,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "";
-        assert_eq!("", process(input)?);
-        Ok(())
+    fn test_process() {
+        assert!(matches!(process(""), Err(AocError::NoPart2)));
     }
 }